@@ -4,6 +4,8 @@ use std::num::ParseIntError;
 use std::str::ParseBoolError;
 use std::string::FromUtf8Error;
 
+use bleu_merkle::MerkleError;
+use bleu_net::NetError;
 use hex::FromHexError;
 use lettre::transport::smtp;
 
@@ -23,6 +25,29 @@ pub enum ExpectedError {
     RocksDBError(String),
     JsonRpcError(String),
     RetryFailError(String),
+    VerificationError(String),
+}
+
+impl ExpectedError {
+    pub fn variant(&self) -> &'static str {
+        match self {
+            ExpectedError::TypeError(_) => "TypeError",
+            ExpectedError::NoneError(_) => "NoneError",
+            ExpectedError::ProcessError(_) => "ProcessError",
+            ExpectedError::InvalidError(_) => "InvalidError",
+            ExpectedError::RequestError(_) => "RequestError",
+            ExpectedError::ParsingError(_) => "ParsingError",
+            ExpectedError::ChannelError(_) => "ChannelError",
+            ExpectedError::FilterError(_) => "FilterError",
+            ExpectedError::BlockHeightError(_) => "BlockHeightError",
+            ExpectedError::PostgresError(_) => "PostgresError",
+            ExpectedError::IoError(_) => "IoError",
+            ExpectedError::RocksDBError(_) => "RocksDBError",
+            ExpectedError::JsonRpcError(_) => "JsonRpcError",
+            ExpectedError::RetryFailError(_) => "RetryFailError",
+            ExpectedError::VerificationError(_) => "VerificationError",
+        }
+    }
 }
 
 impl From<smtp::Error> for ExpectedError {
@@ -100,6 +125,21 @@ impl From<FromHexError> for ExpectedError {
     }
 }
 
+impl From<MerkleError> for ExpectedError {
+    fn from(err: MerkleError) -> Self {
+        match err {
+            MerkleError::HexError(_) => ExpectedError::ParsingError(err.to_string()),
+            _ => ExpectedError::InvalidError(err.to_string()),
+        }
+    }
+}
+
+impl From<NetError> for ExpectedError {
+    fn from(err: NetError) -> Self {
+        ExpectedError::InvalidError(err.to_string())
+    }
+}
+
 impl From<jsonrpc_core::Error> for ExpectedError {
     fn from(err: jsonrpc_core::Error) -> Self {
         ExpectedError::JsonRpcError(err.to_string())
@@ -123,6 +163,7 @@ impl Display for ExpectedError {
             ExpectedError::RocksDBError(err) => write!(f, "{}", err),
             ExpectedError::JsonRpcError(err) => write!(f, "{}", err),
             ExpectedError::RetryFailError(err) => write!(f, "{}", err),
+            ExpectedError::VerificationError(err) => write!(f, "{}", err),
         }
     }
 }
@@ -6,3 +6,15 @@ pub mod postgres;
 pub mod convert;
 pub mod subscribe;
 pub mod error;
+pub mod merkle;
+pub mod verify;
+pub mod abi;
+pub mod cross_domain;
+pub mod bridge;
+pub mod gas_oracle;
+pub mod stat;
+pub mod time;
+pub mod metrics;
+pub mod email;
+pub mod webhook;
+pub mod config;
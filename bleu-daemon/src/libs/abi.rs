@@ -0,0 +1,110 @@
+use primitive_types::U256;
+use serde_json::{Map, Value};
+
+use crate::error::error::ExpectedError;
+use crate::libs::merkle::{Bytes32, hex_to_bytes};
+use crate::libs::opt::opt_to_result;
+use crate::libs::serde::get_array;
+
+pub type Address = [u8; 20];
+
+pub fn get_topic(log: &Map<String, Value>, index: usize) -> Result<String, ExpectedError> {
+    let topics = get_array(log, "topics")?;
+    let topic = opt_to_result(opt_to_result(topics.get(index))?.as_str())?;
+    Ok(topic.to_lowercase())
+}
+
+pub fn topic_to_address(topic: &str) -> Result<Address, ExpectedError> {
+    Ok(word_to_address(&read_word(&hex_to_bytes(topic)?, 0)?))
+}
+
+pub fn read_word(data: &[u8], offset: usize) -> Result<Bytes32, ExpectedError> {
+    let slice = match data.get(offset..offset + 32) {
+        Some(slice) => slice,
+        None => return Err(ExpectedError::ParsingError(format!("abi data is too short! offset={}, length={}", offset, data.len()))),
+    };
+    let mut word = [0u8; 32];
+    word.copy_from_slice(slice);
+    Ok(word)
+}
+
+pub fn read_address(data: &[u8], offset: usize) -> Result<Address, ExpectedError> {
+    Ok(word_to_address(&read_word(data, offset)?))
+}
+
+pub fn read_uint(data: &[u8], offset: usize) -> Result<U256, ExpectedError> {
+    Ok(U256::from_big_endian(&read_word(data, offset)?))
+}
+
+/*
+ * Reads a dynamic `bytes` argument whose head word is at `offset`.
+ */
+pub fn read_bytes(data: &[u8], offset: usize) -> Result<Vec<u8>, ExpectedError> {
+    let start = to_usize(read_uint(data, offset)?)?;
+    let length = to_usize(read_uint(data, start)?)?;
+    match data.get(start + 32..start + 32 + length) {
+        Some(bytes) => Ok(bytes.to_vec()),
+        None => Err(ExpectedError::ParsingError(format!("abi bytes is out of range! start={}, length={}", start, length))),
+    }
+}
+
+pub fn address_to_word(address: &Address) -> Bytes32 {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(address);
+    word
+}
+
+pub fn u256_to_word(value: U256) -> Bytes32 {
+    let mut word = [0u8; 32];
+    value.to_big_endian(&mut word);
+    word
+}
+
+pub fn address_to_hex(address: &Address) -> String {
+    format!("0x{}", hex::encode(address))
+}
+
+pub fn bytes_to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn word_to_address(word: &Bytes32) -> Address {
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&word[12..]);
+    address
+}
+
+fn to_usize(value: U256) -> Result<usize, ExpectedError> {
+    if value > U256::from(usize::MAX) {
+        return Err(ExpectedError::ParsingError(format!("abi value is out of range! value={}", value)));
+    }
+    Ok(value.as_usize())
+}
+
+#[cfg(test)]
+mod abi {
+    use primitive_types::U256;
+
+    use crate::libs::abi::{address_to_hex, read_address, read_bytes, read_uint, topic_to_address};
+    use crate::libs::merkle::hex_to_bytes;
+
+    #[test]
+    fn read_bytes_test() {
+        let data = hex_to_bytes("0x\
+            0000000000000000000000000000000000000000000000000000000000000040\
+            0000000000000000000000000000000000000000000000000000000000000001\
+            0000000000000000000000000000000000000000000000000000000000000003\
+            abcdef0000000000000000000000000000000000000000000000000000000000").unwrap();
+        assert_eq!(read_bytes(&data, 0).unwrap(), vec![0xab, 0xcd, 0xef]);
+        assert_eq!(read_uint(&data, 32).unwrap(), U256::from(1));
+        assert!(read_bytes(&data, 32).is_err());
+    }
+
+    #[test]
+    fn read_address_test() {
+        let topic = "0x000000000000000000000000deaddeaddeaddeaddeaddeaddeaddeaddead0000";
+        let address = topic_to_address(topic).unwrap();
+        assert_eq!(address_to_hex(&address), "0xdeaddeaddeaddeaddeaddeaddeaddeaddead0000");
+        assert_eq!(read_address(&hex_to_bytes(topic).unwrap(), 0).unwrap(), address);
+    }
+}
@@ -0,0 +1,168 @@
+use serde_json::{Map, Value};
+
+use crate::error::error::ExpectedError;
+use crate::libs::abi::{address_to_hex, bytes_to_hex, get_topic, read_address, read_bytes, read_uint, topic_to_address};
+use crate::libs::merkle::hex_to_bytes;
+use crate::libs::serde::get_str;
+
+pub const L2_STANDARD_BRIDGE: &str = "0x4200000000000000000000000000000000000010";
+pub const TRANSACTION_ENQUEUED_TOPIC: &str = "0x4b388aecf9fa6cc92253704e5975a6129a4f735bdbd99567df4ed0094ee4ceb5";
+pub const ETH_DEPOSIT_INITIATED_TOPIC: &str = "0x35d79ab81f2b2017e19afb5c5571778877782d7a8786f5907f93b0f4702f4f23";
+pub const ERC20_DEPOSIT_INITIATED_TOPIC: &str = "0x718594027abd4eaed59f95162563e0cc6d0e8d5b86b1c7be8b1b0ac3343d0396";
+pub const DEPOSIT_FINALIZED_TOPIC: &str = "0xb0444523268717a02698be47d0803aa7468c00acbed2f8bd93a0459cde61dd89";
+pub const WITHDRAWAL_INITIATED_TOPIC: &str = "0x73d170910aba9e6d50b102db522b1dbcd796216f5128b445aa2135272886497e";
+const ETH_L1_TOKEN: &str = "0x0000000000000000000000000000000000000000";
+const ETH_L2_TOKEN: &str = "0xdeaddeaddeaddeaddeaddeaddeaddeaddead0000";
+
+pub fn is_l2_bridge_event(log: &Map<String, Value>) -> Result<bool, ExpectedError> {
+    let address = get_str(log, "address")?;
+    let topic0 = get_topic(log, 0).unwrap_or_default();
+    Ok(address.to_lowercase() == L2_STANDARD_BRIDGE && (topic0 == DEPOSIT_FINALIZED_TOPIC || topic0 == WITHDRAWAL_INITIATED_TOPIC))
+}
+
+pub fn is_l1_bridge_event(log: &Map<String, Value>, bridge_address: &str) -> Result<bool, ExpectedError> {
+    let address = get_str(log, "address")?;
+    let topic0 = get_topic(log, 0).unwrap_or_default();
+    Ok(address.to_lowercase() == bridge_address.to_lowercase() && (topic0 == ETH_DEPOSIT_INITIATED_TOPIC || topic0 == ERC20_DEPOSIT_INITIATED_TOPIC))
+}
+
+/*
+ * The topic 3 of `TransactionEnqueued` is the queue index, so it is only read from the logs of the CTC.
+ */
+pub fn is_enqueue_event(log: &Map<String, Value>, ctc_address: &str) -> Result<bool, ExpectedError> {
+    let address = get_str(log, "address")?;
+    let topic0 = get_topic(log, 0).unwrap_or_default();
+    Ok(address.to_lowercase() == ctc_address.to_lowercase() && topic0 == TRANSACTION_ENQUEUED_TOPIC)
+}
+
+/*
+ * ETHDepositInitiated(address indexed from, address indexed to, uint256 amount, bytes data)
+ * ERC20DepositInitiated, DepositFinalized and WithdrawalInitiated share
+ * (address indexed l1Token, address indexed l2Token, address indexed from, address to, uint256 amount, bytes data)
+ */
+pub fn bridge_transfer_converter(log: &Map<String, Value>) -> Result<Map<String, Value>, ExpectedError> {
+    let topic0 = get_topic(log, 0)?;
+    let data = hex_to_bytes(get_str(log, "data")?)?;
+    let (event_name, transfer_type) = match topic0.as_str() {
+        ETH_DEPOSIT_INITIATED_TOPIC => ("ETHDepositInitiated", "deposit"),
+        ERC20_DEPOSIT_INITIATED_TOPIC => ("ERC20DepositInitiated", "deposit"),
+        DEPOSIT_FINALIZED_TOPIC => ("DepositFinalized", "deposit"),
+        WITHDRAWAL_INITIATED_TOPIC => ("WithdrawalInitiated", "withdrawal"),
+        _ => return Err(ExpectedError::InvalidError(format!("log is not bridge event! topic={}", topic0))),
+    };
+    let (l1_token, l2_token, from_address, to_address, amount, extra_data) = if topic0 == ETH_DEPOSIT_INITIATED_TOPIC {
+        (String::from(ETH_L1_TOKEN), String::from(ETH_L2_TOKEN),
+         address_to_hex(&topic_to_address(&get_topic(log, 1)?)?), address_to_hex(&topic_to_address(&get_topic(log, 2)?)?),
+         read_uint(&data, 0)?, read_bytes(&data, 32)?)
+    } else {
+        (address_to_hex(&topic_to_address(&get_topic(log, 1)?)?), address_to_hex(&topic_to_address(&get_topic(log, 2)?)?),
+         address_to_hex(&topic_to_address(&get_topic(log, 3)?)?), address_to_hex(&read_address(&data, 0)?),
+         read_uint(&data, 32)?, read_bytes(&data, 64)?)
+    };
+
+    let mut converted_log = log.clone();
+    converted_log.insert(String::from("event_name"), Value::String(String::from(event_name)));
+    converted_log.insert(String::from("transfer_type"), Value::String(String::from(transfer_type)));
+    converted_log.insert(String::from("l1_token"), Value::String(l1_token));
+    converted_log.insert(String::from("l2_token"), Value::String(l2_token));
+    converted_log.insert(String::from("from_address"), Value::String(from_address));
+    converted_log.insert(String::from("to_address"), Value::String(to_address));
+    converted_log.insert(String::from("amount"), Value::String(amount.to_string()));
+    converted_log.insert(String::from("extra_data"), Value::String(bytes_to_hex(&extra_data)));
+    Ok(converted_log)
+}
+
+#[cfg(test)]
+mod bridge {
+    use serde_json::json;
+
+    use crate::libs::bridge::{bridge_transfer_converter, DEPOSIT_FINALIZED_TOPIC, ERC20_DEPOSIT_INITIATED_TOPIC, ETH_DEPOSIT_INITIATED_TOPIC, is_enqueue_event, is_l1_bridge_event, is_l2_bridge_event, L2_STANDARD_BRIDGE, TRANSACTION_ENQUEUED_TOPIC, WITHDRAWAL_INITIATED_TOPIC};
+    use crate::libs::merkle::{bytes32_to_hex, keccak256};
+
+    #[test]
+    fn event_topic_test() {
+        assert_eq!(bytes32_to_hex(&keccak256("TransactionEnqueued(address,address,uint256,bytes,uint256,uint256)".as_bytes())), TRANSACTION_ENQUEUED_TOPIC);
+        assert_eq!(bytes32_to_hex(&keccak256("ETHDepositInitiated(address,address,uint256,bytes)".as_bytes())), ETH_DEPOSIT_INITIATED_TOPIC);
+        assert_eq!(bytes32_to_hex(&keccak256("ERC20DepositInitiated(address,address,address,address,uint256,bytes)".as_bytes())), ERC20_DEPOSIT_INITIATED_TOPIC);
+        assert_eq!(bytes32_to_hex(&keccak256("DepositFinalized(address,address,address,address,uint256,bytes)".as_bytes())), DEPOSIT_FINALIZED_TOPIC);
+        assert_eq!(bytes32_to_hex(&keccak256("WithdrawalInitiated(address,address,address,address,uint256,bytes)".as_bytes())), WITHDRAWAL_INITIATED_TOPIC);
+    }
+
+    #[test]
+    fn enqueue_event_test() {
+        let log = json!({
+            "address": "0x5E4e65926BA27467555EB562121fac00D24E9dD2",
+            "topics": [
+                TRANSACTION_ENQUEUED_TOPIC,
+                "0x00000000000000000000000099c9fc46f92e8a1c0dec1b1747d010903e884be1",
+                "0x0000000000000000000000004200000000000000000000000000000000000007",
+                "0x0000000000000000000000000000000000000000000000000000000000000010"
+            ],
+            "data": "0x"
+        });
+        let log_map = log.as_object().unwrap();
+        assert!(is_enqueue_event(log_map, "0x5e4e65926ba27467555eb562121fac00d24e9dd2").unwrap());
+        assert!(!is_enqueue_event(log_map, "0x99c9fc46f92e8a1c0dec1b1747d010903e884be1").unwrap());
+        assert!(!is_l1_bridge_event(log_map, "0x5e4e65926ba27467555eb562121fac00d24e9dd2").unwrap());
+    }
+
+    #[test]
+    fn eth_deposit_converter_test() {
+        let log = json!({
+            "address": "0x99C9fc46f92E8a1c0deC1b1747d010903E884bE1",
+            "topics": [
+                ETH_DEPOSIT_INITIATED_TOPIC,
+                "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "0x0000000000000000000000000000000000000000000000000000000000000002"
+            ],
+            "data": "0x\
+                0000000000000000000000000000000000000000000000000de0b6b3a7640000\
+                0000000000000000000000000000000000000000000000000000000000000040\
+                0000000000000000000000000000000000000000000000000000000000000000",
+            "logIndex": "0x3"
+        });
+        let log_map = log.as_object().unwrap();
+        assert!(is_l1_bridge_event(log_map, "0x99c9fc46f92e8a1c0dec1b1747d010903e884be1").unwrap());
+        assert!(!is_l2_bridge_event(log_map).unwrap());
+
+        let converted = bridge_transfer_converter(log_map).unwrap();
+        assert_eq!(converted.get("event_name").unwrap(), "ETHDepositInitiated");
+        assert_eq!(converted.get("transfer_type").unwrap(), "deposit");
+        assert_eq!(converted.get("l2_token").unwrap(), "0xdeaddeaddeaddeaddeaddeaddeaddeaddead0000");
+        assert_eq!(converted.get("from_address").unwrap(), "0x0000000000000000000000000000000000000001");
+        assert_eq!(converted.get("to_address").unwrap(), "0x0000000000000000000000000000000000000002");
+        assert_eq!(converted.get("amount").unwrap(), "1000000000000000000");
+        assert_eq!(converted.get("extra_data").unwrap(), "0x");
+        assert_eq!(converted.get("logIndex").unwrap(), "0x3");
+    }
+
+    #[test]
+    fn withdrawal_converter_test() {
+        let log = json!({
+            "address": L2_STANDARD_BRIDGE,
+            "topics": [
+                WITHDRAWAL_INITIATED_TOPIC,
+                "0x000000000000000000000000000000000000000000000000000000000000000a",
+                "0x000000000000000000000000000000000000000000000000000000000000000b",
+                "0x000000000000000000000000000000000000000000000000000000000000000c"
+            ],
+            "data": "0x\
+                000000000000000000000000000000000000000000000000000000000000000d\
+                0000000000000000000000000000000000000000000000000000000000000064\
+                0000000000000000000000000000000000000000000000000000000000000060\
+                0000000000000000000000000000000000000000000000000000000000000001\
+                ff00000000000000000000000000000000000000000000000000000000000000"
+        });
+        let log_map = log.as_object().unwrap();
+        assert!(is_l2_bridge_event(log_map).unwrap());
+
+        let converted = bridge_transfer_converter(log_map).unwrap();
+        assert_eq!(converted.get("transfer_type").unwrap(), "withdrawal");
+        assert_eq!(converted.get("l1_token").unwrap(), "0x000000000000000000000000000000000000000a");
+        assert_eq!(converted.get("l2_token").unwrap(), "0x000000000000000000000000000000000000000b");
+        assert_eq!(converted.get("from_address").unwrap(), "0x000000000000000000000000000000000000000c");
+        assert_eq!(converted.get("to_address").unwrap(), "0x000000000000000000000000000000000000000d");
+        assert_eq!(converted.get("amount").unwrap(), "100");
+        assert_eq!(converted.get("extra_data").unwrap(), "0xff");
+    }
+}
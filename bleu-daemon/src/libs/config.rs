@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, RwLock};
+
+use appbase::prelude::*;
+use lazy_static::lazy_static;
+
+use crate::error::error::ExpectedError;
+use crate::libs::opt::{get_value_str, resolve_value};
+use crate::types::config::Config;
+
+lazy_static! {
+    static ref CONFIG: RwLock<Option<Arc<Config>>> = RwLock::new(None);
+}
+
+/*
+ * SHOULD be called after `APP.init()`, because options are parsed from the command line and `config.toml` there.
+ */
+pub fn load() -> Result<(), ExpectedError> {
+    let config = Config::load(&get_value_str)?;
+    *CONFIG.write().unwrap() = Some(Arc::new(config));
+    Ok(())
+}
+
+pub fn get() -> Arc<Config> {
+    CONFIG.read().unwrap().clone().expect("config is not loaded!")
+}
+
+/*
+ * Reads `config_file` again, and the reloaded config replaces the current one only when it is valid.
+ * Options missing in the file fall back to the command line, and environment overrides still apply.
+ * The options requiring a restart keep their running values, so that the swapped config matches what is running.
+ * Returns the changed options applied on the fly and the ones requiring a restart.
+ */
+pub fn reload(config_file: &str) -> Result<(Vec<String>, Vec<String>), ExpectedError> {
+    let file_values = read_config_file(config_file)?;
+    let (config, changes) = get().reload(&|key| resolve_value(key, file_values.get(key).cloned().or_else(|| APP.options.value_of(key))))?;
+    *CONFIG.write().unwrap() = Some(Arc::new(config));
+    Ok(changes)
+}
+
+fn read_config_file(config_file: &str) -> Result<HashMap<String, String>, ExpectedError> {
+    let toml_str = fs::read_to_string(config_file)?;
+    let sections = toml_str.parse::<toml::Value>().map_err(|err| ExpectedError::ParsingError(format!("failed to parse config file! file={}, error={}", config_file, err)))?;
+    let mut values = HashMap::new();
+    for (section, table) in sections.as_table().into_iter().flatten() {
+        for (key, value) in table.as_table().into_iter().flatten() {
+            let value = match value {
+                toml::Value::String(value) => value.clone(),
+                toml::Value::Integer(value) => value.to_string(),
+                toml::Value::Float(value) => value.to_string(),
+                toml::Value::Boolean(value) => value.to_string(),
+                _ => continue,
+            };
+            values.insert(format!("{}::{}", section, key), value);
+        }
+    }
+    Ok(values)
+}
@@ -1,5 +1,6 @@
+use primitive_types::U256;
 use regex::Regex;
-use serde_json::{Map, Value};
+use serde_json::Value;
 
 use crate::error::error::ExpectedError;
 use crate::libs::opt::opt_to_result;
@@ -17,47 +18,53 @@ pub fn hex_to_decimal(hex_str: String) -> Result<String, ExpectedError> {
     }
 }
 
-pub fn hex_to_decimal_converter(origin_map: &Map<String, Value>, target_keys: Vec<&str>) -> Result<Map<String, Value>, ExpectedError> {
-    let mut cloned_map = origin_map.clone();
-    for key in target_keys.into_iter() {
-        if let true = cloned_map.contains_key(key) {
-            let value = opt_to_result(cloned_map.get(key))?;
-            if value.is_string() {
-                let origin_string = String::from(opt_to_result(value.as_str())?);
-                if is_hex_string(&origin_string) {
-                    let converted = hex_to_decimal(origin_string)?;
-                    cloned_map.insert(key.to_owned(), Value::String(converted));
-                }
-            }
-        }
-    }
-    Ok(cloned_map)
-}
-
 fn is_hex_string(hex_str: &str) -> bool {
     let regex = Regex::new(r"^(0[xX])?[A-Fa-f0-9]+$").unwrap();
     regex.is_match(hex_str)
 }
 
-pub fn number_to_string_convert(origin_map: &Map<String, Value>, target_keys: Vec<&str>) -> Result<Map<String, Value>, ExpectedError> {
-    let mut cloned_map = origin_map.clone();
-    for key in target_keys.into_iter() {
-        if let true = cloned_map.contains_key(key) {
-            let value = opt_to_result(cloned_map.get(key))?;
-            if value.is_number() {
-                let converted_value = value.to_string();
-                cloned_map.insert(key.to_owned(), Value::String(converted_value));
-            }
-        }
+/*
+ * Reads a json number, a decimal string or a 0x prefixed hex string as an unsigned integer.
+ */
+pub fn value_to_u256(value: &Value) -> Result<U256, ExpectedError> {
+    let parsed = match value {
+        Value::Number(number) => number.as_u64().map(U256::from),
+        Value::String(s) if s.starts_with("0x") || s.starts_with("0X") => U256::from_str_radix(&s[2..], 16).ok().filter(|_| s.len() > 2),
+        Value::String(s) => U256::from_dec_str(s).ok(),
+        _ => None,
+    };
+    opt_to_result(parsed).map_err(|_| ExpectedError::InvalidError(format!("value is not unsigned integer! value={}", value)))
+}
+
+pub fn value_to_u64(value: &Value) -> Result<u64, ExpectedError> {
+    let u256 = value_to_u256(value)?;
+    if u256 > U256::from(i64::MAX) {
+        return Err(ExpectedError::InvalidError(format!("value is out of bigint range! value={}", value)));
+    }
+    Ok(u256.as_u64())
+}
+
+/*
+ * Checks a 0x prefixed hex string of `bytes` length (any even length if None), and lowercases it.
+ */
+pub fn value_to_hex(value: &Value, bytes: Option<usize>) -> Result<String, ExpectedError> {
+    let hex_str = opt_to_result(value.as_str()).map_err(|_| ExpectedError::InvalidError(format!("value is not hex string! value={}", value)))?;
+    let digits = hex_str.strip_prefix("0x").or_else(|| hex_str.strip_prefix("0X")).unwrap_or("-");
+    let valid_length = match bytes {
+        Some(bytes) => digits.len() == bytes * 2,
+        None => digits.len() % 2 == 0,
+    };
+    if !valid_length || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ExpectedError::InvalidError(format!("value is not hex string of expected length! value={}, bytes={:?}", value, bytes)));
     }
-    Ok(cloned_map)
+    Ok(hex_str.to_lowercase())
 }
 
 #[cfg(test)]
 mod number {
-    use serde_json::{Map, Value};
+    use serde_json::json;
 
-    use crate::libs::convert::{hex_to_decimal, hex_to_decimal_converter, number_to_string_convert};
+    use crate::libs::convert::{hex_to_decimal, value_to_hex, value_to_u256, value_to_u64};
 
     #[test]
     fn hex_to_decimal_test() {
@@ -78,30 +85,25 @@ mod number {
     }
 
     #[test]
-    fn hex_to_decimal_converter_test() {
-        let mut test_map = Map::new();
-        test_map.insert(String::from("key1"), Value::String(String::from("0x11")));
-        test_map.insert(String::from("key2"), Value::String(String::from("0x22")));
-        test_map.insert(String::from("key3"), Value::String(String::from("bleu-daemon")));
-        test_map.insert(String::from("key4"), Value::Null);
-
-        let converted_map = hex_to_decimal_converter(&test_map, vec!["key1", "key3", "key4"]).unwrap();
-        assert_eq!(converted_map.get("key1").unwrap(), "17");
-        assert_eq!(converted_map.get("key2").unwrap(), "0x22");
-        assert_eq!(converted_map.get("key3").unwrap(), "bleu-daemon");
-        assert_eq!(converted_map.get("key4").unwrap().clone(), Value::Null);
+    fn value_to_u256_test() {
+        assert_eq!(value_to_u256(&json!("0x16345785d8a0000")).unwrap().to_string(), "100000000000000000");
+        assert_eq!(value_to_u256(&json!("100000000000000000")).unwrap().to_string(), "100000000000000000");
+        assert_eq!(value_to_u256(&json!(17)).unwrap().as_u64(), 17);
+        assert!(value_to_u256(&json!("0x")).is_err());
+        assert!(value_to_u256(&json!("-1")).is_err());
+        assert!(value_to_u256(&json!(true)).is_err());
+        assert_eq!(value_to_u64(&json!("0x11")).unwrap(), 17);
+        assert!(value_to_u64(&json!("0x8000000000000000")).is_err());
     }
 
     #[test]
-    fn number_to_string_convert_test() {
-        let mut test_map = Map::new();
-        test_map.insert(String::from("key1"), Value::from(1));
-        test_map.insert(String::from("key2"), Value::String("a".to_string()));
-        test_map.insert(String::from("key3"), Value::from(1));
-
-        let converted_map = number_to_string_convert(&test_map, vec!["key1", "key2"]).unwrap();
-        assert_eq!(converted_map.get("key1").unwrap(), "1");
-        assert_eq!(converted_map.get("key2").unwrap(), "a");
-        assert_eq!(converted_map.get("key3").unwrap().clone(), Value::from(1));
+    fn value_to_hex_test() {
+        let address = "0x4200000000000000000000000000000000000006";
+        assert_eq!(value_to_hex(&json!(address.to_uppercase().replace("0X", "0x")), Some(20)).unwrap(), address);
+        assert!(value_to_hex(&json!(address), Some(32)).is_err());
+        assert_eq!(value_to_hex(&json!("0x"), None).unwrap(), "0x");
+        assert!(value_to_hex(&json!("0x123"), None).is_err());
+        assert!(value_to_hex(&json!("1234"), None).is_err());
+        assert!(value_to_hex(&json!("0xzz"), None).is_err());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,169 @@
+use primitive_types::U256;
+use serde_json::{Map, Value};
+
+use crate::error::error::ExpectedError;
+use crate::libs::abi::{Address, address_to_hex, address_to_word, bytes_to_hex, get_topic, read_address, read_bytes, read_uint, topic_to_address, u256_to_word};
+use crate::libs::merkle::{Bytes32, bytes32_to_hex, hex_to_bytes, keccak256};
+use crate::libs::serde::get_str;
+
+pub const L2_CROSS_DOMAIN_MESSENGER: &str = "0x4200000000000000000000000000000000000007";
+pub const SENT_MESSAGE_TOPIC: &str = "0xcb0f7ffd78f9aee47a248fae8db181db6eee833039123e026dcbff529522e52a";
+pub const RELAYED_MESSAGE_TOPIC: &str = "0x4641df4a962071e12719d8c8c8e5ac7fc4d97b927346a3d7a335b1f7517e133c";
+pub const FAILED_RELAYED_MESSAGE_TOPIC: &str = "0x99d0e048484baa1b1540b1367cb128acd7ab2946d1ed91ec10e3c85e4bf51b8f";
+const RELAY_MESSAGE_SELECTOR: [u8; 4] = [0xcb, 0xd4, 0xec, 0xe9];
+
+pub fn is_sent_message(log: &Map<String, Value>) -> Result<bool, ExpectedError> {
+    let address = get_str(log, "address")?;
+    let topic0 = get_topic(log, 0).unwrap_or_default();
+    Ok(address.to_lowercase() == L2_CROSS_DOMAIN_MESSENGER && topic0 == SENT_MESSAGE_TOPIC)
+}
+
+/*
+ * SentMessage(address indexed target, address sender, bytes message, uint256 messageNonce, uint256 gasLimit)
+ */
+pub fn sent_message_converter(log: &Map<String, Value>) -> Result<Map<String, Value>, ExpectedError> {
+    let target = topic_to_address(&get_topic(log, 1)?)?;
+    let data = hex_to_bytes(get_str(log, "data")?)?;
+    let sender = read_address(&data, 0)?;
+    let message = read_bytes(&data, 32)?;
+    let message_nonce = read_uint(&data, 64)?;
+    let gas_limit = read_uint(&data, 96)?;
+    let msg_hash = hash_cross_domain_message(&target, &sender, &message, message_nonce);
+
+    let mut converted_log = log.clone();
+    converted_log.insert(String::from("event_name"), Value::String(String::from("SentMessage")));
+    converted_log.insert(String::from("msg_hash"), Value::String(bytes32_to_hex(&msg_hash)));
+    converted_log.insert(String::from("target"), Value::String(address_to_hex(&target)));
+    converted_log.insert(String::from("sender"), Value::String(address_to_hex(&sender)));
+    converted_log.insert(String::from("message"), Value::String(bytes_to_hex(&message)));
+    converted_log.insert(String::from("message_nonce"), Value::String(message_nonce.to_string()));
+    converted_log.insert(String::from("gas_limit"), Value::String(gas_limit.to_string()));
+    Ok(converted_log)
+}
+
+/*
+ * RelayedMessage(bytes32 indexed msgHash), FailedRelayedMessage(bytes32 indexed msgHash)
+ */
+pub fn relayed_message_converter(log: &Map<String, Value>, block_timestamp: String) -> Result<Map<String, Value>, ExpectedError> {
+    let topic0 = get_topic(log, 0)?;
+    let event_name = match topic0.as_str() {
+        RELAYED_MESSAGE_TOPIC => "RelayedMessage",
+        FAILED_RELAYED_MESSAGE_TOPIC => "FailedRelayedMessage",
+        _ => return Err(ExpectedError::InvalidError(format!("log is not relay event! topic={}", topic0))),
+    };
+    let msg_hash = get_topic(log, 1)?;
+
+    let mut converted_log = log.clone();
+    converted_log.insert(String::from("event_name"), Value::String(String::from(event_name)));
+    converted_log.insert(String::from("msg_hash"), Value::String(msg_hash));
+    converted_log.insert(String::from("block_timestamp"), Value::String(block_timestamp));
+    Ok(converted_log)
+}
+
+/*
+ * Same as `Lib_CrossDomainUtils.encodeXDomainCalldata` hashed by the messengers.
+ * keccak256(abi.encodeWithSignature("relayMessage(address,address,bytes,uint256)", target, sender, message, messageNonce))
+ */
+pub fn hash_cross_domain_message(target: &Address, sender: &Address, message: &[u8], message_nonce: U256) -> Bytes32 {
+    let padded_length = (message.len() + 31) / 32 * 32;
+    let mut encoded = Vec::with_capacity(4 + 32 * 5 + padded_length);
+    encoded.extend_from_slice(&RELAY_MESSAGE_SELECTOR);
+    encoded.extend_from_slice(&address_to_word(target));
+    encoded.extend_from_slice(&address_to_word(sender));
+    encoded.extend_from_slice(&u256_to_word(U256::from(128)));
+    encoded.extend_from_slice(&u256_to_word(message_nonce));
+    encoded.extend_from_slice(&u256_to_word(U256::from(message.len())));
+    encoded.extend_from_slice(message);
+    encoded.resize(4 + 32 * 5 + padded_length, 0);
+    keccak256(&encoded)
+}
+
+#[cfg(test)]
+mod cross_domain {
+    use primitive_types::U256;
+    use serde_json::{json, Value};
+
+    use crate::libs::cross_domain::{FAILED_RELAYED_MESSAGE_TOPIC, hash_cross_domain_message, is_sent_message, L2_CROSS_DOMAIN_MESSENGER, RELAYED_MESSAGE_TOPIC, relayed_message_converter, SENT_MESSAGE_TOPIC, sent_message_converter};
+    use crate::libs::merkle::{bytes32_to_hex, keccak256};
+
+    fn sent_message_log() -> Value {
+        let data = format!("0x{}{}{}{}{}{}",
+                           "000000000000000000000000000000000000000000000000000000000000abcd",
+                           "0000000000000000000000000000000000000000000000000000000000000080",
+                           "0000000000000000000000000000000000000000000000000000000000000007",
+                           "00000000000000000000000000000000000000000000000000000000000f4240",
+                           "0000000000000000000000000000000000000000000000000000000000000002",
+                           "1234000000000000000000000000000000000000000000000000000000000000");
+        json!({
+            "address": L2_CROSS_DOMAIN_MESSENGER,
+            "topics": [SENT_MESSAGE_TOPIC, "0x0000000000000000000000000000000000000000000000000000000000001234"],
+            "data": data,
+            "blockNumber": "0x10",
+            "logIndex": "0x1",
+            "transactionIndex": "0x0",
+            "transactionHash": "0xaa"
+        })
+    }
+
+    #[test]
+    fn event_topic_test() {
+        assert_eq!(bytes32_to_hex(&keccak256("SentMessage(address,address,bytes,uint256,uint256)".as_bytes())), SENT_MESSAGE_TOPIC);
+        assert_eq!(bytes32_to_hex(&keccak256("RelayedMessage(bytes32)".as_bytes())), RELAYED_MESSAGE_TOPIC);
+        assert_eq!(bytes32_to_hex(&keccak256("FailedRelayedMessage(bytes32)".as_bytes())), FAILED_RELAYED_MESSAGE_TOPIC);
+    }
+
+    #[test]
+    fn hash_cross_domain_message_test() {
+        let mut target = [0u8; 20];
+        target[19] = 1;
+        let mut sender = [0u8; 20];
+        sender[19] = 2;
+        let mut encoded = keccak256("relayMessage(address,address,bytes,uint256)".as_bytes())[..4].to_vec();
+        for word in [1u8, 2, 128, 3, 1].iter() {
+            let mut padded = [0u8; 32];
+            padded[31] = *word;
+            encoded.extend_from_slice(&padded);
+        }
+        let mut padded_message = [0u8; 32];
+        padded_message[0] = 0xff;
+        encoded.extend_from_slice(&padded_message);
+        assert_eq!(hash_cross_domain_message(&target, &sender, &[0xff], U256::from(3)), keccak256(&encoded));
+    }
+
+    #[test]
+    fn sent_message_converter_test() {
+        let log = sent_message_log();
+        let log_map = log.as_object().unwrap();
+        assert!(is_sent_message(log_map).unwrap());
+
+        let converted = sent_message_converter(log_map).unwrap();
+        let mut target = [0u8; 20];
+        target[18..].copy_from_slice(&[0x12, 0x34]);
+        let mut sender = [0u8; 20];
+        sender[18..].copy_from_slice(&[0xab, 0xcd]);
+        let msg_hash = hash_cross_domain_message(&target, &sender, &[0x12, 0x34], U256::from(7));
+        assert_eq!(converted.get("msg_hash").unwrap(), &Value::String(bytes32_to_hex(&msg_hash)));
+        assert_eq!(converted.get("target").unwrap(), "0x0000000000000000000000000000000000001234");
+        assert_eq!(converted.get("sender").unwrap(), "0x000000000000000000000000000000000000abcd");
+        assert_eq!(converted.get("message").unwrap(), "0x1234");
+        assert_eq!(converted.get("message_nonce").unwrap(), "7");
+        assert_eq!(converted.get("gas_limit").unwrap(), "1000000");
+        assert_eq!(converted.get("blockNumber").unwrap(), "0x10");
+    }
+
+    #[test]
+    fn relayed_message_converter_test() {
+        let log = json!({
+            "topics": [FAILED_RELAYED_MESSAGE_TOPIC, "0xABCD"],
+            "data": "0x",
+            "blockNumber": "0x10",
+            "logIndex": "0x1"
+        });
+        let converted = relayed_message_converter(log.as_object().unwrap(), String::from("1000")).unwrap();
+        assert_eq!(converted.get("event_name").unwrap(), "FailedRelayedMessage");
+        assert_eq!(converted.get("msg_hash").unwrap(), "0xabcd");
+
+        let log = json!({"topics": [SENT_MESSAGE_TOPIC, "0xabcd"]});
+        assert!(relayed_message_converter(log.as_object().unwrap(), String::from("1000")).is_err());
+    }
+}
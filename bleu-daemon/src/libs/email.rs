@@ -0,0 +1,151 @@
+use lettre::{Message, SmtpTransport, Transport};
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+
+use crate::error::error::ExpectedError;
+
+#[derive(Clone)]
+pub struct SmtpConfig {
+    pub relay: String,
+    pub port: Option<u16>,
+    pub tls: bool,
+    pub credentials: Option<Credentials>,
+    pub from: String,
+    pub reply_to: String,
+}
+
+/*
+ * A relay without tls is only meant for a local SMTP stand-in, e.g. `mailhog/mailhog`.
+ */
+pub fn create_mailer(config: &SmtpConfig) -> Result<SmtpTransport, ExpectedError> {
+    let mut builder = if config.tls {
+        SmtpTransport::relay(config.relay.as_str())?
+    } else {
+        SmtpTransport::builder_dangerous(config.relay.as_str())
+    };
+    if let Some(port) = config.port {
+        builder = builder.port(port);
+    }
+    if let Some(credentials) = config.credentials.clone() {
+        builder = builder.credentials(credentials);
+    }
+    Ok(builder.build())
+}
+
+pub fn parse_recipients(recipients: &str) -> Vec<String> {
+    recipients.split(',')
+        .map(|recipient| recipient.trim())
+        .filter(|recipient| !recipient.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox, ExpectedError> {
+    address.parse::<Mailbox>().map_err(|err| ExpectedError::ParsingError(format!("invalid email address! address={}, error={}", address, err.to_string())))
+}
+
+pub fn send(config: &SmtpConfig, to: &[String], subject: &str, body: &str) -> Result<(), ExpectedError> {
+    if to.is_empty() {
+        return Err(ExpectedError::NoneError(format!("no recipient! subject={}", subject)));
+    }
+    let mut builder = Message::builder()
+        .from(parse_mailbox(&config.from)?)
+        .reply_to(parse_mailbox(&config.reply_to)?)
+        .subject(subject);
+    for recipient in to.iter() {
+        builder = builder.to(parse_mailbox(recipient)?);
+    }
+    let email = builder.body(String::from(body)).map_err(|err| ExpectedError::ProcessError(err.to_string()))?;
+
+    let mailer = create_mailer(config)?;
+    let _ = mailer.send(&email)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod email {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::mpsc;
+    use std::sync::mpsc::Sender;
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::libs::email::{parse_recipients, send, SmtpConfig};
+
+    /*
+     * Minimal SMTP stand-in, which accepts everything and hands over the received DATA.
+     */
+    fn smtp_stand_in(listener: TcpListener, data_sender: Sender<String>) {
+        for stream in listener.incoming() {
+            let data_sender = data_sender.clone();
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => return,
+            };
+            thread::spawn(move || smtp_session(stream, data_sender));
+        }
+    }
+
+    fn smtp_session(mut stream: TcpStream, data_sender: Sender<String>) {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let _ = stream.write_all(b"220 localhost ESMTP\r\n");
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap_or(0) > 0 {
+            let command = line.trim_end().to_uppercase();
+            let reply: &[u8] = if command.starts_with("EHLO") || command.starts_with("HELO") {
+                b"250 localhost\r\n"
+            } else if command == "DATA" {
+                let _ = stream.write_all(b"354 end data with <CR><LF>.<CR><LF>\r\n");
+                let mut data = String::new();
+                let mut data_line = String::new();
+                while reader.read_line(&mut data_line).unwrap_or(0) > 0 && data_line != ".\r\n" {
+                    data.push_str(&data_line);
+                    data_line.clear();
+                }
+                let _ = data_sender.send(data);
+                b"250 queued\r\n"
+            } else if command == "QUIT" {
+                let _ = stream.write_all(b"221 bye\r\n");
+                return;
+            } else {
+                b"250 OK\r\n"
+            };
+            let _ = stream.write_all(reply);
+            line.clear();
+        }
+    }
+
+    #[test]
+    fn parse_recipients_test() {
+        assert_eq!(parse_recipients("a@domain.dev, b@domain.dev,"), vec!["a@domain.dev", "b@domain.dev"]);
+        assert!(parse_recipients("").is_empty());
+    }
+
+    #[test]
+    fn send_test() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (data_sender, data_receiver) = mpsc::channel();
+        thread::spawn(move || smtp_stand_in(listener, data_sender));
+
+        let config = SmtpConfig {
+            relay: String::from("127.0.0.1"),
+            port: Some(port),
+            tls: false,
+            credentials: None,
+            from: String::from("Bleu <bleu@domain.dev>"),
+            reply_to: String::from("Bleu <bleu@domain.dev>"),
+        };
+        let to = parse_recipients("ops@domain.dev,oncall@domain.dev");
+        send(&config, &to, "[bleu-daemon] error digest", "l2_block_tx: connection refused").unwrap();
+
+        let data = data_receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert!(data.contains("Subject: [bleu-daemon] error digest"));
+        assert!(data.contains("ops@domain.dev"));
+        assert!(data.contains("oncall@domain.dev"));
+        assert!(data.contains("l2_block_tx: connection refused"));
+
+        assert!(send(&config, &[], "empty", "body").is_err());
+    }
+}
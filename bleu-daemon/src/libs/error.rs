@@ -1,18 +1,46 @@
 use appbase::prelude::*;
+
 use crate::error::error::ExpectedError;
-use crate::plugin::slack::{SlackMsg, SlackMsgLevel};
+use crate::plugin::alert::{AlertMethod, AlertMsg, AlertPlugin};
+use crate::plugin::email::EmailAlertMsg;
+use crate::plugin::slack::SlackMsgLevel;
 use crate::types::enumeration::Enumeration;
 
-pub fn error_handler(slack_sender: Sender, error: ExpectedError) {
+/*
+ * Errors go through AlertPlugin, which deduplicates them by task and error variant before they reach Slack.
+ */
+pub fn error_handler(alert_sender: Sender, task: &str, error: ExpectedError) {
     log::error!("{}", error.to_string());
-    if let Err(e) = slack_sender.send(SlackMsg::new(SlackMsgLevel::Error.value(), error.to_string())) {
-        log::error!("failed to send slack message! error={}", e.to_string());
-    }
+    alert_handler(alert_sender, task, SlackMsgLevel::Error, error);
 }
 
-pub fn warn_handler(slack_sender: Sender, error: ExpectedError) {
+pub fn warn_handler(alert_sender: Sender, task: &str, error: ExpectedError) {
     log::warn!("{}", error.to_string());
-    if let Err(e) = slack_sender.send(SlackMsg::new(SlackMsgLevel::Warn.value(), error.to_string())) {
-        log::error!("failed to send slack message! error={}", e.to_string());
+    alert_handler(alert_sender, task, SlackMsgLevel::Warn, error);
+}
+
+fn alert_handler(alert_sender: Sender, task: &str, msg_level: SlackMsgLevel, error: ExpectedError) {
+    if let Err(e) = alert_sender.send(AlertMsg::new(AlertMethod::Fire, String::from(task), String::from(error.variant()), msg_level.value(), error.to_string())) {
+        log::error!("failed to send alert message! error={}", e.to_string());
+    }
+}
+
+/*
+ * A success is sent only while the task has an alert which has not recovered, so the alert channel does not carry every processed index.
+ */
+pub fn resolve_handler(alert_sender: Sender, task: &str) {
+    if !AlertPlugin::is_failing(task) {
+        return;
+    }
+    if let Err(e) = alert_sender.send(AlertMsg::new(AlertMethod::Resolve, String::from(task), String::new(), String::new(), String::new())) {
+        log::error!("failed to send alert message! error={}", e.to_string());
     }
+}
+
+/*
+ * Escalations skip the alert deduplication and are mailed right away, e.g. when retries are exhausted.
+ */
+pub fn escalation_handler(email_sender: Sender, task: &str, error: &ExpectedError) {
+    // nothing subscribes to the email channel unless EmailPlugin is activated
+    let _ = email_sender.send(EmailAlertMsg::new(String::from(task), SlackMsgLevel::Error.value(), error.to_string(), true));
 }
\ No newline at end of file
@@ -0,0 +1,87 @@
+use serde_json::{Map, Value};
+
+use crate::error::error::ExpectedError;
+use crate::libs::abi::{get_topic, read_uint};
+use crate::libs::merkle::hex_to_bytes;
+use crate::libs::serde::get_str;
+
+pub const GAS_PRICE_ORACLE: &str = "0x420000000000000000000000000000000000000f";
+pub const GAS_PRICE_UPDATED_TOPIC: &str = "0xfcdccc6074c6c42e4bd578aa9870c697dc976a270968452d2b8c8dc369fae396";
+pub const L1_BASE_FEE_UPDATED_TOPIC: &str = "0x351fb23757bb5ea0546c85b7996ddd7155f96b939ebaa5ff7bc49c75f27f2c44";
+pub const OVERHEAD_UPDATED_TOPIC: &str = "0x32740b35c0ea213650f60d44366b4fb211c9033b50714e4a1d34e65d5beb9bb4";
+pub const SCALAR_UPDATED_TOPIC: &str = "0x3336cd9708eaf2769a0f0dc0679f30e80f15dcd88d1921b5a16858e8b85c591a";
+pub const DECIMALS_UPDATED_TOPIC: &str = "0xd68112a8707e326d08be3656b528c1bcc5bbbfc47f4177e2179b14d8640838c1";
+
+fn param_name(topic0: &str) -> Option<&'static str> {
+    match topic0 {
+        GAS_PRICE_UPDATED_TOPIC => Some("gas_price"),
+        L1_BASE_FEE_UPDATED_TOPIC => Some("l1_base_fee"),
+        OVERHEAD_UPDATED_TOPIC => Some("overhead"),
+        SCALAR_UPDATED_TOPIC => Some("scalar"),
+        DECIMALS_UPDATED_TOPIC => Some("decimals"),
+        _ => None,
+    }
+}
+
+pub fn is_gas_price_oracle_update(log: &Map<String, Value>) -> Result<bool, ExpectedError> {
+    let address = get_str(log, "address")?;
+    let topic0 = get_topic(log, 0).unwrap_or_default();
+    Ok(address.to_lowercase() == GAS_PRICE_ORACLE && param_name(&topic0).is_some())
+}
+
+/*
+ * GasPriceUpdated(uint256), L1BaseFeeUpdated(uint256), OverheadUpdated(uint256), ScalarUpdated(uint256), DecimalsUpdated(uint256)
+ */
+pub fn gas_price_oracle_converter(log: &Map<String, Value>) -> Result<Map<String, Value>, ExpectedError> {
+    let topic0 = get_topic(log, 0)?;
+    let param_name = match param_name(&topic0) {
+        Some(param_name) => param_name,
+        None => return Err(ExpectedError::InvalidError(format!("log is not gas price oracle event! topic={}", topic0))),
+    };
+    let data = hex_to_bytes(get_str(log, "data")?)?;
+    let param_value = read_uint(&data, 0)?;
+
+    let mut converted_log = log.clone();
+    converted_log.insert(String::from("param_name"), Value::String(String::from(param_name)));
+    converted_log.insert(String::from("param_value"), Value::String(param_value.to_string()));
+    Ok(converted_log)
+}
+
+#[cfg(test)]
+mod gas_oracle {
+    use serde_json::json;
+
+    use crate::libs::gas_oracle::{DECIMALS_UPDATED_TOPIC, GAS_PRICE_ORACLE, GAS_PRICE_UPDATED_TOPIC, gas_price_oracle_converter, is_gas_price_oracle_update, L1_BASE_FEE_UPDATED_TOPIC, OVERHEAD_UPDATED_TOPIC, SCALAR_UPDATED_TOPIC};
+    use crate::libs::merkle::{bytes32_to_hex, keccak256};
+
+    #[test]
+    fn event_topic_test() {
+        assert_eq!(bytes32_to_hex(&keccak256("GasPriceUpdated(uint256)".as_bytes())), GAS_PRICE_UPDATED_TOPIC);
+        assert_eq!(bytes32_to_hex(&keccak256("L1BaseFeeUpdated(uint256)".as_bytes())), L1_BASE_FEE_UPDATED_TOPIC);
+        assert_eq!(bytes32_to_hex(&keccak256("OverheadUpdated(uint256)".as_bytes())), OVERHEAD_UPDATED_TOPIC);
+        assert_eq!(bytes32_to_hex(&keccak256("ScalarUpdated(uint256)".as_bytes())), SCALAR_UPDATED_TOPIC);
+        assert_eq!(bytes32_to_hex(&keccak256("DecimalsUpdated(uint256)".as_bytes())), DECIMALS_UPDATED_TOPIC);
+    }
+
+    #[test]
+    fn gas_price_oracle_converter_test() {
+        let log = json!({
+            "address": "0x420000000000000000000000000000000000000F",
+            "topics": [OVERHEAD_UPDATED_TOPIC],
+            "data": "0x0000000000000000000000000000000000000000000000000000000000000a28",
+            "blockNumber": "0x10",
+            "logIndex": "0x0"
+        });
+        let log_map = log.as_object().unwrap();
+        assert!(is_gas_price_oracle_update(log_map).unwrap());
+
+        let converted = gas_price_oracle_converter(log_map).unwrap();
+        assert_eq!(converted.get("param_name").unwrap(), "overhead");
+        assert_eq!(converted.get("param_value").unwrap(), "2600");
+        assert_eq!(converted.get("blockNumber").unwrap(), "0x10");
+
+        let log = json!({"address": GAS_PRICE_ORACLE, "topics": ["0x00"], "data": "0x"});
+        assert!(!is_gas_price_oracle_update(log.as_object().unwrap()).unwrap());
+        assert!(gas_price_oracle_converter(log.as_object().unwrap()).is_err());
+    }
+}
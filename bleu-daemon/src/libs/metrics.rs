@@ -0,0 +1,52 @@
+use lazy_static::lazy_static;
+use prometheus::{Encoder, HistogramVec, IntCounterVec, IntGaugeVec, register_histogram_vec, register_int_counter_vec, register_int_gauge_vec, TextEncoder};
+use reqwest::Url;
+
+lazy_static! {
+    pub static ref TASK_PROCESSED: IntCounterVec = register_int_counter_vec!("bleu_task_processed_total", "Number of indexes processed by task", &["task"]).unwrap();
+    pub static ref TASK_ERRORS: IntCounterVec = register_int_counter_vec!("bleu_task_errors_total", "Number of task errors by ExpectedError variant", &["task", "variant"]).unwrap();
+    pub static ref TASK_RETRY_QUEUE_SIZE: IntGaugeVec = register_int_gauge_vec!("bleu_task_retry_queue_size", "Number of jobs waiting in the retry queue", &["task"]).unwrap();
+    pub static ref TASK_DEAD_LETTERS: IntCounterVec = register_int_counter_vec!("bleu_task_dead_letters_total", "Number of jobs out of retries and handed over for manual retry", &["task"]).unwrap();
+    pub static ref TASK_CURRENT_INDEX: IntGaugeVec = register_int_gauge_vec!("bleu_task_current_index", "Next index the task will process", &["task"]).unwrap();
+    pub static ref TASK_HEAD_INDEX: IntGaugeVec = register_int_gauge_vec!("bleu_task_head_index", "Newest index of the task upstream", &["task"]).unwrap();
+    pub static ref POSTGRES_INSERT_SECONDS: HistogramVec = register_histogram_vec!("bleu_postgres_insert_seconds", "PostgreSQL insert latency by schema", &["schema"]).unwrap();
+    pub static ref POSTGRES_ROWS: IntCounterVec = register_int_counter_vec!("bleu_postgres_rows_total", "Number of rows inserted by schema", &["schema"]).unwrap();
+    pub static ref CHANNEL_DEPTH: IntGaugeVec = register_int_gauge_vec!("bleu_channel_depth", "Number of messages waiting in the channel", &["channel"]).unwrap();
+    pub static ref RPC_REQUEST_SECONDS: HistogramVec = register_histogram_vec!("bleu_rpc_request_seconds", "Upstream request latency by endpoint", &["endpoint"]).unwrap();
+}
+
+/*
+ * Only the origin of the url is used as label, so indexes in the path and api keys do not end up in metrics.
+ */
+pub fn endpoint_label(url: &str) -> String {
+    match Url::parse(url) {
+        Ok(parsed_url) => parsed_url.origin().ascii_serialization(),
+        Err(_) => String::from("unknown"),
+    }
+}
+
+pub fn gather() -> String {
+    let mut buffer = Vec::new();
+    if let Err(err) = TextEncoder::new().encode(&prometheus::gather(), &mut buffer) {
+        log::error!("failed to encode metrics! error={}", err.to_string());
+    }
+    String::from_utf8(buffer).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod metrics {
+    use crate::libs::metrics::{endpoint_label, gather, TASK_PROCESSED};
+
+    #[test]
+    fn endpoint_label_test() {
+        assert_eq!(endpoint_label("https://mainnet.infura.io/v3/secret"), "https://mainnet.infura.io");
+        assert_eq!(endpoint_label("http://localhost:7878/batch/transaction/index/10"), "http://localhost:7878");
+        assert_eq!(endpoint_label("not a url"), "unknown");
+    }
+
+    #[test]
+    fn gather_test() {
+        TASK_PROCESSED.with_label_values(&["l2_block_tx"]).inc();
+        assert!(gather().contains("bleu_task_processed_total{task=\"l2_block_tx\"}"));
+    }
+}
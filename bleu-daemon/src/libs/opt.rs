@@ -1,24 +1,45 @@
-use std::str::FromStr;
+use std::{env, fs};
 
 use appbase::prelude::*;
-use serde::Deserialize;
 
 use crate::error::error::ExpectedError;
 
+const ENV_PREFIX: &str = "BLEU_";
+const FILE_PREFIX: &str = "file:";
+
+/*
+ * `postgres::password` is overridden by `BLEU_POSTGRES_PASSWORD`, or read from the file in `BLEU_POSTGRES_PASSWORD_FILE`.
+ * A value written as `file:/run/secrets/postgres_password` is read from the file as well.
+ */
 pub fn get_value_str(key: &str) -> Result<String, ExpectedError> {
-    let value_str = APP.options.value_of(key)
-        .ok_or(ExpectedError::NoneError("argument is null!".to_string()))?;
-    Ok(value_str.to_string())
+    resolve_value(key, APP.options.value_of(key))
+}
+
+/*
+ * Applies the environment overrides and the secret files on `value`, which is the option given by config or command line.
+ */
+pub fn resolve_value(key: &str, value: Option<String>) -> Result<String, ExpectedError> {
+    let env_key = env_key(key);
+    let value_str = match env::var(&env_key) {
+        Ok(value_str) => value_str,
+        Err(_) => match env::var(format!("{}_FILE", env_key)) {
+            Ok(path) => return read_secret(&path),
+            Err(_) => value.ok_or(ExpectedError::NoneError("argument is null!".to_string()))?,
+        },
+    };
+    match value_str.strip_prefix(FILE_PREFIX) {
+        Some(path) => read_secret(path),
+        None => Ok(value_str),
+    }
+}
+
+pub fn env_key(key: &str) -> String {
+    format!("{}{}", ENV_PREFIX, key.replace("::", "_").replace('-', "_").to_uppercase())
 }
 
-pub fn get_value<T>(key: &str) -> Result<T, ExpectedError>
-    where
-        T: FromStr + Deserialize<'static>,
-        <T as FromStr>::Err: std::fmt::Display
-{
-    let value_str = APP.options.value_of_t::<T>(key)
-        .ok_or(ExpectedError::NoneError("argument is null!".to_string()))?;
-    Ok(value_str)
+fn read_secret(path: &str) -> Result<String, ExpectedError> {
+    let secret = fs::read_to_string(path).map_err(|err| ExpectedError::IoError(format!("failed to read secret file! path={}, error={}", path, err)))?;
+    Ok(String::from(secret.trim_end()))
 }
 
 pub fn opt_to_result<T>(option: Option<T>) -> Result<T, ExpectedError> {
@@ -28,9 +49,13 @@ pub fn opt_to_result<T>(option: Option<T>) -> Result<T, ExpectedError> {
     }
 }
 
-pub fn opt_ref_to_result<T>(option: Option<&T>) -> Result<&T, ExpectedError> {
-    match option {
-        Some(t) => Ok(t),
-        None => Err(ExpectedError::NoneError(String::from("value is none!")))
+#[cfg(test)]
+mod opt_test {
+    use crate::libs::opt::env_key;
+
+    #[test]
+    fn env_key_test() {
+        assert_eq!(env_key("postgres::password"), "BLEU_POSTGRES_PASSWORD");
+        assert_eq!(env_key("addresswebhook::poll-interval"), "BLEU_ADDRESSWEBHOOK_POLL_INTERVAL");
     }
 }
@@ -1,10 +1,15 @@
 use std::collections::HashMap;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use r2d2_postgres::postgres::Client;
 use serde_json::{Map, Value};
 
 use crate::error::error::ExpectedError;
+use crate::libs::convert::{value_to_hex, value_to_u256, value_to_u64};
 use crate::libs::serde::find_value;
 use crate::plugin::postgres::Pool;
+use crate::types::migration::{ColumnState, CREATE_HEX_TO_NUMERIC, MIGRATION_TABLE, MigrationPlan, TableState};
 use crate::types::postgres::PostgresSchema;
 
 pub fn convert_type(_type: String) -> Result<String, ExpectedError> {
@@ -20,58 +25,212 @@ pub fn convert_type(_type: String) -> Result<String, ExpectedError> {
         "json"
     } else if _type == "array" {
         "varchar"
+    } else if _type == "uint64" || _type == "timestamp" {
+        "bigint"
+    } else if _type == "uint256" {
+        "numeric(78, 0)"
+    } else if _type == "address" || _type == "hash" || _type == "bytes" {
+        "varchar"
     } else {
         return Err(ExpectedError::TypeError(String::from("unsupported type!")));
     };
     Ok(String::from(converted))
 }
 
-pub fn create_table(pool: Pool, schema_map: &HashMap<String, PostgresSchema>) -> Result<(), r2d2_postgres::postgres::Error> {
-    let mut client = pool.get().unwrap();
-    for (_, schema) in schema_map.iter() {
-        if let Err(err) = client.execute(schema.create_table.as_str(), &[]) {
-            let _ = error_handler(err)?;
+/*
+ * Every `*.json` file in the directory, sorted so that the load order does not depend on the file system.
+ */
+pub fn find_schema_files(schema_dir: &str) -> Result<Vec<String>, ExpectedError> {
+    let mut schema_files = Vec::new();
+    for entry in fs::read_dir(schema_dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().and_then(|extension| extension.to_str()) == Some("json") {
+            schema_files.push(path.to_string_lossy().to_string());
         }
-        for create_index in schema.create_index.iter() {
-            if let Err(err) = client.execute(create_index.as_str(), &[]) {
-                let _ = error_handler(err)?;
+    }
+    schema_files.sort();
+    Ok(schema_files)
+}
+
+/*
+ * Loads the schemas of every file by their qualified table name, and fails when two files define the same table.
+ */
+pub fn load_schema_files(schema_files: &[String]) -> Result<HashMap<String, PostgresSchema>, ExpectedError> {
+    let mut schema_map = HashMap::new();
+    let mut defined_in: HashMap<String, &String> = HashMap::new();
+    for schema_file in schema_files.iter() {
+        let json_str = fs::read_to_string(schema_file)?;
+        let json_schema: Value = serde_json::from_str(json_str.as_str())?;
+        let schemas = PostgresSchema::from_file(&json_schema)
+            .map_err(|err| ExpectedError::InvalidError(format!("{} file={}", err, schema_file)))?;
+        for schema in schemas {
+            if let Some(defined) = defined_in.insert(schema.schema_name.clone(), schema_file) {
+                return Err(ExpectedError::InvalidError(format!("table is defined in two schema files! table={}, files={}, {}", schema.schema_name, defined, schema_file)));
             }
+            schema_map.insert(schema.schema_name.clone(), schema);
+        }
+    }
+    if schema_map.is_empty() {
+        return Err(ExpectedError::NoneError(format!("no schema is loaded! files={:?}", schema_files)));
+    }
+    Ok(schema_map)
+}
+
+/*
+ * Resolves a table by its qualified name, or by the name written in its schema file such as `optimism_blocks`,
+ * which must then be defined by one file only.
+ */
+pub fn resolve_schema<'a>(schema_map: &'a HashMap<String, PostgresSchema>, name: &str) -> Result<&'a PostgresSchema, ExpectedError> {
+    if let Some(schema) = schema_map.get(name) {
+        return Ok(schema);
+    }
+    let mut matched = schema_map.values().filter(|schema| schema.name == name);
+    match (matched.next(), matched.next()) {
+        (Some(schema), None) => Ok(schema),
+        (Some(_), Some(_)) => Err(ExpectedError::InvalidError(format!("table name is ambiguous! use the qualified name, name={}", name))),
+        _ => Err(ExpectedError::NoneError(format!("table is not loaded! name={}", name))),
+    }
+}
+
+/*
+ * Replaces `{table}` in the query with the qualified table name, and `{table.id}` with its id column.
+ */
+pub fn resolve_query(schema_map: &HashMap<String, PostgresSchema>, query: &str) -> Result<String, ExpectedError> {
+    let mut resolved = String::new();
+    let mut rest = query;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => return Err(ExpectedError::InvalidError(format!("table name is not closed! query={}", query))),
+        };
+        resolved.push_str(&rest[..start]);
+        match rest[start + 1..end].split_once('.') {
+            None => resolved.push_str(&resolve_schema(schema_map, &rest[start + 1..end])?.schema_name),
+            Some((name, "id")) => resolved.push_str(&resolve_schema(schema_map, name)?.id_column()),
+            Some((_, column)) => return Err(ExpectedError::InvalidError(format!("only the id column can be resolved! column={}", column))),
         }
+        rest = &rest[end + 1..];
+    }
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+/*
+ * Compares every schema with the database and plans the DDL to bring the tables up to date.
+ */
+pub fn plan_migrations(pool: Pool, schema_map: &HashMap<String, PostgresSchema>) -> Result<Vec<MigrationPlan>, ExpectedError> {
+    let mut client = pool.get().map_err(|err| ExpectedError::PostgresError(err.to_string()))?;
+    let mut schema_names: Vec<&String> = schema_map.keys().collect();
+    schema_names.sort();
+    let mut plans = Vec::new();
+    for schema_name in schema_names {
+        let schema = schema_map.get(schema_name).unwrap();
+        let table = table_state(&mut client, schema)?;
+        let plan = MigrationPlan::new(schema, table.as_ref());
+        if !plan.is_empty() {
+            plans.push(plan);
+        }
+    }
+    Ok(plans)
+}
+
+/*
+ * Applies each plan in its own transaction and records it as the next version of the schema.
+ */
+pub fn apply_migrations(pool: Pool, plans: &Vec<MigrationPlan>) -> Result<(), ExpectedError> {
+    let mut client = pool.get().map_err(|err| ExpectedError::PostgresError(err.to_string()))?;
+    client.batch_execute(CREATE_HEX_TO_NUMERIC)?;
+    client.execute(format!("CREATE TABLE IF NOT EXISTS {} (schema_name varchar NOT NULL, version int8 NOT NULL, statements text NOT NULL, destructive boolean NOT NULL, applied_at int8 NOT NULL, CONSTRAINT {}_pk PRIMARY KEY (schema_name, version))", MIGRATION_TABLE, MIGRATION_TABLE).as_str(), &[])?;
+    for plan in plans.iter() {
+        let mut transaction = client.transaction()?;
+        for statement in plan.statements.iter() {
+            transaction.execute(statement.as_str(), &[])?;
+        }
+        let row = transaction.query_one(format!("SELECT COALESCE(MAX(version), 0) + 1 FROM {} WHERE schema_name = $1", MIGRATION_TABLE).as_str(), &[&plan.schema_name])?;
+        let version: i64 = row.get(0);
+        let applied_at = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+        transaction.execute(format!("INSERT INTO {} (schema_name, version, statements, destructive, applied_at) VALUES ($1, $2, $3, $4, $5)", MIGRATION_TABLE).as_str(),
+                            &[&plan.schema_name, &version, &plan.statements.join(";\n"), &plan.is_destructive(), &applied_at])?;
+        transaction.commit()?;
+        log::info!("schema migrated! schema={}, version={}, statements={}", plan.schema_name, version, plan.statements.len());
     }
     Ok(())
 }
 
-pub fn error_handler(err: r2d2_postgres::postgres::Error) -> Result<(), r2d2_postgres::postgres::Error> {
-    let err_str = err.to_string();
-    if err_str.contains("already exists") {
-        log::warn!("{}", err_str);
-        Ok(())
-    } else {
-        log::error!("{}", err_str);
-        Err(err)
+/*
+ * A table without a namespace is looked up in the current schema of the connection.
+ */
+fn table_state(client: &mut Client, schema: &PostgresSchema) -> Result<Option<TableState>, ExpectedError> {
+    let (table_name, namespace) = (&schema.table_name, &schema.namespace);
+    let rows = client.query("SELECT column_name::varchar, data_type::varchar, character_maximum_length::int4, is_nullable::varchar FROM information_schema.columns WHERE table_schema = COALESCE($2::varchar, current_schema()) AND table_name = $1", &[table_name, namespace])?;
+    if rows.is_empty() {
+        return Ok(None);
+    }
+    let mut table = TableState::default();
+    for row in rows.iter() {
+        let max_length: Option<i32> = row.get(2);
+        let is_nullable: String = row.get(3);
+        table.columns.insert(row.get(0), ColumnState {
+            data_type: row.get(1),
+            max_length: max_length.map(|max_length| max_length as u32),
+            nullable: is_nullable == "YES",
+        });
+    }
+    for row in client.query("SELECT indexname::varchar FROM pg_indexes WHERE schemaname = COALESCE($2::varchar, current_schema()) AND tablename = $1", &[table_name, namespace])?.iter() {
+        table.indexes.insert(row.get(0));
     }
+    for row in client.query("SELECT constraint_name::varchar FROM information_schema.table_constraints WHERE table_schema = COALESCE($2::varchar, current_schema()) AND table_name = $1 AND constraint_type = 'UNIQUE'", &[table_name, namespace])?.iter() {
+        table.constraints.insert(row.get(0));
+    }
+    Ok(Some(table))
+}
+
+/*
+ * Values of the on-chain types are converted from hex or decimal json by the schema type,
+ * and a value which does not fit its type fails the insert.
+ */
+pub fn convert_value(_type: &str, value: Value) -> Result<String, ExpectedError> {
+    if value.is_null() {
+        return Ok(String::from("null"));
+    }
+    let converted = match _type {
+        "uint64" | "timestamp" => value_to_u64(&value)?.to_string(),
+        "uint256" => value_to_u256(&value)?.to_string(),
+        "address" => format!("'{}'", value_to_hex(&value, Some(20))?),
+        "hash" => format!("'{}'", value_to_hex(&value, Some(32))?),
+        "bytes" => format!("'{}'", value_to_hex(&value, None)?),
+        _ => to_query_value(value),
+    };
+    Ok(converted)
 }
 
 pub fn insert_value(pool: Pool, schema: &PostgresSchema, values: &Map<String, Value>) -> Result<(), ExpectedError> {
     let mut client = pool.get().unwrap();
-    let value_names = schema.attributes.iter().map(|attribute| { attribute.description.clone() }).collect::<Vec<String>>();
-    let insert_query = create_insert_query(&schema.insert_query, value_names, values)?;
+    let insert_query = create_insert_query(schema, values)?;
     let _ = client.execute(insert_query.as_str(), &[])?;
     Ok(())
 }
 
-fn create_insert_query(insert_query: &String, value_names: Vec<String>, values: &Map<String, Value>) -> Result<String, ExpectedError> {
-    let mut temp_query = insert_query.clone();
-    for value_name in value_names.iter() {
-        let to_value = get_query_value(&values, value_name);
-        let from = format!("${}$", value_name);
+/*
+ * Each attribute takes its value by its mapping, and the value is converted by the attribute type.
+ */
+fn create_insert_query(schema: &PostgresSchema, values: &Map<String, Value>) -> Result<String, ExpectedError> {
+    let mut temp_query = schema.insert_query.clone();
+    for attribute in schema.attributes.iter() {
+        let to_value = attribute.mapping.map(values)
+            .and_then(|value| convert_value(&attribute.get_type(), value))
+            .map_err(|err| ExpectedError::InvalidError(format!("{} field={}", err, attribute.name)))?;
+        let from = format!("${}$", attribute.name);
         temp_query = temp_query.replace(&from, &to_value);
     }
     Ok(temp_query)
 }
 
 pub fn get_query_value(values: &Map<String, Value>, target_name: &str) -> String {
-    let value = find_value(values, target_name);
+    to_query_value(find_value(values, target_name))
+}
+
+fn to_query_value(value: Value) -> String {
     match value {
         Value::Null => String::from("null"),
         Value::String(s) => format!("'{}'", s),
@@ -83,21 +242,45 @@ pub fn get_query_value(values: &Map<String, Value>, target_name: &str) -> String
 
 #[cfg(test)]
 mod postgres {
+    use std::{env, fs, process};
+    use std::collections::HashMap;
+
     use serde_json::{json, Map, Value};
 
-    use crate::libs::postgres::{create_insert_query, get_query_value};
+    use crate::libs::postgres::{convert_value, create_insert_query, find_schema_files, get_query_value, load_schema_files, resolve_query, resolve_schema};
+    use crate::types::postgres::PostgresSchema;
+
+    fn example_schema() -> PostgresSchema {
+        let values = json!({
+            "attributes": {
+                "a": { "type": "string" },
+                "b": { "type": "string", "path": "$.nested.b" },
+                "c": { "type": "boolean" },
+                "d": { "type": [ "uint64", "null" ], "path": "$.nested.d", "default": 0 }
+            },
+            "indexes": [],
+            "uniques": []
+        });
+        PostgresSchema::from(String::from("test"), &values).unwrap()
+    }
 
     #[test]
     fn create_insert_query_test() {
-        let insert_query = String::from("INSERT INTO test (a, b, c) VALUES ($a$, $b$, $c$)");
-        let value_names = vec![String::from("a"), String::from("b"), String::from("c")];
         let mut values = Map::new();
         values.insert(String::from("a"), Value::String(String::from("x")));
-        values.insert(String::from("b"), Value::String(String::from("y")));
+        values.insert(String::from("b"), Value::String(String::from("wrong")));
         values.insert(String::from("c"), Value::Bool(false));
+        values.insert(String::from("nested"), json!({ "b": "y" }));
 
-        let created_insert_query = create_insert_query(&insert_query, value_names, &values).unwrap();
-        assert_eq!("INSERT INTO test (a, b, c) VALUES ('x', 'y', false)", created_insert_query);
+        let created_insert_query = create_insert_query(&example_schema(), &values).unwrap();
+        assert_eq!("INSERT INTO test (a, b, c, d) VALUES ('x', 'y', false, 0)", created_insert_query);
+    }
+
+    #[test]
+    fn create_insert_query_fail_test() {
+        let values = json!({ "a": "x", "c": true, "nested": { "d": "pending" } });
+        let err = create_insert_query(&example_schema(), values.as_object().unwrap()).unwrap_err().to_string();
+        assert!(err.contains("field=d"));
     }
 
     #[test]
@@ -115,4 +298,69 @@ mod postgres {
         assert_eq!("false", b);
         assert_eq!("1", c);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn convert_value_test() {
+        assert_eq!(convert_value("uint64", json!("0x1a")).unwrap(), "26");
+        assert_eq!(convert_value("timestamp", json!("1650000000")).unwrap(), "1650000000");
+        assert_eq!(convert_value("uint256", json!("0xde0b6b3a7640000")).unwrap(), "1000000000000000000");
+        assert_eq!(convert_value("address", json!("0x4200000000000000000000000000000000000006")).unwrap(), "'0x4200000000000000000000000000000000000006'");
+        assert_eq!(convert_value("bytes", json!("0xABCD")).unwrap(), "'0xabcd'");
+        assert_eq!(convert_value("hash", Value::Null).unwrap(), "null");
+        assert_eq!(convert_value("string", json!("x")).unwrap(), "'x'");
+        assert!(convert_value("hash", json!("0x01")).is_err());
+        assert!(convert_value("uint64", json!("pending")).is_err());
+    }
+
+    #[test]
+    fn load_schema_files_test() {
+        let schema_dir = env::temp_dir().join(format!("bleu_schema_files_test_{}", process::id()));
+        fs::create_dir_all(schema_dir.join("nested.json")).unwrap();
+        let table = |name: &str| json!({ name: { "attributes": { "hash": { "type": "hash" } }, "indexes": [], "uniques": [] } }).to_string();
+        fs::write(schema_dir.join("b.json"), table("b_blocks")).unwrap();
+        fs::write(schema_dir.join("a.json"), table("a_blocks")).unwrap();
+        fs::write(schema_dir.join("notes.txt"), table("c_blocks")).unwrap();
+
+        let schema_files = find_schema_files(schema_dir.to_str().unwrap()).unwrap();
+        let schema_map = load_schema_files(&schema_files).unwrap();
+        fs::remove_dir_all(&schema_dir).unwrap();
+
+        let expected = [schema_dir.join("a.json"), schema_dir.join("b.json")].iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(schema_files, expected);
+        assert_eq!(schema_map.len(), 2);
+        assert!(schema_map.contains_key("a_blocks"));
+        assert!(schema_map.contains_key("b_blocks"));
+    }
+
+    #[test]
+    fn resolve_schema_test() {
+        let table = |namespace: &str| json!({ "$namespace": namespace, "$tablePrefix": "op_", "blocks": { "attributes": { "hash": { "type": "hash" } }, "indexes": [], "uniques": [] } });
+        let mut schema_map = HashMap::new();
+        for schema in PostgresSchema::from_file(&table("goerli")).unwrap() {
+            schema_map.insert(schema.schema_name.clone(), schema);
+        }
+        assert_eq!(resolve_schema(&schema_map, "blocks").unwrap().schema_name, "goerli.op_blocks");
+        assert_eq!(resolve_schema(&schema_map, "goerli.op_blocks").unwrap().schema_name, "goerli.op_blocks");
+        assert_eq!(resolve_query(&schema_map, "SELECT {blocks.id} AS id FROM {blocks} b WHERE b.hash = $1").unwrap(), "SELECT op_blocks_id AS id FROM goerli.op_blocks b WHERE b.hash = $1");
+        assert!(resolve_schema(&schema_map, "txs").is_err());
+        assert!(resolve_query(&schema_map, "SELECT {blocks.hash} FROM {blocks}").is_err());
+        assert!(resolve_query(&schema_map, "SELECT * FROM {blocks").is_err());
+
+        for schema in PostgresSchema::from_file(&table("sepolia")).unwrap() {
+            schema_map.insert(schema.schema_name.clone(), schema);
+        }
+        assert!(resolve_schema(&schema_map, "blocks").unwrap_err().to_string().contains("ambiguous"));
+        assert_eq!(resolve_schema(&schema_map, "sepolia.op_blocks").unwrap().schema_name, "sepolia.op_blocks");
+    }
+
+    #[test]
+    fn load_schema_files_fail_test() {
+        let schema_files = vec![String::from("schema/ethereum.json"), String::from("schema/ethereum.json")];
+        let err = load_schema_files(&schema_files).unwrap_err().to_string();
+        assert!(err.contains("table is defined in two schema files! table=ethereum_tx_logs"));
+        assert!(load_schema_files(&[]).is_err());
+        assert!(find_schema_files("not_found").is_err());
+    }
+}
@@ -1,12 +1,15 @@
 use serde_json::{Map, Value};
 
 use crate::error::error::ExpectedError;
+use crate::libs::metrics::{endpoint_label, RPC_REQUEST_SECONDS};
 use crate::libs::serde::get_string;
 
 pub async fn get_async(url: &str) -> Result<Map<String, Value>, ExpectedError> {
+    let timer = RPC_REQUEST_SECONDS.with_label_values(&[&endpoint_label(url)]).start_timer();
     let res = reqwest::get(url).await?;
     let status = res.status().clone();
     let body = res.text().await?;
+    timer.observe_duration();
     let parsed_body: Map<String, Value> = serde_json::from_str(body.as_str())?;
 
     if !status.is_success() {
@@ -19,9 +22,11 @@ pub async fn get_async(url: &str) -> Result<Map<String, Value>, ExpectedError> {
 pub async fn post_async(url: &str, req_body: &str) -> Result<Map<String, Value>, ExpectedError> {
     let req = String::from(req_body);
     let client = reqwest::Client::new();
+    let timer = RPC_REQUEST_SECONDS.with_label_values(&[&endpoint_label(url)]).start_timer();
     let res = client.post(url).body(req).header("Content-Type", "application/json").send().await?;
     let status = res.status().clone();
     let body = res.text().await?;
+    timer.observe_duration();
     let parsed_body: Map<String, Value> = serde_json::from_str(body.as_str())?;
 
     if !status.is_success() {
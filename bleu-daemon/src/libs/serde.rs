@@ -1,9 +1,7 @@
-use std::str::FromStr;
-
 use serde_json::{Map, Value};
 
 use crate::error::error::ExpectedError;
-use crate::libs::opt::{opt_ref_to_result, opt_to_result};
+use crate::libs::opt::opt_to_result;
 
 // pub fn select_value(params: &Map<String, Value>, names: Vec<&str>) -> Result<Map<String, Value>, ExpectedError> {
 //     let mut values = Map::new();
@@ -133,118 +131,3 @@ pub fn get_type(value: &Value) -> String {
     };
     String::from(types)
 }
-
-pub fn filter(values: &Map<String, Value>, filter: String) -> Result<bool, ExpectedError> {
-    if filter.trim().is_empty() {
-        return Ok(true);
-    }
-    let mut calc_vec: Vec<String> = Vec::new();
-    let mut key_value = String::new();
-    let filter_chars = filter.chars();
-    for c in filter_chars {
-        if c == '&' || c == '|' || c == '(' || c == ')' {
-            if !key_value.trim().is_empty() {
-                let calc_result = filter_value(values, &key_value)?;
-                calc_vec.push(calc_result.to_string());
-            }
-            calc_vec.push(String::from(c));
-            key_value = String::new();
-        } else {
-            key_value.push(c);
-        }
-    }
-    if !key_value.trim().is_empty() {
-        let calc_result = filter_value(values, &key_value)?;
-        calc_vec.push(calc_result.to_string());
-    }
-
-    let mut bool_stack: Vec<bool> = Vec::new();
-    let mut calc_stack: Vec<String> = Vec::new();
-    for vec_item in calc_vec {
-        if vec_item == ")" {
-            while opt_ref_to_result(calc_stack.last())? != "(" {
-                if bool_stack.len() < 2 {
-                    return Err(ExpectedError::InvalidError(String::from("filter format error!")));
-                }
-                let calc_ret = filter_calc(&mut bool_stack, &mut calc_stack)?;
-                bool_stack.push(calc_ret);
-            }
-            calc_stack.pop();
-        } else {
-            if vec_item == "(" || vec_item == "&" || vec_item == "|" {
-                calc_stack.push(vec_item.clone());
-            } else {
-                bool_stack.push(bool::from_str(vec_item.as_str()).unwrap());
-            }
-        }
-    }
-    while !calc_stack.is_empty() {
-        let calc_ret = filter_calc(&mut bool_stack, &mut calc_stack)?;
-        bool_stack.push(calc_ret);
-    }
-    let ret = opt_to_result(bool_stack.pop())?;
-    Ok(ret)
-}
-
-fn filter_value(values: &Map<String, Value>, key_value: &String) -> Result<bool, ExpectedError> {
-    let mut split_kv = key_value.split("=");
-    if split_kv.clone().count() != 2 {
-        return Err(ExpectedError::TypeError(String::from("invalid filter condition format! example='key=val'")));
-    }
-    let key = split_kv.next().unwrap().trim();
-    let value = split_kv.next().unwrap().trim();
-    let found = if key.contains(".") {
-        match get_value_by_path(values, key) {
-            Ok(val) => val.clone(),
-            Err(_) => Value::Null
-        }
-    } else {
-        find_value(values, key)
-    };
-    let found_val = match found {
-        Value::String(s) => s,
-        _ => found.to_string(),
-    };
-    Ok(value == found_val.as_str())
-}
-
-fn filter_calc(bool_stack: &mut Vec<bool>, calc_stack: &mut Vec<String>) -> Result<bool, ExpectedError> {
-    let calc_op = opt_to_result(calc_stack.pop())?;
-    let top = opt_to_result(bool_stack.pop())?;
-    let second = opt_to_result(bool_stack.pop())?;
-    if calc_op == "&" {
-        Ok(top & second)
-    } else {
-        Ok(top | second)
-    }
-}
-
-#[cfg(test)]
-mod serde {
-    use serde_json::{json, Map, Value};
-
-    use crate::libs::serde;
-
-    #[test]
-    fn filter_success_test() {
-        let mut test_map = Map::new();
-        test_map.insert(String::from("key1"), Value::String(String::from("val1")));
-        test_map.insert(String::from("key2"), json!({"sub_key1": "sub_val1"}));
-        test_map.insert(String::from("key3"), json!(100));
-
-        let ret = serde::filter(&test_map, String::from("(key1 = val1 & sub_key1 = sub_val1 & key3 =101) | key4=null | key3=101")).unwrap();
-        assert_eq!(ret, true);
-    }
-
-    #[test]
-    fn filter_fail_test() {
-        let mut test_map = Map::new();
-        test_map.insert(String::from("key1"), Value::String(String::from("val1")));
-        test_map.insert(String::from("key2"), json!({"sub_key1": "sub_val1"}));
-        test_map.insert(String::from("key3"), json!(100));
-        test_map.insert(String::from("key4"), Value::String(String::from("not_null")));
-
-        let ret = serde::filter(&test_map, String::from("(key1 = val1 & sub_key1 = sub_val1 & key3 =100) & key4=null")).unwrap();
-        assert_eq!(ret, false);
-    }
-}
@@ -0,0 +1,27 @@
+pub const HOUR: u64 = 3600;
+pub const DAY: u64 = 86400;
+
+/*
+ * Time ranges rolled up once the hour bucket `hour_idx` is complete.
+ * The day bucket is rolled up together with its last hour.
+ */
+pub fn stat_ranges(hour_idx: u64) -> Vec<(&'static str, u64, u64)> {
+    let hour_start = hour_idx * HOUR;
+    let hour_end = hour_start + HOUR;
+    let mut ranges = vec![("hour", hour_start, hour_end)];
+    if hour_end % DAY == 0 {
+        ranges.push(("day", hour_end - DAY, hour_end));
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod stat {
+    use crate::libs::stat::stat_ranges;
+
+    #[test]
+    fn stat_ranges_test() {
+        assert_eq!(stat_ranges(454608), vec![("hour", 1636588800, 1636592400)]);
+        assert_eq!(stat_ranges(454631), vec![("hour", 1636671600, 1636675200), ("day", 1636588800, 1636675200)]);
+    }
+}
@@ -11,32 +11,76 @@ use crate::libs;
 use crate::libs::opt::opt_to_result;
 use crate::libs::request::adjust_url;
 use crate::libs::rocks::{get_by_prefix_static, get_static};
-use crate::libs::serde::{filter, get_object, get_str};
+use crate::libs::serde::{get_object, get_str};
 use crate::plugin::rocks::{RocksDB, RocksMethod, RocksMsg};
 use crate::types::channel::MultiSender;
 use crate::types::enumeration::Enumeration;
+use crate::types::filter::Filter;
 use crate::types::subscribe::{RetryJob, SubscribeEvent, SubscribeStatus, SubscribeTask, TaskMethod};
+use crate::types::webhook::WebhookEventType;
 
+/*
+ * A task which has run before is loaded from RocksDB, and the changes of its task file are applied on it.
+ */
 pub fn task_loader(rocksdb: RocksDB, file_path: &str, chain: &str, task_prefix: &str, task_name: &str) -> Result<SubscribeEvent, ExpectedError> {
     match load_task_from_rocksdb(rocksdb, task_prefix, task_name) {
-        Ok(sub_event) => Ok(sub_event),
+        Ok(mut sub_event) => {
+            match load_task_from_json(file_path, chain, task_prefix, task_name) {
+                Ok(definition) => {
+                    let changed = sub_event.reload(definition);
+                    if !changed.is_empty() {
+                        log::info!("task file changed since the last run! task={}, changed={}", task_name, changed.join(","));
+                    }
+                }
+                Err(err) => log::warn!("failed to load task file, the stored task is used! task={}, error={}", task_name, err.to_string()),
+            }
+            Ok(sub_event)
+        }
         Err(_) => load_task_from_json(file_path, chain, task_prefix, task_name)
     }
 }
 
+pub fn task_file(task_name: &str) -> String {
+    format!("task/{}.json", task_name)
+}
+
 fn load_task_from_rocksdb(rocksdb: RocksDB, task_prefix: &str, task: &str) -> Result<SubscribeEvent, ExpectedError> {
     let task_key = format!("{}:{}", task_prefix, task);
     let task_value = get_static(&rocksdb, &task_key)?;
-    Ok(SubscribeEvent::from(opt_to_result(task_value.as_object())?))
+    SubscribeEvent::from(opt_to_result(task_value.as_object())?)
 }
 
 pub fn load_task_from_json(file_path: &str, chain: &str, task_prefix: &str, task_name: &str) -> Result<SubscribeEvent, ExpectedError> {
+    let task_id = format!("{}:{}", task_prefix, task_name);
+    load_task_definition(file_path, task_id, chain, task_name)
+}
+
+fn load_task_definition(file_path: &str, task_id: String, chain: &str, task_name: &str) -> Result<SubscribeEvent, ExpectedError> {
     let json_str = fs::read_to_string(file_path)?;
     let json_value: Value = serde_json::from_str(json_str.as_str())?;
     let task_map = opt_to_result(json_value.as_object())?;
     let detail_map = get_object(task_map, task_name)?;
-    let task_id = format!("{}:{}", task_prefix, task_name);
-    Ok(SubscribeEvent::load(task_id, String::from(task_name), String::from(chain), detail_map))
+    SubscribeEvent::load(task_id, String::from(task_name), String::from(chain), detail_map)
+}
+
+/*
+ * Diffs the task file against the live task, `sub_event` is left as it is.
+ */
+pub fn reload_diff(sub_event: &SubscribeEvent) -> Result<Vec<String>, ExpectedError> {
+    let mut reloaded = sub_event.clone();
+    Ok(reloaded.reload(load_task_definition(&task_file(&sub_event.task), sub_event.task_id.clone(), &sub_event.chain, &sub_event.task)?))
+}
+
+fn reload_handler(sub_event: &mut SubscribeEvent, senders: &MultiSender) -> Result<(), ExpectedError> {
+    let definition = load_task_definition(&task_file(&sub_event.task), sub_event.task_id.clone(), &sub_event.chain, &sub_event.task)?;
+    let changed = sub_event.reload(definition);
+    if !changed.is_empty() {
+        log::info!("task reloaded! task={}, changed={}", sub_event.task, changed.join(","));
+        if sub_event.status != SubscribeStatus::Removed {
+            task_syncer(sub_event, senders);
+        }
+    }
+    Ok(())
 }
 
 pub fn is_value_created(res_body: &Map<String, Value>, value_name: &str) -> bool {
@@ -55,16 +99,29 @@ pub fn error_handler(err: ExpectedError, sub_event: &mut SubscribeEvent, senders
         ExpectedError::BlockHeightError(err) => log::info!("{}", err.to_string()),
         ExpectedError::FilterError(err) => {
             log::info!("{}", err.to_string());
-            task_syncer(sub_event, senders);
-            sub_event.next_idx();
+            success_handler(sub_event, senders);
         }
         _ => {
+            libs::metrics::TASK_ERRORS.with_label_values(&[&sub_event.task, err.variant()]).inc();
+            let prev_status = sub_event.status.clone();
             sub_event.handle_error(&rocks_sender, err.to_string());
-            let _ = libs::error::error_handler(senders.get("slack"), err);
+            if sub_event.status != prev_status {
+                status_handler(sub_event, senders);
+            }
+            let _ = libs::error::error_handler(senders.get("alert"), &sub_event.task, err);
         }
     };
 }
 
+/*
+ * Moves the task to the next index, and lets AlertPlugin know that the task succeeded again.
+ */
+pub fn success_handler(sub_event: &mut SubscribeEvent, senders: &MultiSender) {
+    task_syncer(sub_event, senders);
+    sub_event.next_idx();
+    libs::error::resolve_handler(senders.get("alert"), &sub_event.task);
+}
+
 pub fn task_syncer(sub_event: &SubscribeEvent, senders: &MultiSender) {
     let task = SubscribeTask::from(sub_event, String::from(""));
     let rocks_sender = senders.get("rocks");
@@ -93,15 +150,24 @@ pub fn message_handler(message: Value, sub_event: &mut SubscribeEvent, senders:
             sub_event.status(SubscribeStatus::Removed);
             let _ = libs::rocks::delete(&rocks_sender, sub_event.get_task_id());
         }
+        TaskMethod::Reload => return reload_handler(sub_event, senders),
     };
+    status_handler(sub_event, senders);
     Ok(())
 }
 
-pub fn response_verifier(response: &Map<String, Value>, task_name: &str, value_name: &str, condition: String) -> Result<(), ExpectedError> {
+pub fn status_handler(sub_event: &SubscribeEvent, senders: &MultiSender) {
+    let status = sub_event.status.value();
+    let msg = format!("task status changed! task={}, status={}", sub_event.task, status);
+    let data = json!({"task_id": sub_event.task_id, "status": status, "curr_idx": sub_event.curr_idx});
+    libs::webhook::event_handler(senders.get("webhook"), WebhookEventType::TaskStatus, &sub_event.task, &msg, data);
+}
+
+pub fn response_verifier(response: &Map<String, Value>, task_name: &str, value_name: &str, filter: &Filter) -> Result<(), ExpectedError> {
     if !is_value_created(response, value_name) {
         return Err(ExpectedError::BlockHeightError(format!("waiting for data created...task={}", task_name)));
     }
-    if !filter(response, condition)? {
+    if !filter.matches(response) {
         return Err(ExpectedError::FilterError(format!("not matched filter condition! task={}", task_name)));
     }
     Ok(())
@@ -0,0 +1,87 @@
+use std::thread;
+use std::time::Duration;
+
+use appbase::prelude::*;
+use serde_json::{json, Map, Value};
+
+use crate::error::error::ExpectedError;
+use crate::libs;
+use crate::libs::subscribe::task_loader;
+use crate::plugin::postgres::{Pool, PostgresMsg, PostgresPlugin};
+use crate::plugin::rocks::RocksPlugin;
+use crate::types::channel::MultiSender;
+use crate::types::subscribe::SubscribeEvent;
+use crate::types::webhook::WebhookEventType;
+
+const CHAIN: &str = "optimism";
+const TASK_PREFIX: &str = "task:optimism";
+
+type VerifyFn = fn(&SubscribeEvent, &MultiSender, &Pool) -> Result<(), ExpectedError>;
+
+/*
+ * The task of a verify plugin, which verifies `curr_idx` against the saved rows and moves to the next index once it is verified.
+ * A verify function returns BlockHeightError while the rows it needs are not saved yet.
+ */
+pub struct VerifyTask {
+    task_name: &'static str,
+    sub_event: SubscribeEvent,
+    senders: MultiSender,
+    receiver: Receiver,
+    pool: Pool,
+}
+
+impl VerifyTask {
+    pub fn load(task_name: &'static str, task_file: &str) -> Self {
+        let rocksdb = APP.run_with::<RocksPlugin, _, _>(|rocks| rocks.get_db());
+        VerifyTask {
+            task_name,
+            sub_event: task_loader(rocksdb, task_file, CHAIN, TASK_PREFIX, task_name).expect(format!("failed to load task! task={}", task_name).as_str()),
+            senders: MultiSender::new(vec!("rocks", "postgres", "alert")),
+            receiver: APP.channels.subscribe(task_name),
+            pool: APP.run_with::<PostgresPlugin, _, _>(|postgres| postgres.get_pool()),
+        }
+    }
+
+    /*
+     * `poll_interval` is read on every poll, so a reloaded config applies without a restart.
+     */
+    pub fn start(self, poll_interval: fn() -> u64, verify: VerifyFn) {
+        let app = APP.quit_handle().unwrap();
+        Self::recv(self, poll_interval, verify, app);
+    }
+
+    fn recv(mut task: VerifyTask, poll_interval: fn() -> u64, verify: VerifyFn, app: QuitHandle) {
+        APP.spawn_blocking(move || {
+            if let Ok(message) = task.receiver.try_recv() {
+                if let Err(err) = libs::subscribe::message_handler(message, &mut task.sub_event, &task.senders) {
+                    let _ = libs::error::warn_handler(task.senders.get("alert"), task.task_name, err);
+                }
+            }
+            if task.sub_event.is_workable() {
+                match verify(&task.sub_event, &task.senders, &task.pool) {
+                    Ok(_) => libs::subscribe::success_handler(&mut task.sub_event, &task.senders),
+                    Err(err) => libs::subscribe::error_handler(err, &mut task.sub_event, &task.senders)
+                }
+            }
+            if !app.is_quitting() {
+                thread::sleep(Duration::from_millis(poll_interval()));
+                Self::recv(task, poll_interval, verify, app);
+            }
+        });
+    }
+}
+
+pub fn batch_root_mismatch_handler(senders: &MultiSender, task_name: &str, batch_type: &str, batch_index: String, l1_tx_hash: Option<String>, reported_root: String, computed_root: String) -> Result<(), ExpectedError> {
+    let mut mismatch = Map::new();
+    mismatch.insert(String::from("batch_type"), Value::String(String::from(batch_type)));
+    mismatch.insert(String::from("batch_index"), Value::String(batch_index));
+    mismatch.insert(String::from("l1_tx_hash"), json!(l1_tx_hash));
+    mismatch.insert(String::from("reported_root"), Value::String(reported_root));
+    mismatch.insert(String::from("computed_root"), Value::String(computed_root));
+    let _ = senders.get("postgres").send(PostgresMsg::new(String::from("optimism_batch_root_mismatches"), Value::Object(mismatch.clone())))?;
+
+    let err = ExpectedError::VerificationError(format!("batch root mismatched! task={}, mismatch={}", task_name, Value::Object(mismatch.clone())));
+    libs::webhook::event_handler(senders.get("webhook"), WebhookEventType::VerificationFailure, task_name, &err.to_string(), Value::Object(mismatch));
+    libs::error::error_handler(senders.get("alert"), task_name, err);
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use std::net::IpAddr;
+use std::time::Duration;
+
+use appbase::prelude::*;
+use bleu_net::{is_public_ip, NetError};
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::Sha256;
+
+use crate::error::error::ExpectedError;
+use crate::libs::metrics::{endpoint_label, RPC_REQUEST_SECONDS};
+use crate::plugin::webhook::WebhookMsg;
+use crate::types::enumeration::Enumeration;
+use crate::types::webhook::{WebhookEndpoint, WebhookEventType};
+
+type HmacSha256 = Hmac<Sha256>;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn event_handler(webhook_sender: Sender, event_type: WebhookEventType, task: &str, msg: &str, data: Value) {
+    // nothing subscribes to the webhook channel unless WebhookPlugin is activated
+    let _ = webhook_sender.send(WebhookMsg::new(event_type.value(), String::from(task), String::from(msg), data));
+}
+
+pub fn sign(secret: &str, body: &str) -> Result<String, ExpectedError> {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).map_err(|err| ExpectedError::InvalidError(err.to_string()))?;
+    mac.update(body.as_bytes());
+    Ok(format!("sha256={}", hex::encode(mac.finalize().into_bytes())))
+}
+
+/*
+ * The body is signed with the secret of the endpoint, so receivers can verify `X-Bleu-Signature`.
+ * Deliveries are posted concurrently, and a slow endpoint is cut off after `WEBHOOK_TIMEOUT`.
+ */
+pub async fn post(endpoint: &WebhookEndpoint, event: &str, body: &str) -> Result<(), ExpectedError> {
+    let client = reqwest::Client::new();
+    let mut req = client.post(endpoint.url.as_str())
+        .body(String::from(body))
+        .header("Content-Type", "application/json")
+        .header("X-Bleu-Event", event)
+        .timeout(WEBHOOK_TIMEOUT);
+    if let Some(secret) = &endpoint.secret {
+        req = req.header("X-Bleu-Signature", sign(secret, body)?);
+    }
+    let timer = RPC_REQUEST_SECONDS.with_label_values(&[&endpoint_label(&endpoint.url)]).start_timer();
+    let res = req.send().await?;
+    timer.observe_duration();
+    let status = res.status();
+    if !status.is_success() {
+        let body = res.text().await.unwrap_or_default();
+        return Err(ExpectedError::RequestError(format!("webhook failed! endpoint={}, status={}, body={}", endpoint.name, status, body)));
+    }
+    Ok(())
+}
+
+/*
+ * Returns the response status, so the caller can log a delivery whether it succeeded or not.
+ */
+pub fn post_blocking(url: &str, secret: &str, event: &str, body: &str) -> Result<u16, ExpectedError> {
+    let client = public_client(url)?;
+    let timer = RPC_REQUEST_SECONDS.with_label_values(&[&endpoint_label(url)]).start_timer();
+    let res = client.post(url)
+        .body(String::from(body))
+        .header("Content-Type", "application/json")
+        .header("X-Bleu-Event", event)
+        .header("X-Bleu-Signature", sign(secret, body)?)
+        .timeout(WEBHOOK_TIMEOUT)
+        .send()?;
+    timer.observe_duration();
+    Ok(res.status().as_u16())
+}
+
+/*
+ * Callback urls are registered by anyone through bleu-server, so redirects are not followed and only public addresses are connected.
+ * A domain is pinned to the address checked by `resolve_public`, so it cannot be rebound to a private address before the request.
+ */
+fn public_client(url: &str) -> Result<reqwest::blocking::Client, ExpectedError> {
+    let target = bleu_net::resolve_public(url)?;
+    let parsed_url = reqwest::Url::parse(url).map_err(|err| ExpectedError::InvalidError(err.to_string()))?;
+    let host = parsed_url.host_str().unwrap_or_default().trim_start_matches('[').trim_end_matches(']');
+    let mut builder = reqwest::blocking::Client::builder().redirect(reqwest::redirect::Policy::none());
+    match host.parse::<IpAddr>() {
+        Ok(ip) if !is_public_ip(&ip) => return Err(NetError::NotPublic { host: String::from(host), ip }.into()),
+        Ok(_) => {}
+        Err(_) if host == target.host => builder = builder.resolve(host, target.addrs[0]),
+        Err(_) => return Err(ExpectedError::InvalidError(format!("callback host is not the checked host! host={}, checked={}", host, target.host))),
+    }
+    Ok(builder.build()?)
+}
+
+#[cfg(test)]
+mod webhook {
+    use crate::libs::webhook::sign;
+
+    #[test]
+    fn sign_test() {
+        let signature = sign("Jefe", "what do ya want for nothing?").unwrap();
+        assert_eq!(signature, "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+    }
+}
@@ -1,12 +1,23 @@
 use appbase::prelude::*;
 
+use crate::plugin::address_webhook::AddressWebhookPlugin;
+use crate::plugin::email::EmailPlugin;
+use crate::plugin::l1_bridge_transfer::L1BridgeTransferPlugin;
+use crate::plugin::l1_cross_domain_message::L1CrossDomainMessagePlugin;
 use crate::plugin::l1_tx_log::L1TxLogPlugin;
 use crate::plugin::l2_block_tx::L2BlockTxPlugin;
+use crate::plugin::l2_chain_stat::L2ChainStatPlugin;
 use crate::plugin::l2_enqueue::L2EnqueuePlugin;
 use crate::plugin::l2_state_batch::L2StateBatchPlugin;
+use crate::plugin::l2_state_batch_verify::L2StateBatchVerifyPlugin;
+use crate::plugin::l2_state_root_verify::L2StateRootVerifyPlugin;
 use crate::plugin::l2_tx_batch::L2TxBatchPlugin;
+use crate::plugin::l2_tx_batch_verify::L2TxBatchVerifyPlugin;
 use crate::plugin::l2_tx_receipt::L2TxReceiptPlugin;
+use crate::plugin::liveness::LivenessPlugin;
+use crate::plugin::postgres::PostgresPlugin;
 use crate::plugin::task::TaskPlugin;
+use crate::plugin::webhook::WebhookPlugin;
 
 mod plugin;
 mod types;
@@ -22,15 +33,62 @@ fn main() {
     APP.register::<L2TxReceiptPlugin>();
     APP.register::<L2EnqueuePlugin>();
     APP.register::<L1TxLogPlugin>();
+    APP.register::<L1CrossDomainMessagePlugin>();
+    APP.register::<L1BridgeTransferPlugin>();
+    APP.register::<L2StateRootVerifyPlugin>();
+    APP.register::<L2TxBatchVerifyPlugin>();
+    APP.register::<L2StateBatchVerifyPlugin>();
+    APP.register::<L2ChainStatPlugin>();
+    APP.register::<LivenessPlugin>();
     APP.register::<TaskPlugin>();
+    APP.register::<EmailPlugin>();
+    APP.register::<WebhookPlugin>();
+    APP.register::<AddressWebhookPlugin>();
     APP.init();
-    APP.plugin_init::<L2BlockTxPlugin>();
-    APP.plugin_init::<L2TxBatchPlugin>();
-    APP.plugin_init::<L2StateBatchPlugin>();
-    APP.plugin_init::<L2TxReceiptPlugin>();
-    APP.plugin_init::<L2EnqueuePlugin>();
-    APP.plugin_init::<L1TxLogPlugin>();
+    if let Err(err) = libs::config::load() {
+        log::error!("{}", err.to_string());
+        std::process::exit(1);
+    }
+    /* a dry run only plans the migrations, so nothing else is initialized or started */
+    if libs::config::get().postgres.migration_dry_run {
+        APP.plugin_init::<PostgresPlugin>();
+        return;
+    }
+    for plugin in libs::config::get().task.plugins.iter() {
+        task_plugin_init(plugin);
+    }
+    APP.plugin_init::<LivenessPlugin>();
     APP.plugin_init::<TaskPlugin>();
+    if libs::config::get().email.activate {
+        APP.plugin_init::<EmailPlugin>();
+    }
+    if libs::config::get().webhook.activate {
+        APP.plugin_init::<WebhookPlugin>();
+    }
+    if libs::config::get().address_webhook.activate {
+        APP.plugin_init::<AddressWebhookPlugin>();
+    }
     APP.startup();
     APP.execute();
 }
+
+/*
+ * Every task plugin is registered so that its options are known, but only the ones enabled by `task::plugins` are initialized.
+ */
+fn task_plugin_init(plugin: &str) {
+    match plugin {
+        "l2_block_tx" => APP.plugin_init::<L2BlockTxPlugin>(),
+        "l2_tx_batch" => APP.plugin_init::<L2TxBatchPlugin>(),
+        "l2_state_batch" => APP.plugin_init::<L2StateBatchPlugin>(),
+        "l2_tx_receipt" => APP.plugin_init::<L2TxReceiptPlugin>(),
+        "l2_enqueue" => APP.plugin_init::<L2EnqueuePlugin>(),
+        "l1_tx_log" => APP.plugin_init::<L1TxLogPlugin>(),
+        "l1_cross_domain_message" => APP.plugin_init::<L1CrossDomainMessagePlugin>(),
+        "l1_bridge_transfer" => APP.plugin_init::<L1BridgeTransferPlugin>(),
+        "l2_state_root_verify" => APP.plugin_init::<L2StateRootVerifyPlugin>(),
+        "l2_tx_batch_verify" => APP.plugin_init::<L2TxBatchVerifyPlugin>(),
+        "l2_state_batch_verify" => APP.plugin_init::<L2StateBatchVerifyPlugin>(),
+        "l2_chain_stat" => APP.plugin_init::<L2ChainStatPlugin>(),
+        _ => unreachable!("task::plugins is validated against TASK_PLUGINS! plugin={}", plugin),
+    }
+}
@@ -10,3 +10,13 @@ pub mod l2_state_batch;
 pub mod l2_tx_receipt;
 pub mod l2_enqueue;
 pub mod l1_tx_log;
+pub mod l2_state_root_verify;
+pub mod l2_tx_batch_verify;
+pub mod l2_state_batch_verify;
+pub mod l1_cross_domain_message;
+pub mod l1_bridge_transfer;
+pub mod l2_chain_stat;
+pub mod liveness;
+pub mod alert;
+pub mod webhook;
+pub mod address_webhook;
@@ -0,0 +1,234 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use appbase::prelude::*;
+use clap::Arg;
+use serde_json::{json, Value};
+
+use crate::error::error::ExpectedError;
+use crate::libs;
+use crate::libs::rocks::{get_by_prefix_static, get_static};
+use crate::libs::serde::get_u64;
+use crate::plugin::postgres::{Pool, PostgresPlugin};
+use crate::plugin::rocks::{RocksDB, RocksPlugin};
+use crate::types::address_webhook::{AddressDirection, AddressWebhook, AddressWebhookDelivery, DeliveryStatus, IndexedLog, IndexedTx};
+use crate::types::channel::MultiSender;
+use crate::types::enumeration::Enumeration;
+
+#[appbase_plugin(PostgresPlugin, RocksPlugin)]
+pub struct AddressWebhookPlugin {
+    senders: Option<MultiSender>,
+    pool: Option<Pool>,
+    rocksdb: Option<RocksDB>,
+}
+
+const CURSOR_KEY: &str = "address_webhook:cursor";
+const DELIVERY_PREFIX: &str = "address_webhook:delivery:";
+const SELECT_WEBHOOKS: &str = "SELECT {optimism_address_webhooks.id} AS optimism_address_webhooks_id, address, direction, topic, callback_url, secret FROM {optimism_address_webhooks} WHERE active = true";
+const SELECT_LAST_IDS: &str = "SELECT \
+    (SELECT COALESCE(MAX({optimism_block_txs.id}), 0) FROM {optimism_block_txs}) AS tx_cursor, \
+    (SELECT COALESCE(MAX({optimism_tx_receipt_logs.id}), 0) FROM {optimism_tx_receipt_logs}) AS log_cursor";
+const SELECT_TXS: &str = "SELECT {optimism_block_txs.id} AS optimism_block_txs_id, hash, block_number::text AS block_number, from_address, to_address, value::text AS value FROM {optimism_block_txs} \
+    WHERE {optimism_block_txs.id} > $1 ORDER BY {optimism_block_txs.id} LIMIT $2";
+const SELECT_LOGS: &str = "SELECT {optimism_tx_receipt_logs.id} AS optimism_tx_receipt_logs_id, tx_hash, log_index::text AS log_index, block_number::text AS block_number, address, topics, data FROM {optimism_tx_receipt_logs} \
+    WHERE {optimism_tx_receipt_logs.id} > $1 ORDER BY {optimism_tx_receipt_logs.id} LIMIT $2";
+const INSERT_DELIVERY: &str = "INSERT INTO {optimism_address_webhook_deliveries} \
+    (webhook_id, event, tx_hash, log_index, status, attempt, response_status, error, delivered_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)";
+
+/*
+ * Cursors are the last `optimism_block_txs_id` and `optimism_tx_receipt_logs_id` matched against the subscriptions.
+ */
+struct Cursor {
+    tx_cursor: i64,
+    log_cursor: i64,
+}
+
+impl Plugin for AddressWebhookPlugin {
+    fn new() -> Self {
+        APP.options.arg(Arg::new("addresswebhook::activate").long("address-webhook-activate").takes_value(true));
+        APP.options.arg(Arg::new("addresswebhook::poll-interval").long("address-webhook-poll-interval").takes_value(true));
+        APP.options.arg(Arg::new("addresswebhook::batch-size").long("address-webhook-batch-size").takes_value(true));
+        APP.options.arg(Arg::new("addresswebhook::retry-count").long("address-webhook-retry-count").takes_value(true));
+        APP.options.arg(Arg::new("addresswebhook::retry-backoff").long("address-webhook-retry-backoff").takes_value(true));
+        APP.options.arg(Arg::new("addresswebhook::delivery-budget").long("address-webhook-delivery-budget").takes_value(true));
+        AddressWebhookPlugin {
+            senders: None,
+            pool: None,
+            rocksdb: None,
+        }
+    }
+
+    fn init(&mut self) {
+        self.senders = Some(MultiSender::new(vec!("rocks")));
+        self.pool = Some(APP.run_with::<PostgresPlugin, _, _>(|postgres| postgres.get_pool()));
+        self.rocksdb = Some(APP.run_with::<RocksPlugin, _, _>(|rocks| rocks.get_db()));
+    }
+
+    fn startup(&mut self) {
+        let senders = self.senders.take().unwrap();
+        let pool = self.pool.take().unwrap();
+        let rocksdb = self.rocksdb.take().unwrap();
+        let cursor = Self::load_cursor(&rocksdb, &pool).expect("failed to load address webhook cursor!");
+        let deliveries = Self::load_deliveries(&rocksdb).expect("failed to load pending address webhook deliveries!");
+        let app = APP.quit_handle().unwrap();
+
+        Self::recv(cursor, deliveries, senders, pool, app);
+    }
+
+    fn shutdown(&mut self) {}
+}
+
+impl AddressWebhookPlugin {
+    fn recv(mut cursor: Cursor, mut deliveries: Vec<AddressWebhookDelivery>, senders: MultiSender, pool: Pool, app: QuitHandle) {
+        APP.spawn_blocking(move || {
+            let now = libs::time::current_timestamp();
+            match Self::match_handler(&mut cursor, &senders, &pool, now) {
+                Ok(matched) => deliveries.extend(matched),
+                Err(err) => log::warn!("failed to match address webhooks! error={}", err.to_string()),
+            }
+            let deliveries = Self::delivery_handler(deliveries, &senders, &pool, now);
+            if !app.is_quitting() {
+                let poll_interval = libs::config::get().address_webhook.poll_interval;
+                thread::sleep(Duration::from_millis(poll_interval));
+                Self::recv(cursor, deliveries, senders, pool, app);
+            }
+        });
+    }
+
+    fn match_handler(cursor: &mut Cursor, senders: &MultiSender, pool: &Pool, now: u64) -> Result<Vec<AddressWebhookDelivery>, ExpectedError> {
+        let mut client = pool.get().map_err(|err| ExpectedError::PostgresError(err.to_string()))?;
+        let batch_size = libs::config::get().address_webhook.batch_size;
+        let webhooks = client.query(PostgresPlugin::resolve_query(SELECT_WEBHOOKS)?.as_str(), &[])?.iter()
+            .filter_map(|row| {
+                Some(AddressWebhook {
+                    id: row.get("optimism_address_webhooks_id"),
+                    address: row.get("address"),
+                    direction: AddressDirection::find(row.get("direction"))?,
+                    topic: row.get("topic"),
+                    callback_url: row.get("callback_url"),
+                    secret: row.get("secret"),
+                })
+            })
+            .collect::<Vec<AddressWebhook>>();
+
+        let mut deliveries = Vec::new();
+        let tx_rows = client.query(PostgresPlugin::resolve_query(SELECT_TXS)?.as_str(), &[&cursor.tx_cursor, &batch_size])?;
+        for row in tx_rows.iter() {
+            let tx = IndexedTx {
+                hash: row.get::<_, Option<String>>("hash").unwrap_or_default(),
+                block_number: row.get("block_number"),
+                from_address: row.get("from_address"),
+                to_address: row.get("to_address"),
+                value: row.get("value"),
+            };
+            deliveries.extend(webhooks.iter().filter(|webhook| webhook.match_tx(&tx)).map(|webhook| AddressWebhookDelivery::tx(webhook, &tx, now)));
+            cursor.tx_cursor = row.get("optimism_block_txs_id");
+        }
+        let log_rows = client.query(PostgresPlugin::resolve_query(SELECT_LOGS)?.as_str(), &[&cursor.log_cursor, &batch_size])?;
+        for row in log_rows.iter() {
+            let log = IndexedLog {
+                tx_hash: row.get::<_, Option<String>>("tx_hash").unwrap_or_default(),
+                log_index: row.get("log_index"),
+                block_number: row.get("block_number"),
+                address: row.get("address"),
+                topics: IndexedLog::parse_topics(row.get("topics")),
+                data: row.get("data"),
+            };
+            deliveries.extend(webhooks.iter().filter(|webhook| webhook.match_log(&log)).map(|webhook| AddressWebhookDelivery::log(webhook, &log, now)));
+            cursor.log_cursor = row.get("optimism_tx_receipt_logs_id");
+        }
+        let rocks_sender = senders.get("rocks");
+        for delivery in deliveries.iter() {
+            let _ = libs::rocks::save(&rocks_sender, delivery.delivery_key(DELIVERY_PREFIX), delivery)?;
+        }
+        if !tx_rows.is_empty() || !log_rows.is_empty() {
+            let _ = libs::rocks::save(&rocks_sender, String::from(CURSOR_KEY), json!({"tx_cursor": cursor.tx_cursor, "log_cursor": cursor.log_cursor}))?;
+        }
+        Ok(deliveries)
+    }
+
+    /*
+     * Every attempt is written to the delivery log, and deliveries which are not due yet are returned as they are.
+     * Once `delivery_budget` milliseconds are spent, the remaining deliveries wait for the next poll.
+     */
+    fn delivery_handler(deliveries: Vec<AddressWebhookDelivery>, senders: &MultiSender, pool: &Pool, now: u64) -> Vec<AddressWebhookDelivery> {
+        let rocks_sender = senders.get("rocks");
+        let delivery_budget = Duration::from_millis(libs::config::get().address_webhook.delivery_budget);
+        let started_at = Instant::now();
+        let mut pending = Vec::new();
+        for mut delivery in deliveries.into_iter() {
+            if !delivery.is_due(now) || started_at.elapsed() >= delivery_budget {
+                pending.push(delivery);
+                continue;
+            }
+            let (response_status, error) = match libs::webhook::post_blocking(&delivery.webhook.callback_url, &delivery.webhook.secret, &delivery.event.value(), &delivery.body) {
+                Ok(status) if (200..300).contains(&status) => (Some(status), None),
+                Ok(status) => (Some(status), Some(format!("unexpected response status! status={}", status))),
+                Err(err) => (None, Some(err.to_string())),
+            };
+            let attempt = delivery.attempt;
+            let status = match error {
+                None => DeliveryStatus::Delivered,
+                Some(_) => {
+                    let config = libs::config::get();
+                    if delivery.retry(now, config.address_webhook.retry_count, config.address_webhook.retry_backoff) { DeliveryStatus::Retrying } else { DeliveryStatus::Failed }
+                }
+            };
+            if let Err(err) = Self::delivery_logger(pool, &delivery, &status, attempt, response_status, error, now) {
+                log::warn!("failed to log address webhook delivery! error={}", err.to_string());
+            }
+            let saved = match status {
+                DeliveryStatus::Retrying => libs::rocks::save(&rocks_sender, delivery.delivery_key(DELIVERY_PREFIX), &delivery),
+                _ => libs::rocks::delete(&rocks_sender, delivery.delivery_key(DELIVERY_PREFIX)),
+            };
+            if let Err(err) = saved {
+                log::warn!("failed to save pending address webhook delivery! error={}", err.to_string());
+            }
+            if status == DeliveryStatus::Retrying {
+                pending.push(delivery);
+            }
+        }
+        pending
+    }
+
+    fn delivery_logger(pool: &Pool, delivery: &AddressWebhookDelivery, status: &DeliveryStatus, attempt: u32, response_status: Option<u16>, error: Option<String>, now: u64) -> Result<(), ExpectedError> {
+        let mut client = pool.get().map_err(|err| ExpectedError::PostgresError(err.to_string()))?;
+        let _ = client.execute(PostgresPlugin::resolve_query(INSERT_DELIVERY)?.as_str(), &[
+            &delivery.webhook.id,
+            &delivery.event.value(),
+            &delivery.tx_hash,
+            &delivery.log_index,
+            &status.value(),
+            &(attempt as i64),
+            &response_status.map(|response_status| response_status as i64),
+            &error,
+            &(now as i64),
+        ])?;
+        Ok(())
+    }
+
+    fn load_deliveries(rocksdb: &RocksDB) -> Result<Vec<AddressWebhookDelivery>, ExpectedError> {
+        match get_by_prefix_static(rocksdb, DELIVERY_PREFIX) {
+            Value::Array(saved) => Ok(saved.into_iter().map(serde_json::from_value::<AddressWebhookDelivery>).collect::<Result<Vec<_>, _>>()?),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /*
+     * Without a saved cursor, only the rows indexed after the first startup are matched.
+     */
+    fn load_cursor(rocksdb: &RocksDB, pool: &Pool) -> Result<Cursor, ExpectedError> {
+        if let Value::Object(saved) = get_static(rocksdb, CURSOR_KEY)? {
+            return Ok(Cursor {
+                tx_cursor: get_u64(&saved, "tx_cursor")? as i64,
+                log_cursor: get_u64(&saved, "log_cursor")? as i64,
+            });
+        }
+        let mut client = pool.get().map_err(|err| ExpectedError::PostgresError(err.to_string()))?;
+        let row = client.query_one(PostgresPlugin::resolve_query(SELECT_LAST_IDS)?.as_str(), &[])?;
+        Ok(Cursor {
+            tx_cursor: row.get("tx_cursor"),
+            log_cursor: row.get("log_cursor"),
+        })
+    }
+}
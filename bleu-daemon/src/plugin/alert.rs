@@ -0,0 +1,114 @@
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+use appbase::prelude::*;
+use clap::Arg;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{enumeration, libs, message};
+use crate::libs::serde::get_str;
+use crate::plugin::email::EmailAlertMsg;
+use crate::plugin::slack::{SlackHookMsg, SlackMsg, SlackPlugin};
+use crate::types::alert::{Alert, AlertNotice, AlertRoute, AlertState};
+use crate::types::channel::MultiSender;
+use crate::types::enumeration::Enumeration;
+
+#[appbase_plugin(SlackPlugin)]
+pub struct AlertPlugin {
+    routes: Option<Vec<AlertRoute>>,
+    senders: Option<MultiSender>,
+    monitor: Option<Receiver>,
+}
+
+message!((AlertMsg; {task: String}, {variant: String}, {msg_level: String}, {msg: String}); (AlertMethod; {Fire: "fire"}, {Resolve: "resolve"}));
+
+lazy_static! {
+    static ref FAILING_TASKS: RwLock<HashSet<String>> = RwLock::new(HashSet::new());
+}
+
+impl Plugin for AlertPlugin {
+    fn new() -> Self {
+        APP.options.arg(Arg::new("alert::dedup-window").long("alert-dedup-window").takes_value(true));
+        APP.options.arg(Arg::new("alert::resolve-after").long("alert-resolve-after").takes_value(true));
+        APP.options.arg(Arg::new("alert::routes").long("alert-routes").takes_value(true));
+        AlertPlugin {
+            routes: None,
+            senders: None,
+            monitor: None,
+        }
+    }
+
+    fn init(&mut self) {
+        self.routes = Some(libs::config::get().alert.routes.clone());
+        self.senders = Some(MultiSender::new(vec!("slack", "email")));
+        self.monitor = Some(APP.channels.subscribe("alert"));
+    }
+
+    fn startup(&mut self) {
+        let routes = self.routes.take().unwrap();
+        let senders = self.senders.take().unwrap();
+        let monitor = self.monitor.take().unwrap();
+        let app = APP.quit_handle().unwrap();
+
+        Self::recv(AlertState::default(), routes, senders, monitor, app);
+    }
+
+    fn shutdown(&mut self) {}
+}
+
+impl AlertPlugin {
+    pub fn is_failing(task: &str) -> bool {
+        FAILING_TASKS.read().unwrap().contains(task)
+    }
+
+    fn recv(mut state: AlertState, routes: Vec<AlertRoute>, senders: MultiSender, mut monitor: Receiver, app: QuitHandle) {
+        APP.spawn(async move {
+            let now = libs::time::current_timestamp();
+            if let Ok(msg) = monitor.try_recv() {
+                let parsed_msg = msg.as_object().unwrap();
+                let task = get_str(parsed_msg, "task").unwrap();
+                match AlertMethod::find(get_str(parsed_msg, "method").unwrap()) {
+                    Some(AlertMethod::Fire) => {
+                        let alert = Alert::new(
+                            task,
+                            get_str(parsed_msg, "variant").unwrap(),
+                            get_str(parsed_msg, "msg_level").unwrap(),
+                            get_str(parsed_msg, "msg").unwrap(),
+                        );
+                        if let Some(notice) = state.fire(alert, now) {
+                            Self::notice_handler(notice, &routes, &senders);
+                        }
+                    }
+                    Some(AlertMethod::Resolve) => state.recover(task, now),
+                    None => log::error!("unknown alert method! msg={}", msg),
+                }
+                *FAILING_TASKS.write().unwrap() = state.failing_tasks();
+            }
+            let config = libs::config::get();
+            for notice in state.tick(now, config.alert.dedup_window, config.alert.resolve_after) {
+                Self::notice_handler(notice, &routes, &senders);
+            }
+            if !app.is_quitting() {
+                tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+                Self::recv(state, routes, senders, monitor, app);
+            }
+        });
+    }
+
+    /*
+     * A notice matching a route goes to the hook of the route, otherwise to the hook of its level.
+     * Every notice is also collected into the email digest when EmailPlugin is activated.
+     */
+    fn notice_handler(notice: AlertNotice, routes: &[AlertRoute], senders: &MultiSender) {
+        let _ = senders.get("email").send(EmailAlertMsg::new(notice.task.clone(), notice.msg_level.clone(), notice.msg.clone(), false));
+        let slack_msg = match AlertRoute::find(routes, &notice.task, &notice.msg_level) {
+            Some(hook) => SlackHookMsg::new(String::from(hook), notice.msg),
+            None => SlackMsg::new(notice.msg_level, notice.msg),
+        };
+        if let Err(err) = senders.get("slack").send(slack_msg) {
+            log::error!("failed to send slack message! error={}", err.to_string());
+        }
+    }
+}
@@ -1,90 +1,127 @@
 use appbase::prelude::*;
-use lettre::{Message, SmtpTransport, Transport};
 use lettre::transport::smtp::authentication::Credentials;
 use serde::{Deserialize, Serialize};
-use serde_json::{json, Value};
+use serde_json::{json, Map, Value};
 
 use crate::error::error::ExpectedError;
 use crate::libs;
+use crate::libs::email::{parse_recipients, SmtpConfig};
 use crate::libs::serde::get_str;
 use crate::message;
+use crate::types::email::EmailDigest;
 
 #[appbase_plugin]
 pub struct EmailPlugin {
+    config: Option<SmtpConfig>,
     monitor: Option<Receiver>,
 }
 
 message!(EmailMsg; {to: String}, {subject: String}, {body: String});
+message!(EmailAlertMsg; {task: String}, {msg_level: String}, {msg: String}, {escalate: bool});
 
 impl Plugin for EmailPlugin {
     fn new() -> Self {
+        APP.options.arg(clap::Arg::new("email::activate").long("email-activate").takes_value(true));
         APP.options.arg(clap::Arg::new("email::smtp-username").long("smtp-username").takes_value(true));
         APP.options.arg(clap::Arg::new("email::smtp-password").long("smtp-password").takes_value(true));
         APP.options.arg(clap::Arg::new("email::smtp-relay").long("smtp-relay").takes_value(true));
+        APP.options.arg(clap::Arg::new("email::smtp-port").long("smtp-port").takes_value(true));
+        APP.options.arg(clap::Arg::new("email::smtp-tls").long("smtp-tls").takes_value(true));
         APP.options.arg(clap::Arg::new("email::from").long("email-from").takes_value(true));
         APP.options.arg(clap::Arg::new("email::reply-to").long("email-reply-to").takes_value(true));
+        APP.options.arg(clap::Arg::new("email::info").long("email-info").takes_value(true));
+        APP.options.arg(clap::Arg::new("email::warn").long("email-warn").takes_value(true));
+        APP.options.arg(clap::Arg::new("email::error").long("email-error").takes_value(true));
+        APP.options.arg(clap::Arg::new("email::escalation").long("email-escalation").takes_value(true));
+        APP.options.arg(clap::Arg::new("email::digest-interval").long("email-digest-interval").takes_value(true));
 
         EmailPlugin {
+            config: None,
             monitor: None,
         }
     }
 
     fn init(&mut self) {
+        self.config = Some(Self::load_config());
         self.monitor = Some(APP.channels.subscribe("email"));
     }
 
     fn startup(&mut self) {
+        let config = self.config.take().unwrap();
         let monitor = self.monitor.take().unwrap();
         let app = APP.quit_handle().unwrap();
-        Self::recv(monitor, app);
+        Self::recv(EmailDigest::new(libs::time::current_timestamp()), config, monitor, app);
     }
 
     fn shutdown(&mut self) {}
 }
 
 impl EmailPlugin {
-    fn recv(mut monitor: Receiver, app: QuitHandle) {
-        APP.spawn(async move {
+    fn recv(mut digest: EmailDigest, config: SmtpConfig, mut monitor: Receiver, app: QuitHandle) {
+        APP.spawn_blocking(move || {
+            let now = libs::time::current_timestamp();
             if let Ok(msg) = monitor.try_recv() {
                 let parsed_msg = msg.as_object().unwrap();
-
-                let to = get_str(parsed_msg, "to").unwrap();
-                let subject = get_str(parsed_msg, "subject").unwrap();
-                let body = get_str(parsed_msg, "body").unwrap();
-
-                if let Err(result) = Self::send(to, subject, body) {
-                    log::error!("{}", result);
+                if let Err(err) = Self::message_handler(parsed_msg, &mut digest, &config, now) {
+                    log::error!("failed to send email! error={}", err.to_string());
+                }
+            }
+            if digest.is_due(now, libs::config::get().email.digest_interval) {
+                for (msg_level, subject, body) in digest.flush(now) {
+                    if let Err(err) = libs::email::send(&config, &Self::recipients(&msg_level), &subject, &body) {
+                        log::error!("failed to send email digest! error={}", err.to_string());
+                    }
                 }
             }
             if !app.is_quitting() {
-                tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
-                Self::recv(monitor, app);
+                std::thread::sleep(std::time::Duration::from_millis(10));
+                Self::recv(digest, config, monitor, app);
             }
         });
     }
 
-    pub fn send(to: &str, subject: &str, body: &str) -> Result<(), ExpectedError> {
-        let smtp_username = libs::opt::get_value_str("email::smtp-username")?;
-        let smtp_password = libs::opt::get_value_str("email::smtp-password")?;
-        let credentials = Credentials::new(smtp_username, smtp_password);
-        let smtp_relay = libs::opt::get_value_str("email::smtp-relay")?;
-        let from = libs::opt::get_value_str("email::from")?;
-        let reply_to = libs::opt::get_value_str("email::reply-to")?;
-
-        let email = Message::builder()
-            .from(from.as_str().parse().unwrap())
-            .reply_to(reply_to.as_str().parse().unwrap())
-            .to(to.parse().unwrap())
-            .subject(subject)
-            .body(String::from(body))
-            .unwrap();
+    /*
+     * `EmailMsg` is sent as it is, alerts are collected into the digest unless they are escalated.
+     */
+    fn message_handler(parsed_msg: &Map<String, Value>, digest: &mut EmailDigest, config: &SmtpConfig, now: u64) -> Result<(), ExpectedError> {
+        if let Ok(to) = get_str(parsed_msg, "to") {
+            return libs::email::send(config, &parse_recipients(to), get_str(parsed_msg, "subject")?, get_str(parsed_msg, "body")?);
+        }
+        let task = get_str(parsed_msg, "task")?;
+        let msg_level = get_str(parsed_msg, "msg_level")?;
+        let msg = get_str(parsed_msg, "msg")?;
+        if parsed_msg.get("escalate").and_then(|escalate| escalate.as_bool()).unwrap_or(false) {
+            let subject = format!("[bleu-daemon] escalation! task={}", task);
+            return libs::email::send(config, &Self::recipients("escalation"), &subject, msg);
+        }
+        digest.push(msg_level, task, msg, now);
+        Ok(())
+    }
 
-        let mailer = SmtpTransport::relay(smtp_relay.as_str())
-            .unwrap()
-            .credentials(credentials)
-            .build();
+    /*
+     * Escalations go to the `error` recipients when no `escalation` recipient is given.
+     */
+    fn recipients(msg_level: &str) -> Vec<String> {
+        let recipients = parse_recipients(libs::config::get().email.recipients(msg_level));
+        if recipients.is_empty() && msg_level == "escalation" {
+            return Self::recipients("error");
+        }
+        recipients
+    }
 
-        let _ = mailer.send(&email)?;
-        Ok(())
+    fn load_config() -> SmtpConfig {
+        let config = libs::config::get().email.clone();
+        let credentials = match (config.smtp_username, config.smtp_password) {
+            (Some(smtp_username), Some(smtp_password)) => Some(Credentials::new(smtp_username, smtp_password)),
+            _ => None,
+        };
+        SmtpConfig {
+            relay: config.smtp_relay,
+            port: config.smtp_port,
+            tls: config.smtp_tls,
+            credentials,
+            from: config.from,
+            reply_to: config.reply_to,
+        }
     }
 }
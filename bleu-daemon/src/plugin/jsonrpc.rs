@@ -1,19 +1,34 @@
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
-use std::str::FromStr;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::Duration;
 
 use appbase::prelude::*;
 use clap::Arg;
 use jsonrpc_core::{IoHandler, RpcMethodSimple, RpcMethodSync};
-use jsonrpc_http_server::{CloseHandle, ServerBuilder};
+use jsonrpc_http_server::{CloseHandle, RequestMiddlewareAction, Response, ServerBuilder};
+use jsonrpc_http_server::hyper::{Body, Request, StatusCode};
+use jsonrpc_http_server::hyper::header::HeaderValue;
+use serde_json::json;
+
+use crate::error::error::ExpectedError;
+use crate::libs;
+use crate::types::health::{HealthCheck, HealthChecker, HealthReport};
 
 #[appbase_plugin]
 pub struct JsonRpcPlugin {
     io: Option<IoHandler>,
+    health_checks: Option<Vec<(String, HealthChecker)>>,
     server: Option<CloseHandle>,
 }
 
+const METRICS_PATH: &str = "/metrics";
+const HEALTH_PATH: &str = "/health";
+const READY_PATH: &str = "/ready";
+const READY_PROBE_INTERVAL: u64 = 1000;
+
 /*
- * `add_sync_method` and `add_method` SHOULD be called during plugin initialization.
+ * `add_sync_method`, `add_method` and `add_health_check` SHOULD be called during plugin initialization.
  * After JsonRpcPlugin starts, IoHandler moves into closure, so not available to access from plugin.
  */
 impl JsonRpcPlugin {
@@ -32,6 +47,58 @@ impl JsonRpcPlugin {
             None => log::error!("add method not available"),
         }
     }
+
+    /*
+     * `/ready` responds 200 only when every registered check passed in the last probe.
+     */
+    pub fn add_health_check<F>(&mut self, name: String, check: F) where F: Fn() -> Result<(), ExpectedError> + Send + Sync + 'static {
+        match self.health_checks.as_mut() {
+            Some(health_checks) => health_checks.push((name, Box::new(check))),
+            None => log::error!("add health check not available"),
+        }
+    }
+
+    /*
+     * Prometheus scrapes `GET /metrics` and the orchestrator probes `GET /health` and `GET /ready` on the jsonrpc port.
+     * Every other request goes to the IoHandler.
+     */
+    fn http_middleware(request: Request<Body>, readiness: &RwLock<HealthReport>) -> RequestMiddlewareAction {
+        match request.uri().path() {
+            METRICS_PATH => Response {
+                code: StatusCode::OK,
+                content_type: HeaderValue::from_static(prometheus::TEXT_FORMAT),
+                content: libs::metrics::gather(),
+            }.into(),
+            HEALTH_PATH => Self::health_response(HealthReport::new(vec![HealthCheck::new("process", Ok(()))])),
+            READY_PATH => Self::health_response(readiness.read().unwrap().clone()),
+            _ => request.into(),
+        }
+    }
+
+    /*
+     * The checks may block on RocksDB or PostgreSQL, so they run in the background and `/ready` responds with the last report.
+     */
+    fn ready_prober(health_checks: Arc<Vec<(String, HealthChecker)>>, readiness: Arc<RwLock<HealthReport>>, app: QuitHandle) {
+        APP.spawn_blocking(move || {
+            let checks = health_checks.iter()
+                .map(|(name, check)| HealthCheck::new(name, check()))
+                .collect::<Vec<HealthCheck>>();
+            *readiness.write().unwrap() = HealthReport::new(checks);
+            if !app.is_quitting() {
+                thread::sleep(Duration::from_millis(READY_PROBE_INTERVAL));
+                Self::ready_prober(health_checks, readiness, app);
+            }
+        });
+    }
+
+    fn health_response(report: HealthReport) -> RequestMiddlewareAction {
+        let code = if report.is_ok() { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+        Response {
+            code,
+            content_type: HeaderValue::from_static("application/json; charset=utf-8"),
+            content: json!(report).to_string(),
+        }.into()
+    }
 }
 
 impl Plugin for JsonRpcPlugin {
@@ -40,20 +107,25 @@ impl Plugin for JsonRpcPlugin {
         APP.options.arg(Arg::new("jsonrpc::port").long("jsonrpc-port").takes_value(true));
         JsonRpcPlugin {
             io: None,
+            health_checks: None,
             server: None,
         }
     }
 
     fn init(&mut self) {
         self.io = Some(IoHandler::new());
+        self.health_checks = Some(Vec::new());
     }
 
     fn startup(&mut self) {
-        let host = APP.options.value_of("jsonrpc::host").unwrap_or(String::from("0.0.0.0"));
-        let port = APP.options.value_of_t::<u16>("jsonrpc::port").unwrap_or(9999);
+        let config = libs::config::get();
         let io = self.io.take().unwrap();
-        let socket = SocketAddr::new(IpAddr::V4(Ipv4Addr::from_str(&host).unwrap()), port);
-        if let Ok(server) = ServerBuilder::new(io).start_http(&socket) {
+        let health_checks = Arc::new(self.health_checks.take().unwrap());
+        let readiness = Arc::new(RwLock::new(HealthReport::new(vec![HealthCheck::new("probe", Err(ExpectedError::NoneError(String::from("readiness is not probed yet!"))))])));
+        Self::ready_prober(health_checks, readiness.clone(), APP.quit_handle().unwrap());
+        let socket = SocketAddr::new(IpAddr::V4(config.jsonrpc.host), config.jsonrpc.port);
+        let middleware = move |request: Request<Body>| Self::http_middleware(request, &readiness);
+        if let Ok(server) = ServerBuilder::new(io).request_middleware(middleware).start_http(&socket) {
             self.server = Some(server.close_handle());
             APP.spawn_blocking(|| {
                 server.wait();
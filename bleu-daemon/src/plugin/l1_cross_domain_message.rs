@@ -0,0 +1,126 @@
+use appbase::prelude::*;
+use clap::Arg;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::error::ExpectedError;
+use crate::libs;
+use crate::libs::convert::hex_to_decimal;
+use crate::libs::cross_domain::{FAILED_RELAYED_MESSAGE_TOPIC, RELAYED_MESSAGE_TOPIC, relayed_message_converter};
+use crate::libs::opt::opt_to_result;
+use crate::libs::request;
+use crate::libs::serde::{get_array, get_object, get_string};
+use crate::libs::subscribe::task_loader;
+use crate::message;
+use crate::plugin::alert::AlertPlugin;
+use crate::plugin::postgres::{PostgresMsg, PostgresPlugin};
+use crate::plugin::rocks::RocksPlugin;
+use crate::types::channel::MultiSender;
+use crate::types::subscribe::SubscribeEvent;
+
+#[appbase_plugin(RocksPlugin, PostgresPlugin, AlertPlugin)]
+pub struct L1CrossDomainMessagePlugin {
+    sub_event: Option<SubscribeEvent>,
+    senders: Option<MultiSender>,
+    receiver: Option<Receiver>,
+}
+
+const CHAIN: &str = "ethereum";
+const TASK_PREFIX: &str = "task:ethereum";
+const TASK_NAME: &str = "l1_cross_domain_message";
+const TASK_FILE: &str = "task/l1_cross_domain_message.json";
+
+message!(L1CrossDomainMessageMsg; {method: String});
+
+impl Plugin for L1CrossDomainMessagePlugin {
+    fn new() -> Self {
+        APP.options.arg(Arg::new("l1crossdomainmessage::poll-interval").long("l1crossdomainmessage-poll-interval").takes_value(true));
+        APP.options.arg(Arg::new("l1crossdomainmessage::messenger-address").long("l1crossdomainmessage-messenger-address").takes_value(true));
+        L1CrossDomainMessagePlugin {
+            sub_event: None,
+            senders: None,
+            receiver: None,
+        }
+    }
+
+    fn init(&mut self) {
+        let senders = MultiSender::new(vec!("rocks", "postgres", "alert"));
+        self.senders = Some(senders.to_owned());
+        self.receiver = Some(APP.channels.subscribe(TASK_NAME));
+        let rocksdb = APP.run_with::<RocksPlugin, _, _>(|rocks| rocks.get_db());
+        self.sub_event = Some(task_loader(rocksdb, TASK_FILE, CHAIN, TASK_PREFIX, TASK_NAME).expect(format!("failed to load task! task={}", TASK_NAME).as_str()));
+    }
+
+    fn startup(&mut self) {
+        let receiver = self.receiver.take().unwrap();
+        let sub_event = self.sub_event.take().unwrap();
+        let senders = self.senders.take().unwrap();
+        let app = APP.quit_handle().unwrap();
+
+        Self::recv(receiver, sub_event, senders, app);
+    }
+
+    fn shutdown(&mut self) {}
+}
+
+impl L1CrossDomainMessagePlugin {
+    fn recv(mut receiver: Receiver, mut sub_event: SubscribeEvent, senders: MultiSender, app: QuitHandle) {
+        APP.spawn(async move {
+            if let Ok(message) = receiver.try_recv() {
+                if let Err(err) = libs::subscribe::message_handler(message, &mut sub_event, &senders) {
+                    let _ = libs::error::warn_handler(senders.get("alert"), TASK_NAME, err);
+                }
+            }
+            if sub_event.is_workable() {
+                match Self::event_handler(&sub_event, &senders).await {
+                    Ok(_) => libs::subscribe::success_handler(&mut sub_event, &senders),
+                    Err(err) => libs::subscribe::error_handler(err, &mut sub_event, &senders)
+                }
+            }
+            if !app.is_quitting() {
+                let poll_interval = libs::config::get().l1_cross_domain_message.poll_interval;
+                tokio::time::sleep(tokio::time::Duration::from_millis(poll_interval)).await;
+                Self::recv(receiver, sub_event, senders, app);
+            }
+        });
+    }
+
+    async fn event_handler(sub_event: &SubscribeEvent, senders: &MultiSender) -> Result<(), ExpectedError> {
+        let req_url = sub_event.active_node();
+        let hex_idx = format!("0x{:x}", sub_event.curr_idx);
+        let req_body = json!({
+                    "jsonrpc": "2.0",
+                    "method": "eth_getBlockByNumber",
+                    "params": [ hex_idx, false ],
+                    "id": 1
+                });
+        let response = request::post_async(req_url.as_str(), req_body.to_string().as_str()).await?;
+        let _ = libs::subscribe::response_verifier(&response, TASK_NAME, "result", sub_event.get_filter())?;
+        let block = get_object(&response, "result")?;
+        let block_timestamp = hex_to_decimal(get_string(block, "timestamp")?)?;
+
+        let messenger_address = libs::config::get().l1_cross_domain_message.address.clone();
+        let req_body = json!({
+                    "jsonrpc": "2.0",
+                    "method": "eth_getLogs",
+                    "params": [{
+                        "fromBlock": hex_idx,
+                        "toBlock": hex_idx,
+                        "address": messenger_address,
+                        "topics": [[ RELAYED_MESSAGE_TOPIC, FAILED_RELAYED_MESSAGE_TOPIC ]]
+                    }],
+                    "id": 1
+                });
+        let response = request::post_async(req_url.as_str(), req_body.to_string().as_str()).await?;
+        if !libs::subscribe::is_value_created(&response, "result") {
+            return Err(ExpectedError::NoneError(format!("logs does not created! response={:?}, block_number={}", response, sub_event.curr_idx)));
+        }
+        let logs = get_array(&response, "result")?;
+        let pg_sender = senders.get("postgres");
+        for log in logs.iter() {
+            let relayed_message = relayed_message_converter(opt_to_result(log.as_object())?, block_timestamp.clone())?;
+            let _ = pg_sender.send(PostgresMsg::new(String::from("optimism_cross_domain_messages"), Value::Object(relayed_message)))?;
+        }
+        Ok(())
+    }
+}
@@ -9,20 +9,20 @@ use serde_json::{json, Value};
 
 use crate::{libs, validation};
 use crate::error::error::ExpectedError;
-use crate::libs::convert::hex_to_decimal_converter;
 use crate::libs::opt::opt_to_result;
 use crate::libs::request;
 use crate::libs::serde::{get_array, get_u64};
 use crate::libs::subscribe::{load_retry_queue, load_task_from_json, remove_from_retry_queue, save_retry_queue};
 use crate::message;
+use crate::plugin::alert::AlertPlugin;
 use crate::plugin::jsonrpc::JsonRpcPlugin;
 use crate::plugin::postgres::{PostgresMsg, PostgresPlugin};
 use crate::plugin::rocks::RocksPlugin;
-use crate::plugin::slack::SlackPlugin;
 use crate::types::channel::MultiSender;
 use crate::types::subscribe::{RetryJob, SubscribeEvent};
+use crate::types::webhook::WebhookEventType;
 
-#[appbase_plugin(RocksPlugin, PostgresPlugin, SlackPlugin)]
+#[appbase_plugin(RocksPlugin, PostgresPlugin, AlertPlugin)]
 pub struct L1TxLogPlugin {
     sub_event: Option<SubscribeEvent>,
     senders: Option<MultiSender>,
@@ -36,9 +36,7 @@ const TASK_NAME: &str = "l1_tx_log";
 const TASK_FILE: &str = "task/l1_tx_log.json";
 const TOPIC0: &str = "0x4b388aecf9fa6cc92253704e5975a6129a4f735bdbd99567df4ed0094ee4ceb5";
 const RETRY_PREFIX: &str = "retry:ethereum:l1_tx_log";
-const DEFAULT_RETRY_COUNT: u32 = 3;
 const RETRY_METHOD: &str = "retry_l1_tx_log";
-const DEFAULT_RETRY_ENDPOINT: &str = "http://0.0.0.0:9999";
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 struct L1TxLogRetryJob {
@@ -81,7 +79,7 @@ impl Plugin for L1TxLogPlugin {
     }
 
     fn init(&mut self) {
-        let senders = MultiSender::new(vec!("rocks", "postgres", "slack", "l1_tx_log"));
+        let senders = MultiSender::new(vec!("rocks", "postgres", "alert", "l1_tx_log"));
         self.senders = Some(senders.to_owned());
         self.receiver = Some(APP.channels.subscribe(TASK_NAME));
         self.sub_event = Some(load_task_from_json(TASK_FILE, CHAIN, TASK_PREFIX, TASK_NAME).expect(format!("failed to load task! task={}", TASK_NAME).as_str()));
@@ -107,13 +105,15 @@ impl L1TxLogPlugin {
     fn recv(mut receiver: Receiver, sub_event: SubscribeEvent, senders: MultiSender, mut retry_queue: HashMap<String, L1TxLogRetryJob>, app: QuitHandle) {
         APP.spawn(async move {
             if let Ok(message) = receiver.try_recv() {
-                if let Err(err) = Self::message_handler(message.clone(), &sub_event, &senders, &mut retry_queue).await {
-                    let _ = libs::error::error_handler(senders.get("slack"), err);
+                match Self::message_handler(message.clone(), &sub_event, &senders, &mut retry_queue).await {
+                    Ok(_) => libs::error::resolve_handler(senders.get("alert"), TASK_NAME),
+                    Err(err) => libs::error::error_handler(senders.get("alert"), TASK_NAME, err),
                 }
             }
             if let Err(err) = Self::retry_handler(&mut retry_queue, &sub_event, &senders).await {
-                let _ = libs::error::error_handler(senders.get("slack"), err);
+                let _ = libs::error::error_handler(senders.get("alert"), TASK_NAME, err);
             }
+            libs::metrics::TASK_RETRY_QUEUE_SIZE.with_label_values(&[TASK_NAME]).set(retry_queue.len() as i64);
 
             if !app.is_quitting() {
                 tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
@@ -129,7 +129,7 @@ impl L1TxLogPlugin {
 
         let pg_sender = senders.get("postgres");
         if let Err(err) = Self::log_syncer(block_number, queue_index, sub_event, &pg_sender).await {
-            let retry_count = libs::opt::get_value::<u32>("l1txlog::retry-count").unwrap_or(DEFAULT_RETRY_COUNT);
+            let retry_count = libs::config::get().l1_tx_log.retry_count;
             let retry_job = L1TxLogRetryJob::new(block_number, queue_index, retry_count);
             retry_queue.insert(retry_job.get_retry_id(), retry_job.clone());
             let rocks_sender = senders.get("rocks");
@@ -175,9 +175,9 @@ impl L1TxLogPlugin {
         for log_value in logs {
             let is_matched = Self::is_matched_log(log_value, queue_index);
             if is_matched.is_ok() && is_matched.unwrap() {
-                let mut converted_log = hex_to_decimal_converter(opt_to_result(log_value.as_object())?, vec!["blockNumber", "logIndex", "transactionIndex"])?;
-                converted_log.insert(String::from("queue_index"), Value::String(queue_index.to_string()));
-                let _ = pg_sender.send(PostgresMsg::new(String::from("ethereum_tx_logs"), Value::Object(converted_log)));
+                let mut log = opt_to_result(log_value.as_object())?.clone();
+                log.insert(String::from("queue_index"), Value::String(queue_index.to_string()));
+                let _ = pg_sender.send(PostgresMsg::new(String::from("ethereum_tx_logs"), Value::Object(log)));
                 return Ok(());
             }
         }
@@ -209,15 +209,19 @@ impl L1TxLogPlugin {
                     let _ = remove_from_retry_queue(&rocks_sender, retry_id.clone());
                 }
             }
-            let retry_endpoint = libs::opt::get_value_str("l1txlog::retry-endpoint").unwrap_or(DEFAULT_RETRY_ENDPOINT.to_string());
+            let retry_endpoint = libs::config::get().l1_tx_log.retry_endpoint.clone();
             if !manual_retry.is_empty() {
+                libs::metrics::TASK_DEAD_LETTERS.with_label_values(&[TASK_NAME]).inc_by(manual_retry.len() as u64);
                 let params = manual_retry.iter()
                     .map(|(block_number, queue_index)| {
                         format!("{{\"block_number\": {}, \"queue_index\": {}}}", block_number, queue_index)
                     })
                     .collect::<Vec<String>>().join(",");
                 let retry_query = libs::subscribe::retry_creator(format!("[{}]", params), RETRY_METHOD, retry_endpoint)?;
-                return Err(ExpectedError::RetryFailError(retry_query));
+                let err = ExpectedError::RetryFailError(retry_query.clone());
+                libs::error::escalation_handler(senders.get("email"), TASK_NAME, &err);
+                libs::webhook::event_handler(senders.get("webhook"), WebhookEventType::RetryExhausted, TASK_NAME, &err.to_string(), json!({"retry_query": retry_query, "count": manual_retry.len()}));
+                return Err(err);
             }
         }
         Ok(())
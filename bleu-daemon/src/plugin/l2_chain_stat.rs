@@ -0,0 +1,126 @@
+use std::thread;
+use std::time::Duration;
+
+use appbase::prelude::*;
+use clap::Arg;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::error::ExpectedError;
+use crate::libs;
+use crate::libs::stat::stat_ranges;
+use crate::libs::subscribe::task_loader;
+use crate::message;
+use crate::plugin::alert::AlertPlugin;
+use crate::plugin::postgres::{Pool, PostgresMsg, PostgresPlugin};
+use crate::plugin::rocks::RocksPlugin;
+use crate::types::channel::MultiSender;
+use crate::types::subscribe::SubscribeEvent;
+
+#[appbase_plugin(RocksPlugin, PostgresPlugin, AlertPlugin)]
+pub struct L2ChainStatPlugin {
+    sub_event: Option<SubscribeEvent>,
+    senders: Option<MultiSender>,
+    receiver: Option<Receiver>,
+    pool: Option<Pool>,
+}
+
+const CHAIN: &str = "optimism";
+const TASK_PREFIX: &str = "task:optimism";
+const TASK_NAME: &str = "l2_chain_stat";
+const TASK_FILE: &str = "task/l2_chain_stat.json";
+const SELECT_NEXT_TX: &str = "SELECT 1 FROM {optimism_block_txs} WHERE l1_timestamp >= $1 LIMIT 1";
+const SELECT_CHAIN_STAT: &str = "WITH txs AS (\
+        SELECT hash, from_address, gas_price, queue_origin FROM {optimism_block_txs} WHERE l1_timestamp >= $1 AND l1_timestamp < $2\
+    ) \
+    SELECT COUNT(*) AS tx_count, \
+    COUNT(DISTINCT t.from_address) AS active_addresses, \
+    COUNT(r.contract_address) AS new_contracts, \
+    COALESCE(SUM(r.gas_used), 0)::TEXT AS gas_used, \
+    COALESCE(ROUND(AVG(t.gas_price)), 0)::TEXT AS avg_gas_price, \
+    COUNT(*) FILTER (WHERE t.queue_origin = 'l1') AS deposit_count, \
+    (SELECT COUNT(*) FROM {optimism_tx_batches} WHERE batch_timestamp >= $1 AND batch_timestamp < $2) AS tx_batch_count, \
+    (SELECT COUNT(*) FROM {optimism_state_batches} WHERE batch_timestamp >= $1 AND batch_timestamp < $2) AS state_batch_count \
+    FROM txs t \
+    LEFT JOIN {optimism_tx_receipts} r ON r.tx_hash = t.hash";
+
+message!(L2ChainStatMsg; {method: String});
+
+impl Plugin for L2ChainStatPlugin {
+    fn new() -> Self {
+        APP.options.arg(Arg::new("l2chainstat::poll-interval").long("l2chainstat-poll-interval").takes_value(true));
+        L2ChainStatPlugin {
+            sub_event: None,
+            senders: None,
+            receiver: None,
+            pool: None,
+        }
+    }
+
+    fn init(&mut self) {
+        let senders = MultiSender::new(vec!("rocks", "postgres", "alert"));
+        self.senders = Some(senders.to_owned());
+        self.receiver = Some(APP.channels.subscribe(TASK_NAME));
+        self.pool = Some(APP.run_with::<PostgresPlugin, _, _>(|postgres| postgres.get_pool()));
+        let rocksdb = APP.run_with::<RocksPlugin, _, _>(|rocks| rocks.get_db());
+        self.sub_event = Some(task_loader(rocksdb, TASK_FILE, CHAIN, TASK_PREFIX, TASK_NAME).expect(format!("failed to load task! task={}", TASK_NAME).as_str()));
+    }
+
+    fn startup(&mut self) {
+        let receiver = self.receiver.take().unwrap();
+        let sub_event = self.sub_event.take().unwrap();
+        let senders = self.senders.take().unwrap();
+        let pool = self.pool.take().unwrap();
+        let app = APP.quit_handle().unwrap();
+
+        Self::recv(receiver, sub_event, senders, pool, app);
+    }
+
+    fn shutdown(&mut self) {}
+}
+
+impl L2ChainStatPlugin {
+    fn recv(mut receiver: Receiver, mut sub_event: SubscribeEvent, senders: MultiSender, pool: Pool, app: QuitHandle) {
+        APP.spawn_blocking(move || {
+            if let Ok(message) = receiver.try_recv() {
+                if let Err(err) = libs::subscribe::message_handler(message, &mut sub_event, &senders) {
+                    let _ = libs::error::warn_handler(senders.get("alert"), TASK_NAME, err);
+                }
+            }
+            if sub_event.is_workable() {
+                match Self::event_handler(&sub_event, &senders, &pool) {
+                    Ok(_) => libs::subscribe::success_handler(&mut sub_event, &senders),
+                    Err(err) => libs::subscribe::error_handler(err, &mut sub_event, &senders)
+                }
+            }
+            if !app.is_quitting() {
+                let poll_interval = libs::config::get().l2_chain_stat.poll_interval;
+                thread::sleep(Duration::from_millis(poll_interval));
+                Self::recv(receiver, sub_event, senders, pool, app);
+            }
+        });
+    }
+
+    fn event_handler(sub_event: &SubscribeEvent, senders: &MultiSender, pool: &Pool) -> Result<(), ExpectedError> {
+        let mut client = pool.get().map_err(|err| ExpectedError::PostgresError(err.to_string()))?;
+        let ranges = stat_ranges(sub_event.curr_idx);
+        // the hour bucket is complete once a later tx is indexed
+        let (_, _, hour_end) = ranges[0];
+        if client.query_opt(PostgresPlugin::resolve_query(SELECT_NEXT_TX)?.as_str(), &[&(hour_end as i64)])?.is_none() {
+            return Err(ExpectedError::BlockHeightError(format!("waiting for hour bucket closed...task={}, hour_idx={}", TASK_NAME, sub_event.curr_idx)));
+        }
+        for (granularity, start, end) in ranges {
+            let row = client.query_one(PostgresPlugin::resolve_query(SELECT_CHAIN_STAT)?.as_str(), &[&(start as i64), &(end as i64)])?;
+            let mut chain_stat = Map::new();
+            chain_stat.insert(String::from("granularity"), Value::String(String::from(granularity)));
+            chain_stat.insert(String::from("bucket_timestamp"), Value::String(start.to_string()));
+            for column in ["tx_count", "active_addresses", "new_contracts", "deposit_count", "tx_batch_count", "state_batch_count"].iter() {
+                chain_stat.insert(String::from(*column), Value::String(row.get::<_, i64>(*column).to_string()));
+            }
+            chain_stat.insert(String::from("gas_used"), Value::String(row.get("gas_used")));
+            chain_stat.insert(String::from("avg_gas_price"), Value::String(row.get("avg_gas_price")));
+            let _ = senders.get("postgres").send(PostgresMsg::new(String::from("optimism_chain_stats"), Value::Object(chain_stat)))?;
+        }
+        Ok(())
+    }
+}
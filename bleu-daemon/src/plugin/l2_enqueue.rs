@@ -9,14 +9,14 @@ use crate::libs::request;
 use crate::libs::serde::get_u64;
 use crate::libs::subscribe::{is_value_created, task_loader};
 use crate::message;
+use crate::plugin::alert::AlertPlugin;
 use crate::plugin::l1_tx_log::{L1TxLogMsg, L1TxLogPlugin};
 use crate::plugin::postgres::{PostgresMsg, PostgresPlugin};
 use crate::plugin::rocks::RocksPlugin;
-use crate::plugin::slack::SlackPlugin;
 use crate::types::channel::MultiSender;
 use crate::types::subscribe::SubscribeEvent;
 
-#[appbase_plugin(RocksPlugin, PostgresPlugin, SlackPlugin, L1TxLogPlugin)]
+#[appbase_plugin(RocksPlugin, PostgresPlugin, AlertPlugin, L1TxLogPlugin)]
 pub struct L2EnqueuePlugin {
     sub_event: Option<SubscribeEvent>,
     senders: Option<MultiSender>,
@@ -27,7 +27,6 @@ const CHAIN: &str = "optimism";
 const TASK_PREFIX: &str = "task:optimism";
 const TASK_NAME: &str = "l2_enqueue";
 const TASK_FILE: &str = "task/l2_enqueue.json";
-const DEFAULT_POLL_INTERVAL: u64 = 100;
 
 message!(L2EnqueueMsg; {method: String});
 
@@ -42,7 +41,7 @@ impl Plugin for L2EnqueuePlugin {
     }
 
     fn init(&mut self) {
-        let senders = MultiSender::new(vec!("rocks", "postgres", "slack", "l1_tx_log" /*"elasticsearch"*/));
+        let senders = MultiSender::new(vec!("rocks", "postgres", "alert", "l1_tx_log" /*"elasticsearch"*/));
         self.senders = Some(senders.to_owned());
         self.receiver = Some(APP.channels.subscribe(TASK_NAME));
         let rocksdb = APP.run_with::<RocksPlugin, _, _>(|rocks| rocks.get_db());
@@ -66,20 +65,17 @@ impl L2EnqueuePlugin {
         APP.spawn(async move {
             if let Ok(message) = receiver.try_recv() {
                 if let Err(err) = libs::subscribe::message_handler(message, &mut sub_event, &senders) {
-                    let _ = libs::error::warn_handler(senders.get("slack"), err);
+                    let _ = libs::error::warn_handler(senders.get("alert"), TASK_NAME, err);
                 }
             }
             if sub_event.is_workable() {
                 match Self::event_handler(&sub_event, &senders).await {
-                    Ok(_) => {
-                        libs::subscribe::task_syncer(&sub_event, &senders);
-                        sub_event.next_idx();
-                    }
+                    Ok(_) => libs::subscribe::success_handler(&mut sub_event, &senders),
                     Err(err) => libs::subscribe::error_handler(err, &mut sub_event, &senders)
                 }
             }
             if !app.is_quitting() {
-                let poll_interval = libs::opt::get_value::<u64>("l2enqueue::poll-interval").unwrap_or(DEFAULT_POLL_INTERVAL);
+                let poll_interval = libs::config::get().l2_enqueue.poll_interval;
                 tokio::time::sleep(tokio::time::Duration::from_millis(poll_interval)).await;
                 Self::recv(receiver, sub_event, senders, app);
             }
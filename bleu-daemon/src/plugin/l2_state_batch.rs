@@ -5,19 +5,18 @@ use serde_json::{json, Value};
 
 use crate::error::error::ExpectedError;
 use crate::libs;
-use crate::libs::convert::number_to_string_convert;
 use crate::libs::opt::opt_to_result;
 use crate::libs::request;
 use crate::libs::serde::{get_array, get_object};
 use crate::libs::subscribe::task_loader;
 use crate::message;
+use crate::plugin::alert::AlertPlugin;
 use crate::plugin::postgres::{PostgresMsg, PostgresPlugin};
 use crate::plugin::rocks::RocksPlugin;
-use crate::plugin::slack::SlackPlugin;
 use crate::types::channel::MultiSender;
 use crate::types::subscribe::SubscribeEvent;
 
-#[appbase_plugin(RocksPlugin, PostgresPlugin, SlackPlugin)]
+#[appbase_plugin(RocksPlugin, PostgresPlugin, AlertPlugin)]
 pub struct L2StateBatchPlugin {
     sub_event: Option<SubscribeEvent>,
     senders: Option<MultiSender>,
@@ -28,7 +27,6 @@ const CHAIN: &str = "optimism";
 const TASK_PREFIX: &str = "task:optimism";
 const TASK_NAME: &str = "l2_state_batch";
 const TASK_FILE: &str = "task/l2_state_batch.json";
-const DEFAULT_POLL_INTERVAL: u64 = 1000;
 
 message!(L2StateBatchMsg; {method: String});
 
@@ -43,7 +41,7 @@ impl Plugin for L2StateBatchPlugin {
     }
 
     fn init(&mut self) {
-        let senders = MultiSender::new(vec!("rocks", "postgres", "slack" /*"elasticsearch"*/));
+        let senders = MultiSender::new(vec!("rocks", "postgres", "alert" /*"elasticsearch"*/));
         self.senders = Some(senders.to_owned());
         self.receiver = Some(APP.channels.subscribe(TASK_NAME));
         let rocksdb = APP.run_with::<RocksPlugin, _, _>(|rocks| rocks.get_db());
@@ -67,20 +65,17 @@ impl L2StateBatchPlugin {
         APP.spawn(async move {
             if let Ok(message) = receiver.try_recv() {
                 if let Err(err) = libs::subscribe::message_handler(message, &mut sub_event, &senders) {
-                    let _ = libs::error::warn_handler(senders.get("slack"), err);
+                    let _ = libs::error::warn_handler(senders.get("alert"), TASK_NAME, err);
                 }
             }
             if sub_event.is_workable() {
                 match Self::event_handler(&sub_event, &senders).await {
-                    Ok(_) => {
-                        libs::subscribe::task_syncer(&sub_event, &senders);
-                        sub_event.next_idx();
-                    }
+                    Ok(_) => libs::subscribe::success_handler(&mut sub_event, &senders),
                     Err(err) => libs::subscribe::error_handler(err, &mut sub_event, &senders)
                 }
             }
             if !app.is_quitting() {
-                let poll_interval = libs::opt::get_value::<u64>("l2statebatch::poll-interval").unwrap_or(DEFAULT_POLL_INTERVAL);
+                let poll_interval = libs::config::get().l2_state_batch.poll_interval;
                 tokio::time::sleep(tokio::time::Duration::from_millis(poll_interval)).await;
                 Self::recv(receiver, sub_event, senders, app);
             }
@@ -92,17 +87,16 @@ impl L2StateBatchPlugin {
         let response = request::get_async(req_url.as_str()).await?;
         let _ = libs::subscribe::response_verifier(&response, TASK_NAME, "batch", sub_event.get_filter())?;
         let batch = get_object(&response, "batch")?;
-        let converted_batch = number_to_string_convert(batch, vec!["index", "blockNumber", "timestamp", "size", "prevTotalElements"])?;
         let pg_sender = senders.get("postgres");
         let _ = pg_sender.send(PostgresMsg::new(String::from("optimism_state_batches"), Value::Object(batch.clone())))?;
 
         let state_roots = get_array(&response, "stateRoots")?;
-        let l1_tx_hash = opt_to_result(converted_batch.get("l1TransactionHash"))?;
+        let l1_tx_hash = opt_to_result(batch.get("l1TransactionHash"))?;
         for state_root in state_roots.iter() {
             let state_root_map = opt_to_result(state_root.as_object())?;
-            let mut converted_state_root = number_to_string_convert(state_root_map, vec!["index", "batchIndex"])?;
-            converted_state_root.insert(String::from("l1_tx_hash"), l1_tx_hash.clone());
-            let _ = pg_sender.send(PostgresMsg::new(String::from("optimism_state_roots"), Value::Object(converted_state_root)))?;
+            let mut state_root_map = state_root_map.clone();
+            state_root_map.insert(String::from("l1_tx_hash"), l1_tx_hash.clone());
+            let _ = pg_sender.send(PostgresMsg::new(String::from("optimism_state_roots"), Value::Object(state_root_map)))?;
         }
         Ok(())
     }
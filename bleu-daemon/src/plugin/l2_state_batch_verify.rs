@@ -0,0 +1,75 @@
+use appbase::prelude::*;
+use clap::Arg;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::error::ExpectedError;
+use crate::libs;
+use crate::libs::merkle::{bytes32_to_hex, Bytes32, get_merkle_root, hex_to_bytes32};
+use crate::libs::opt::opt_to_result;
+use crate::libs::verify::VerifyTask;
+use crate::message;
+use crate::plugin::alert::AlertPlugin;
+use crate::plugin::postgres::{Pool, PostgresPlugin};
+use crate::plugin::rocks::RocksPlugin;
+use crate::types::channel::MultiSender;
+use crate::types::subscribe::SubscribeEvent;
+
+#[appbase_plugin(RocksPlugin, PostgresPlugin, AlertPlugin)]
+pub struct L2StateBatchVerifyPlugin {
+    task: Option<VerifyTask>,
+}
+
+const TASK_NAME: &str = "l2_state_batch_verify";
+const TASK_FILE: &str = "task/l2_state_batch_verify.json";
+const BATCH_TYPE: &str = "state";
+const SELECT_STATE_BATCH: &str = "SELECT batch_index, batch_size, batch_root, l1_tx_hash FROM {optimism_state_batches} WHERE batch_index = $1";
+const SELECT_STATE_ROOTS: &str = "SELECT index, value FROM {optimism_state_roots} WHERE batch_index = $1 ORDER BY index";
+
+message!(L2StateBatchVerifyMsg; {method: String});
+
+impl Plugin for L2StateBatchVerifyPlugin {
+    fn new() -> Self {
+        APP.options.arg(Arg::new("l2statebatchverify::poll-interval").long("l2statebatchverify-poll-interval").takes_value(true));
+        L2StateBatchVerifyPlugin {
+            task: None,
+        }
+    }
+
+    fn init(&mut self) {
+        self.task = Some(VerifyTask::load(TASK_NAME, TASK_FILE));
+    }
+
+    fn startup(&mut self) {
+        let task = self.task.take().unwrap();
+        task.start(|| libs::config::get().l2_state_batch_verify.poll_interval, Self::event_handler);
+    }
+
+    fn shutdown(&mut self) {}
+}
+
+impl L2StateBatchVerifyPlugin {
+    fn event_handler(sub_event: &SubscribeEvent, senders: &MultiSender, pool: &Pool) -> Result<(), ExpectedError> {
+        let mut client = pool.get().map_err(|err| ExpectedError::PostgresError(err.to_string()))?;
+        let batch_index = sub_event.curr_idx as i64;
+        let batch = match client.query_opt(PostgresPlugin::resolve_query(SELECT_STATE_BATCH)?.as_str(), &[&batch_index])? {
+            Some(batch) => batch,
+            None => return Err(ExpectedError::BlockHeightError(format!("waiting for state batch created...task={}, batch_index={}", TASK_NAME, batch_index))),
+        };
+        let batch_size = opt_to_result(batch.get::<_, Option<i64>>("batch_size"))? as usize;
+        let state_roots = client.query(PostgresPlugin::resolve_query(SELECT_STATE_ROOTS)?.as_str(), &[&batch_index])?;
+        if state_roots.len() < batch_size {
+            return Err(ExpectedError::BlockHeightError(format!("waiting for state roots created...task={}, batch_index={}, size={}, created={}", TASK_NAME, batch_index, batch_size, state_roots.len())));
+        }
+        let leaves = state_roots.iter()
+            .map(|state_root| Ok(hex_to_bytes32(opt_to_result(state_root.get::<_, Option<String>>("value"))?.as_str())?))
+            .collect::<Result<Vec<Bytes32>, ExpectedError>>()?;
+        let computed_root = bytes32_to_hex(&get_merkle_root(&leaves)?);
+        let reported_root = opt_to_result(batch.get::<_, Option<String>>("batch_root"))?;
+        if computed_root == reported_root.to_lowercase() {
+            return Ok(());
+        }
+        let l1_tx_hash: Option<String> = batch.get("l1_tx_hash");
+        libs::verify::batch_root_mismatch_handler(senders, TASK_NAME, BATCH_TYPE, batch_index.to_string(), l1_tx_hash, reported_root, computed_root)
+    }
+}
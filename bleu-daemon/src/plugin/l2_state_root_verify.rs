@@ -0,0 +1,85 @@
+use appbase::prelude::*;
+use clap::Arg;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::error::error::ExpectedError;
+use crate::libs;
+use crate::libs::verify::VerifyTask;
+use crate::message;
+use crate::plugin::alert::AlertPlugin;
+use crate::plugin::postgres::{Pool, PostgresMsg, PostgresPlugin};
+use crate::plugin::rocks::RocksPlugin;
+use crate::types::channel::MultiSender;
+use crate::types::subscribe::SubscribeEvent;
+use crate::types::webhook::WebhookEventType;
+
+#[appbase_plugin(RocksPlugin, PostgresPlugin, AlertPlugin)]
+pub struct L2StateRootVerifyPlugin {
+    task: Option<VerifyTask>,
+}
+
+const TASK_NAME: &str = "l2_state_root_verify";
+const TASK_FILE: &str = "task/l2_state_root_verify.json";
+const SELECT_STATE_ROOT: &str = "SELECT r.batch_index::text AS batch_index, r.value, r.l1_tx_hash, b.block_number::text AS block_number, b.hash, b.state_root \
+    FROM {optimism_state_roots} r \
+    LEFT JOIN {optimism_block_txs} t ON t.index = r.index \
+    LEFT JOIN {optimism_blocks} b ON b.hash = t.block_hash \
+    WHERE r.index = $1";
+
+message!(L2StateRootVerifyMsg; {method: String});
+
+impl Plugin for L2StateRootVerifyPlugin {
+    fn new() -> Self {
+        APP.options.arg(Arg::new("l2staterootverify::poll-interval").long("l2staterootverify-poll-interval").takes_value(true));
+        L2StateRootVerifyPlugin {
+            task: None,
+        }
+    }
+
+    fn init(&mut self) {
+        self.task = Some(VerifyTask::load(TASK_NAME, TASK_FILE));
+    }
+
+    fn startup(&mut self) {
+        let task = self.task.take().unwrap();
+        task.start(|| libs::config::get().l2_state_root_verify.poll_interval, Self::event_handler);
+    }
+
+    fn shutdown(&mut self) {}
+}
+
+impl L2StateRootVerifyPlugin {
+    fn event_handler(sub_event: &SubscribeEvent, senders: &MultiSender, pool: &Pool) -> Result<(), ExpectedError> {
+        let mut client = pool.get().map_err(|err| ExpectedError::PostgresError(err.to_string()))?;
+        let state_root_index = sub_event.curr_idx.to_string();
+        let row = match client.query_opt(PostgresPlugin::resolve_query(SELECT_STATE_ROOT)?.as_str(), &[&(sub_event.curr_idx as i64)])? {
+            Some(row) => row,
+            None => return Err(ExpectedError::BlockHeightError(format!("waiting for state root created...task={}, index={}", TASK_NAME, state_root_index))),
+        };
+        let block_hash: Option<String> = row.get("hash");
+        if block_hash.is_none() {
+            return Err(ExpectedError::BlockHeightError(format!("waiting for l2 block created...task={}, index={}", TASK_NAME, state_root_index)));
+        }
+        let expected_state_root: Option<String> = row.get("value");
+        let actual_state_root: Option<String> = row.get("state_root");
+        if expected_state_root == actual_state_root {
+            return Ok(());
+        }
+
+        let mut mismatch = Map::new();
+        mismatch.insert(String::from("index"), Value::String(state_root_index.clone()));
+        mismatch.insert(String::from("batch_index"), json!(row.get::<_, Option<String>>("batch_index")));
+        mismatch.insert(String::from("l1_tx_hash"), json!(row.get::<_, Option<String>>("l1_tx_hash")));
+        mismatch.insert(String::from("block_number"), json!(row.get::<_, Option<String>>("block_number")));
+        mismatch.insert(String::from("block_hash"), json!(block_hash));
+        mismatch.insert(String::from("expected_state_root"), json!(expected_state_root));
+        mismatch.insert(String::from("actual_state_root"), json!(actual_state_root));
+        let _ = senders.get("postgres").send(PostgresMsg::new(String::from("optimism_state_root_mismatches"), Value::Object(mismatch.clone())))?;
+
+        let err = ExpectedError::VerificationError(format!("state root mismatched! task={}, mismatch={}", TASK_NAME, Value::Object(mismatch.clone())));
+        libs::webhook::event_handler(senders.get("webhook"), WebhookEventType::VerificationFailure, TASK_NAME, &err.to_string(), Value::Object(mismatch));
+        libs::error::error_handler(senders.get("alert"), TASK_NAME, err);
+        Ok(())
+    }
+}
@@ -5,19 +5,18 @@ use serde_json::{json, Value};
 
 use crate::error::error::ExpectedError;
 use crate::libs;
-use crate::libs::convert::number_to_string_convert;
 use crate::libs::opt::opt_to_result;
 use crate::libs::request;
 use crate::libs::serde::{get_array, get_object};
 use crate::libs::subscribe::task_loader;
 use crate::message;
+use crate::plugin::alert::AlertPlugin;
 use crate::plugin::postgres::{PostgresMsg, PostgresPlugin};
 use crate::plugin::rocks::RocksPlugin;
-use crate::plugin::slack::SlackPlugin;
 use crate::types::channel::MultiSender;
 use crate::types::subscribe::SubscribeEvent;
 
-#[appbase_plugin(RocksPlugin, PostgresPlugin, SlackPlugin)]
+#[appbase_plugin(RocksPlugin, PostgresPlugin, AlertPlugin)]
 pub struct L2TxBatchPlugin {
     sub_event: Option<SubscribeEvent>,
     senders: Option<MultiSender>,
@@ -28,7 +27,6 @@ const CHAIN: &str = "optimism";
 const TASK_PREFIX: &str = "task:optimism";
 const TASK_NAME: &str = "l2_tx_batch";
 const TASK_FILE: &str = "task/l2_tx_batch.json";
-const DEFAULT_POLL_INTERVAL: u64 = 1000;
 
 message!(L2TxBatchMsg; {method: String});
 
@@ -43,7 +41,7 @@ impl Plugin for L2TxBatchPlugin {
     }
 
     fn init(&mut self) {
-        let senders = MultiSender::new(vec!("rocks", "postgres", "slack" /*"elasticsearch"*/));
+        let senders = MultiSender::new(vec!("rocks", "postgres", "alert" /*"elasticsearch"*/));
         self.senders = Some(senders.to_owned());
         self.receiver = Some(APP.channels.subscribe(TASK_NAME));
         let rocksdb = APP.run_with::<RocksPlugin, _, _>(|rocks| rocks.get_db());
@@ -67,20 +65,17 @@ impl L2TxBatchPlugin {
         APP.spawn(async move {
             if let Ok(message) = receiver.try_recv() {
                 if let Err(err) = libs::subscribe::message_handler(message, &mut sub_event, &senders) {
-                    let _ = libs::error::warn_handler(senders.get("slack"), err);
+                    let _ = libs::error::warn_handler(senders.get("alert"), TASK_NAME, err);
                 }
             }
             if sub_event.is_workable() {
                 match Self::event_handler(&sub_event, &senders).await {
-                    Ok(_) => {
-                        libs::subscribe::task_syncer(&sub_event, &senders);
-                        sub_event.next_idx();
-                    }
+                    Ok(_) => libs::subscribe::success_handler(&mut sub_event, &senders),
                     Err(err) => libs::subscribe::error_handler(err, &mut sub_event, &senders)
                 }
             }
             if !app.is_quitting() {
-                let poll_interval = libs::opt::get_value::<u64>("l2txbatch::poll-interval").unwrap_or(DEFAULT_POLL_INTERVAL);
+                let poll_interval = libs::config::get().l2_tx_batch.poll_interval;
                 tokio::time::sleep(tokio::time::Duration::from_millis(poll_interval)).await;
                 Self::recv(receiver, sub_event, senders, app);
             }
@@ -92,17 +87,16 @@ impl L2TxBatchPlugin {
         let response = request::get_async(req_url.as_str()).await?;
         let _ = libs::subscribe::response_verifier(&response, TASK_NAME, "batch", sub_event.get_filter())?;
         let batch = get_object(&response, "batch")?;
-        let converted_batch = number_to_string_convert(batch, vec!["index", "timestamp", "size", "blockNumber", "prevTotalElements"])?;
         let pg_sender = senders.get("postgres");
-        let _ = pg_sender.send(PostgresMsg::new(String::from("optimism_tx_batches"), Value::Object(converted_batch.clone())))?;
+        let _ = pg_sender.send(PostgresMsg::new(String::from("optimism_tx_batches"), Value::Object(batch.clone())))?;
         let txs = get_array(&response, "transactions")?;
-        let l1_tx_hash = opt_to_result(converted_batch.get("l1TransactionHash"))?;
+        let l1_tx_hash = opt_to_result(batch.get("l1TransactionHash"))?;
 
         for tx in txs.iter() {
             let tx_map = opt_to_result(tx.as_object())?;
-            let mut converted_tx = number_to_string_convert(tx_map, vec!["index", "batchIndex", "blockNumber", "timestamp", "queueIndex"])?;
-            converted_tx.insert(String::from("l1_tx_hash"), l1_tx_hash.clone());
-            let _ = pg_sender.send(PostgresMsg::new(String::from("optimism_txs"), Value::Object(converted_tx.to_owned())))?;
+            let mut tx_map = tx_map.clone();
+            tx_map.insert(String::from("l1_tx_hash"), l1_tx_hash.clone());
+            let _ = pg_sender.send(PostgresMsg::new(String::from("optimism_txs"), Value::Object(tx_map)))?;
         }
         Ok(())
     }
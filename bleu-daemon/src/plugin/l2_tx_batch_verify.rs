@@ -0,0 +1,94 @@
+use appbase::prelude::*;
+use clap::Arg;
+use primitive_types::U256;
+use r2d2_postgres::postgres::Row;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::error::ExpectedError;
+use crate::libs;
+use crate::libs::merkle::{bytes32_to_hex, Bytes32, get_merkle_root, hash_tx_chain_element, hex_to_bytes};
+use crate::libs::opt::opt_to_result;
+use crate::libs::verify::VerifyTask;
+use crate::message;
+use crate::plugin::alert::AlertPlugin;
+use crate::plugin::postgres::{Pool, PostgresPlugin};
+use crate::plugin::rocks::RocksPlugin;
+use crate::types::channel::MultiSender;
+use crate::types::subscribe::SubscribeEvent;
+
+#[appbase_plugin(RocksPlugin, PostgresPlugin, AlertPlugin)]
+pub struct L2TxBatchVerifyPlugin {
+    task: Option<VerifyTask>,
+}
+
+const TASK_NAME: &str = "l2_tx_batch_verify";
+const TASK_FILE: &str = "task/l2_tx_batch_verify.json";
+const BATCH_TYPE: &str = "tx";
+const SELECT_TX_BATCH: &str = "SELECT batch_index, batch_size, batch_root, l1_tx_hash FROM {optimism_tx_batches} WHERE batch_index = $1";
+const SELECT_TXS: &str = "SELECT index, queue_origin, queue_index::text AS queue_index, tx_timestamp::text AS tx_timestamp, block_number::text AS block_number, data FROM {optimism_txs} WHERE batch_index = $1 ORDER BY index";
+
+message!(L2TxBatchVerifyMsg; {method: String});
+
+impl Plugin for L2TxBatchVerifyPlugin {
+    fn new() -> Self {
+        APP.options.arg(Arg::new("l2txbatchverify::poll-interval").long("l2txbatchverify-poll-interval").takes_value(true));
+        L2TxBatchVerifyPlugin {
+            task: None,
+        }
+    }
+
+    fn init(&mut self) {
+        self.task = Some(VerifyTask::load(TASK_NAME, TASK_FILE));
+    }
+
+    fn startup(&mut self) {
+        let task = self.task.take().unwrap();
+        task.start(|| libs::config::get().l2_tx_batch_verify.poll_interval, Self::event_handler);
+    }
+
+    fn shutdown(&mut self) {}
+}
+
+impl L2TxBatchVerifyPlugin {
+    fn event_handler(sub_event: &SubscribeEvent, senders: &MultiSender, pool: &Pool) -> Result<(), ExpectedError> {
+        let mut client = pool.get().map_err(|err| ExpectedError::PostgresError(err.to_string()))?;
+        let batch_index = sub_event.curr_idx as i64;
+        let batch = match client.query_opt(PostgresPlugin::resolve_query(SELECT_TX_BATCH)?.as_str(), &[&batch_index])? {
+            Some(batch) => batch,
+            None => return Err(ExpectedError::BlockHeightError(format!("waiting for tx batch created...task={}, batch_index={}", TASK_NAME, batch_index))),
+        };
+        let batch_size = opt_to_result(batch.get::<_, Option<i64>>("batch_size"))? as usize;
+        let txs = client.query(PostgresPlugin::resolve_query(SELECT_TXS)?.as_str(), &[&batch_index])?;
+        if txs.len() < batch_size {
+            return Err(ExpectedError::BlockHeightError(format!("waiting for txs created...task={}, batch_index={}, size={}, created={}", TASK_NAME, batch_index, batch_size, txs.len())));
+        }
+        let leaves = txs.iter().map(|tx| Self::leaf_hash(tx)).collect::<Result<Vec<Bytes32>, ExpectedError>>()?;
+        let computed_root = bytes32_to_hex(&get_merkle_root(&leaves)?);
+        let reported_root = opt_to_result(batch.get::<_, Option<String>>("batch_root"))?;
+        if computed_root == reported_root.to_lowercase() {
+            return Ok(());
+        }
+        let l1_tx_hash: Option<String> = batch.get("l1_tx_hash");
+        libs::verify::batch_root_mismatch_handler(senders, TASK_NAME, BATCH_TYPE, batch_index.to_string(), l1_tx_hash, reported_root, computed_root)
+    }
+
+    fn leaf_hash(tx: &Row) -> Result<Bytes32, ExpectedError> {
+        let queue_origin: Option<String> = tx.get("queue_origin");
+        let leaf_hash = if queue_origin.unwrap_or_default() == "sequencer" {
+            let timestamp = Self::to_u256(tx.get("tx_timestamp"))?;
+            let block_number = Self::to_u256(tx.get("block_number"))?;
+            let data: Option<String> = tx.get("data");
+            let tx_data = hex_to_bytes(opt_to_result(data)?.as_str())?;
+            hash_tx_chain_element(true, U256::zero(), timestamp, block_number, &tx_data)
+        } else {
+            let queue_index = Self::to_u256(tx.get("queue_index"))?;
+            hash_tx_chain_element(false, queue_index, U256::zero(), U256::zero(), &[])
+        };
+        Ok(leaf_hash)
+    }
+
+    fn to_u256(decimal: Option<String>) -> Result<U256, ExpectedError> {
+        U256::from_dec_str(opt_to_result(decimal)?.as_str()).map_err(|err| ExpectedError::ParsingError(format!("{:?}", err)))
+    }
+}
@@ -8,20 +8,20 @@ use serde_json::{json, Value};
 
 use crate::{libs, validation};
 use crate::error::error::ExpectedError;
-use crate::libs::convert::hex_to_decimal_converter;
 use crate::libs::opt::opt_to_result;
 use crate::libs::request;
 use crate::libs::serde::{get_array, get_object, get_str};
 use crate::libs::subscribe::{load_retry_queue, load_task_from_json, remove_from_retry_queue, save_retry_queue};
 use crate::message;
+use crate::plugin::alert::AlertPlugin;
 use crate::plugin::jsonrpc::JsonRpcPlugin;
 use crate::plugin::postgres::{PostgresMsg, PostgresPlugin};
 use crate::plugin::rocks::RocksPlugin;
-use crate::plugin::slack::SlackPlugin;
 use crate::types::channel::MultiSender;
 use crate::types::subscribe::{RetryJob, SubscribeEvent};
+use crate::types::webhook::WebhookEventType;
 
-#[appbase_plugin(RocksPlugin, PostgresPlugin, SlackPlugin)]
+#[appbase_plugin(RocksPlugin, PostgresPlugin, AlertPlugin)]
 pub struct L2TxReceiptPlugin {
     sub_event: Option<SubscribeEvent>,
     senders: Option<MultiSender>,
@@ -34,9 +34,7 @@ const TASK_PREFIX: &str = "task:optimism";
 const TASK_NAME: &str = "l2_tx_receipt";
 const TASK_FILE: &str = "task/l2_tx_receipt.json";
 const RETRY_PREFIX: &str = "retry:optimism:l2_tx_receipt";
-const DEFAULT_RETRY_COUNT: u32 = 3;
 const RETRY_METHOD: &str = "retry_l2_tx_receipt";
-const DEFAULT_RETRY_ENDPOINT: &str = "http://0.0.0.0:9999";
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 struct L2TxReceiptRetryJob {
@@ -77,7 +75,7 @@ impl Plugin for L2TxReceiptPlugin {
     }
 
     fn init(&mut self) {
-        let senders = MultiSender::new(vec!("postgres", "slack", "l2_tx_receipt"));
+        let senders = MultiSender::new(vec!("postgres", "alert", "l2_tx_receipt"));
         self.senders = Some(senders.to_owned());
         self.receiver = Some(APP.channels.subscribe(TASK_NAME));
         self.sub_event = Some(load_task_from_json(TASK_FILE, CHAIN, TASK_PREFIX, TASK_NAME).expect(format!("failed to load task! task={}", TASK_NAME).as_str()));
@@ -103,13 +101,15 @@ impl L2TxReceiptPlugin {
     fn recv(mut receiver: Receiver, sub_event: SubscribeEvent, senders: MultiSender, mut retry_queue: HashMap<String, L2TxReceiptRetryJob>, app: QuitHandle) {
         APP.spawn(async move {
             if let Ok(message) = receiver.try_recv() {
-                if let Err(err) = Self::message_handler(message, &sub_event, &senders, &mut retry_queue).await {
-                    let _ = libs::error::error_handler(senders.get("slack"), err);
+                match Self::message_handler(message, &sub_event, &senders, &mut retry_queue).await {
+                    Ok(_) => libs::error::resolve_handler(senders.get("alert"), TASK_NAME),
+                    Err(err) => libs::error::error_handler(senders.get("alert"), TASK_NAME, err),
                 }
             }
             if let Err(err) = Self::retry_handler(&mut retry_queue, &sub_event, &senders).await {
-                let _ = libs::error::error_handler(senders.get("slack"), err);
+                let _ = libs::error::error_handler(senders.get("alert"), TASK_NAME, err);
             }
+            libs::metrics::TASK_RETRY_QUEUE_SIZE.with_label_values(&[TASK_NAME]).set(retry_queue.len() as i64);
             if !app.is_quitting() {
                 tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
                 Self::recv(receiver, sub_event, senders, retry_queue, app);
@@ -121,7 +121,7 @@ impl L2TxReceiptPlugin {
         let parsed_msg = message.as_object().unwrap();
         let tx_hash = get_str(parsed_msg, "tx_hash")?;
         if let Err(err) = Self::receipt_syncer(tx_hash, sub_event, senders).await {
-            let retry_count = libs::opt::get_value::<u32>("l2txreceipt::retry-count").unwrap_or(DEFAULT_RETRY_COUNT);
+            let retry_count = libs::config::get().l2_tx_receipt.retry_count;
             let retry_job = L2TxReceiptRetryJob::new(String::from(tx_hash), retry_count);
             retry_queue.insert(retry_job.get_retry_id(), retry_job.clone());
             let rocks_sender = senders.get("rocks");
@@ -144,14 +144,32 @@ impl L2TxReceiptPlugin {
             return Err(ExpectedError::NoneError(format!("receipt does not created...tx_hash={}", tx_hash)));
         }
         let receipt = get_object(&response, "result")?;
-        let converted_receipt = hex_to_decimal_converter(receipt, vec!["blockNumber", "cumulativeGasUsed", "gasUsed", "status", "transactionIndex"])?;
         let pg_sender = senders.get("postgres");
-        let _ = pg_sender.send(PostgresMsg::new(String::from("optimism_tx_receipts"), Value::Object(converted_receipt.to_owned())))?;
+        let _ = pg_sender.send(PostgresMsg::new(String::from("optimism_tx_receipts"), Value::Object(receipt.to_owned())))?;
         let logs = get_array(&receipt, "logs")?;
+        let mut sent_msg_hash: Option<Value> = None;
         for log in logs.iter() {
             let log_map = opt_to_result(log.as_object())?;
-            let converted_log = hex_to_decimal_converter(log_map, vec!["blockNumber", "transactionIndex", "logIndex"])?;
-            let _ = pg_sender.send(PostgresMsg::new(String::from("optimism_tx_receipt_logs"), Value::Object(converted_log.to_owned())))?;
+            let _ = pg_sender.send(PostgresMsg::new(String::from("optimism_tx_receipt_logs"), Value::Object(log_map.to_owned())))?;
+            if libs::cross_domain::is_sent_message(log_map)? {
+                let sent_message = libs::cross_domain::sent_message_converter(log_map)?;
+                sent_msg_hash = sent_message.get("msg_hash").cloned();
+                let _ = pg_sender.send(PostgresMsg::new(String::from("optimism_cross_domain_messages"), Value::Object(sent_message)))?;
+            }
+            if libs::bridge::is_l2_bridge_event(log_map)? {
+                let mut bridge_transfer = libs::bridge::bridge_transfer_converter(log_map)?;
+                // the bridge emits WithdrawalInitiated right after the messenger's SentMessage
+                if bridge_transfer.get("transfer_type") == Some(&Value::String(String::from("withdrawal"))) {
+                    if let Some(msg_hash) = sent_msg_hash.take() {
+                        bridge_transfer.insert(String::from("msg_hash"), msg_hash);
+                    }
+                }
+                let _ = pg_sender.send(PostgresMsg::new(String::from("optimism_bridge_transfers"), Value::Object(bridge_transfer)))?;
+            }
+            if libs::gas_oracle::is_gas_price_oracle_update(log_map)? {
+                let oracle_update = libs::gas_oracle::gas_price_oracle_converter(log_map)?;
+                let _ = pg_sender.send(PostgresMsg::new(String::from("optimism_gas_price_oracle_updates"), Value::Object(oracle_update)))?;
+            }
         }
         Ok(())
     }
@@ -180,14 +198,18 @@ impl L2TxReceiptPlugin {
                     let _ = remove_from_retry_queue(&rocks_sender, retry_id.clone())?;
                 }
             }
-            let retry_endpoint = libs::opt::get_value_str("l2txreceipt::retry-endpoint").unwrap_or(DEFAULT_RETRY_ENDPOINT.to_string());
+            let retry_endpoint = libs::config::get().l2_tx_receipt.retry_endpoint.clone();
             if !manual_retry.is_empty() {
+                libs::metrics::TASK_DEAD_LETTERS.with_label_values(&[TASK_NAME]).inc_by(manual_retry.len() as u64);
                 let params = manual_retry.iter()
                     .map(|s| {
                         format!("\"{}\"", s)
                     }).collect::<Vec<String>>().join(",");
                 let retry_query = libs::subscribe::retry_creator(format!("[{}]", params), RETRY_METHOD, retry_endpoint)?;
-                return Err(ExpectedError::RetryFailError(retry_query));
+                let err = ExpectedError::RetryFailError(retry_query.clone());
+                libs::error::escalation_handler(senders.get("email"), TASK_NAME, &err);
+                libs::webhook::event_handler(senders.get("webhook"), WebhookEventType::RetryExhausted, TASK_NAME, &err.to_string(), json!({"retry_query": retry_query, "count": manual_retry.len()}));
+                return Err(err);
             }
         }
         Ok(())
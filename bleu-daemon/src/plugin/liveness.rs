@@ -0,0 +1,104 @@
+use std::thread;
+use std::time::Duration;
+
+use appbase::prelude::*;
+use clap::Arg;
+
+use crate::error::error::ExpectedError;
+use crate::libs;
+use crate::plugin::postgres::{Pool, PostgresPlugin};
+use crate::plugin::slack::{SlackMsg, SlackMsgLevel, SlackPlugin};
+use crate::types::channel::MultiSender;
+use crate::types::enumeration::Enumeration;
+use crate::types::liveness::{LivenessCheck, LivenessEvent, LivenessState};
+
+#[appbase_plugin(PostgresPlugin, SlackPlugin)]
+pub struct LivenessPlugin {
+    senders: Option<MultiSender>,
+    pool: Option<Pool>,
+}
+
+const SELECT_LIVENESS: &str = "SELECT \
+    (SELECT block_timestamp::text FROM {optimism_blocks} ORDER BY {optimism_blocks.id} DESC LIMIT 1) AS block_timestamp, \
+    (SELECT index::text FROM {optimism_block_txs} ORDER BY {optimism_block_txs.id} DESC LIMIT 1) AS l2_head_index, \
+    (SELECT index::text FROM {optimism_txs} ORDER BY {optimism_txs.id} DESC LIMIT 1) AS batched_index, \
+    (SELECT batch_timestamp::text FROM {optimism_tx_batches} ORDER BY {optimism_tx_batches.id} DESC LIMIT 1) AS tx_batch_timestamp, \
+    (SELECT batch_timestamp::text FROM {optimism_state_batches} ORDER BY {optimism_state_batches.id} DESC LIMIT 1) AS state_batch_timestamp";
+
+impl Plugin for LivenessPlugin {
+    fn new() -> Self {
+        APP.options.arg(Arg::new("liveness::activate").long("liveness-activate").takes_value(true));
+        APP.options.arg(Arg::new("liveness::poll-interval").long("liveness-poll-interval").takes_value(true));
+        APP.options.arg(Arg::new("liveness::block-threshold").long("liveness-block-threshold").takes_value(true));
+        APP.options.arg(Arg::new("liveness::tx-batch-threshold").long("liveness-tx-batch-threshold").takes_value(true));
+        APP.options.arg(Arg::new("liveness::state-batch-threshold").long("liveness-state-batch-threshold").takes_value(true));
+        APP.options.arg(Arg::new("liveness::batch-lag-threshold").long("liveness-batch-lag-threshold").takes_value(true));
+        LivenessPlugin {
+            senders: None,
+            pool: None,
+        }
+    }
+
+    fn init(&mut self) {
+        self.senders = Some(MultiSender::new(vec!("slack")));
+        self.pool = Some(APP.run_with::<PostgresPlugin, _, _>(|postgres| postgres.get_pool()));
+    }
+
+    fn startup(&mut self) {
+        let senders = self.senders.take().unwrap();
+        let pool = self.pool.take().unwrap();
+        let app = APP.quit_handle().unwrap();
+
+        Self::recv(LivenessState::default(), senders, pool, app);
+    }
+
+    fn shutdown(&mut self) {}
+}
+
+impl LivenessPlugin {
+    fn recv(mut state: LivenessState, senders: MultiSender, pool: Pool, app: QuitHandle) {
+        APP.spawn_blocking(move || {
+            if libs::config::get().liveness.activate {
+                if let Err(err) = Self::event_handler(&mut state, &senders, &pool) {
+                    log::warn!("failed to check liveness! error={}", err.to_string());
+                }
+            }
+            if !app.is_quitting() {
+                let poll_interval = libs::config::get().liveness.poll_interval;
+                thread::sleep(Duration::from_millis(poll_interval));
+                Self::recv(state, senders, pool, app);
+            }
+        });
+    }
+
+    fn event_handler(state: &mut LivenessState, senders: &MultiSender, pool: &Pool) -> Result<(), ExpectedError> {
+        let mut client = pool.get().map_err(|err| ExpectedError::PostgresError(err.to_string()))?;
+        let row = client.query_one(PostgresPlugin::resolve_query(SELECT_LIVENESS)?.as_str(), &[])?;
+        let now = libs::time::current_timestamp() as i64;
+        let elapsed = |column: &str| to_i64(row.get(column)).map(|timestamp| now - timestamp);
+        let batch_lag = to_i64(row.get("l2_head_index")).map(|l2_head_index| l2_head_index - to_i64(row.get("batched_index")).unwrap_or(-1));
+
+        let config = libs::config::get().liveness.clone();
+        let checks = vec![
+            LivenessCheck::new("seconds since last l2 block", elapsed("block_timestamp"), config.block_threshold),
+            LivenessCheck::new("seconds since last tx batch", elapsed("tx_batch_timestamp"), config.tx_batch_threshold),
+            LivenessCheck::new("seconds since last state batch", elapsed("state_batch_timestamp"), config.state_batch_threshold),
+            LivenessCheck::new("l2 head lag behind batched index", batch_lag, config.batch_lag_threshold),
+        ];
+        let slack_sender = senders.get("slack");
+        for check in checks.iter() {
+            let (msg_level, msg) = match state.update(check) {
+                Some(LivenessEvent::Alert) => (SlackMsgLevel::Error, format!("liveness alert! check={}, value={:?}, threshold={}", check.name, check.value, check.threshold)),
+                Some(LivenessEvent::Recovery) => (SlackMsgLevel::Info, format!("liveness recovered! check={}, value={:?}, threshold={}", check.name, check.value, check.threshold)),
+                None => continue,
+            };
+            log::warn!("{}", msg);
+            let _ = slack_sender.send(SlackMsg::new(msg_level.value(), msg))?;
+        }
+        Ok(())
+    }
+}
+
+fn to_i64(value: Option<String>) -> Option<i64> {
+    value.and_then(|value| value.parse::<i64>().ok())
+}
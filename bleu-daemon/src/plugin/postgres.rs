@@ -1,34 +1,40 @@
-use std::{fs, thread};
+use std::thread;
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use appbase::prelude::*;
+use lazy_static::lazy_static;
 use r2d2_postgres::{PostgresConnectionManager, r2d2};
-use r2d2_postgres::postgres::NoTls;
+use r2d2_postgres::postgres::{Config, NoTls};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
 use crate::{libs, message};
 use crate::error::error::ExpectedError;
-use crate::libs::opt::opt_to_result;
-use crate::libs::postgres::{create_table, insert_value};
+use crate::libs::postgres::{apply_migrations, find_schema_files, insert_value, load_schema_files, plan_migrations, resolve_schema};
 use crate::libs::serde::{get_object, get_str};
-use crate::plugin::slack::{SlackMsg, SlackMsgLevel};
-use crate::plugin::slack::SlackPlugin;
+use crate::plugin::alert::AlertPlugin;
+use crate::plugin::jsonrpc::JsonRpcPlugin;
 use crate::types::channel::MultiSender;
-use crate::types::enumeration::Enumeration;
 use crate::types::postgres::PostgresSchema;
 
-#[appbase_plugin(SlackPlugin)]
+#[appbase_plugin(JsonRpcPlugin, AlertPlugin)]
 pub struct PostgresPlugin {
     monitor: Option<Receiver>,
     senders: Option<MultiSender>,
     pool: Option<Pool>,
-    schema_map: Option<HashMap<String, PostgresSchema>>,
+    schema_map: Option<Arc<HashMap<String, PostgresSchema>>>,
 }
 
 pub type Pool = r2d2::Pool<PostgresConnectionManager<NoTls>>;
 
+lazy_static! {
+    static ref SCHEMA_MAP: RwLock<Arc<HashMap<String, PostgresSchema>>> = RwLock::new(Arc::new(HashMap::new()));
+}
+
+const HEALTH_CHECK_TIMEOUT: u64 = 3;
+
 message!(PostgresMsg; {schema: String}, {value: Value});
 
 impl Plugin for PostgresPlugin {
@@ -38,6 +44,10 @@ impl Plugin for PostgresPlugin {
         APP.options.arg(clap::Arg::new("postgres::dbname").long("postgres-dbname").takes_value(true));
         APP.options.arg(clap::Arg::new("postgres::user").long("postgres-user").takes_value(true));
         APP.options.arg(clap::Arg::new("postgres::password").long("postgres-password").takes_value(true));
+        APP.options.arg(clap::Arg::new("postgres::allow-destructive").long("postgres-allow-destructive").takes_value(true));
+        APP.options.arg(clap::Arg::new("postgres::migration-dry-run").long("postgres-migration-dry-run").takes_value(true));
+        APP.options.arg(clap::Arg::new("postgres::schema-dir").long("postgres-schema-dir").takes_value(true));
+        APP.options.arg(clap::Arg::new("postgres::schema-files").long("postgres-schema-files").takes_value(true));
 
         PostgresPlugin {
             monitor: None,
@@ -48,22 +58,27 @@ impl Plugin for PostgresPlugin {
     }
 
     fn init(&mut self) {
-        let schema_map = Self::load_schema().expect("failed to load schema!");
+        let schema_map = Arc::new(Self::load_schema().expect("failed to load schema!"));
+        *SCHEMA_MAP.write().unwrap() = schema_map.clone();
         let pool = Self::create_pool().expect("failed to create pool!");
-        create_table(pool.clone(), &schema_map).expect("failed to create tables!");
-        let senders = MultiSender::new(vec!("slack"));
+        Self::migrate(pool.clone(), &schema_map).expect("failed to migrate schema!");
+        let senders = MultiSender::new(vec!("alert"));
         self.senders = Some(senders.to_owned());
         self.monitor = Some(APP.channels.subscribe("postgres"));
         self.pool = Some(pool);
         self.schema_map = Some(schema_map);
+        self.health_check_register();
     }
 
     fn startup(&mut self) {
+        let app = APP.quit_handle().unwrap();
+        if app.is_quitting() {
+            return;
+        }
         let pool = self.pool.as_ref().unwrap().clone();
         let schema_map = self.schema_map.as_ref().unwrap().clone();
         let monitor = self.monitor.take().unwrap();
         let senders = self.senders.take().unwrap();
-        let app = APP.quit_handle().unwrap();
 
         Self::recv(pool, schema_map, senders, monitor, app);
     }
@@ -72,17 +87,45 @@ impl Plugin for PostgresPlugin {
 }
 
 impl PostgresPlugin {
-    fn recv(pool: Pool, schema_map: HashMap<String, PostgresSchema>, senders: MultiSender, mut monitor: Receiver, app: QuitHandle) {
+    pub fn get_pool(&self) -> Pool {
+        self.pool.as_ref().unwrap().clone()
+    }
+
+    /*
+     * Raw queries refer to tables as `{optimism_blocks}`, so that they follow the namespace and prefix of the loaded schema files.
+     */
+    pub fn resolve_query(query: &str) -> Result<String, ExpectedError> {
+        libs::postgres::resolve_query(&SCHEMA_MAP.read().unwrap(), query)
+    }
+
+    fn health_check_register(&self) {
+        let pool = self.get_pool();
+        APP.run_with::<JsonRpcPlugin, _, _>(|jsonrpc| {
+            jsonrpc.add_health_check(String::from("postgres"), move || {
+                let mut client = pool.get_timeout(Duration::from_secs(HEALTH_CHECK_TIMEOUT)).map_err(|err| ExpectedError::PostgresError(err.to_string()))?;
+                let _ = client.simple_query("SELECT 1")?;
+                Ok(())
+            });
+        });
+    }
+
+    fn recv(pool: Pool, schema_map: Arc<HashMap<String, PostgresSchema>>, senders: MultiSender, mut monitor: Receiver, app: QuitHandle) {
         APP.spawn_blocking(move || {
             if let Ok(msg) = monitor.try_recv() {
                 let parsed_msg = msg.as_object().unwrap();
                 let schema_name = get_str(parsed_msg, "schema").unwrap();
-                let selected_schema = schema_map.get(schema_name).unwrap();
                 let values = get_object(parsed_msg, "value").unwrap();
-                if let Err(error) = insert_value(pool.clone(), selected_schema, values) {
-                    let _ = senders.get("slack").send(SlackMsg::new(SlackMsgLevel::Warn.value(), error.to_string()));
+                let timer = libs::metrics::POSTGRES_INSERT_SECONDS.with_label_values(&[schema_name]).start_timer();
+                match resolve_schema(&schema_map, schema_name).and_then(|selected_schema| insert_value(pool.clone(), selected_schema, values)) {
+                    Ok(_) => {
+                        libs::metrics::POSTGRES_ROWS.with_label_values(&[schema_name]).inc();
+                        libs::error::resolve_handler(senders.get("alert"), "postgres");
+                    }
+                    Err(error) => libs::error::warn_handler(senders.get("alert"), "postgres", error),
                 }
+                timer.observe_duration();
             }
+            libs::metrics::CHANNEL_DEPTH.with_label_values(&["postgres"]).set(monitor.len() as i64);
             if !app.is_quitting() {
                 thread::sleep(Duration::from_millis(10));
                 Self::recv(pool, schema_map, senders, monitor, app);
@@ -90,30 +133,46 @@ impl PostgresPlugin {
         });
     }
 
+    /*
+     * `postgres::schema-files` is loaded when it is set, otherwise every schema file in `postgres::schema-dir`.
+     */
     fn load_schema() -> Result<HashMap<String, PostgresSchema>, ExpectedError> {
-        let schema_files = vec![String::from("schema/optimism.json"), String::from("schema/ethereum.json")];
-        let mut schema_map = HashMap::new();
-        for schema_file in schema_files.iter() {
-            let json_str = fs::read_to_string(schema_file)?;
-            let json_schema: Value = serde_json::from_str(json_str.as_str())?;
-            let raw_schema_map = opt_to_result(json_schema.as_object())?;
-            for (schema_name, values) in raw_schema_map {
-                schema_map.insert(schema_name.clone(), PostgresSchema::from(schema_name.clone(), values)?);
+        let postgres = libs::config::get().postgres.clone();
+        let schema_files = match postgres.schema_files.is_empty() {
+            true => find_schema_files(&postgres.schema_dir)?,
+            false => postgres.schema_files,
+        };
+        let schema_map = load_schema_files(&schema_files)?;
+        log::info!("schema loaded! files={:?}, tables={}", schema_files, schema_map.len());
+        Ok(schema_map)
+    }
+
+    /*
+     * A dry run logs the planned DDL without applying it, and destructive differences stop the daemon unless they are allowed.
+     */
+    fn migrate(pool: Pool, schema_map: &HashMap<String, PostgresSchema>) -> Result<(), ExpectedError> {
+        let postgres = libs::config::get().postgres.clone();
+        let plans = plan_migrations(pool.clone(), schema_map)?;
+        if postgres.migration_dry_run {
+            for plan in plans.iter() {
+                log::info!("migration planned! schema={}\n{}", plan.schema_name, plan.to_sql());
             }
+            log::info!("migration dry run finished! planned_schemas={}", plans.len());
+            return Ok(());
         }
-        Ok(schema_map)
+        let destructive: Vec<String> = plans.iter().filter(|plan| plan.is_destructive()).flat_map(|plan| plan.destructive.clone()).collect();
+        if !destructive.is_empty() && !postgres.allow_destructive {
+            return Err(ExpectedError::InvalidError(format!("destructive schema migration is not allowed! set postgres::allow-destructive to apply it, statements={:?}", destructive)));
+        }
+        apply_migrations(pool, &plans)
     }
 
     fn create_pool() -> Result<Pool, ExpectedError> {
-        let host = libs::opt::get_value_str("postgres::host")?;
-        let port = libs::opt::get_value_str("postgres::port")?;
-        let dbname = libs::opt::get_value_str("postgres::dbname")?;
-        let user = libs::opt::get_value_str("postgres::user")?;
-        let password = libs::opt::get_value_str("postgres::password")?;
-
-        let config = format!("host={host} port={port} dbname={dbname} user={user} password={password}", host = host, port = port, dbname = dbname, user = user, password = password);
+        let postgres = libs::config::get().postgres.clone();
+        let mut config = Config::new();
+        config.host(&postgres.host).port(postgres.port).dbname(&postgres.dbname).user(&postgres.user).password(&postgres.password);
 
-        let manager = PostgresConnectionManager::new(config.parse().unwrap(), NoTls);
+        let manager = PostgresConnectionManager::new(config, NoTls);
         let pool: Pool = r2d2::Pool::builder().build(manager).expect("failed to create pool.");
         Ok(pool)
     }
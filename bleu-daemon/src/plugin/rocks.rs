@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Map, Value};
 
 use crate::{enumeration, message};
+use crate::libs::metrics::CHANNEL_DEPTH;
 use crate::libs::serde::get_str;
 use crate::plugin::jsonrpc::JsonRpcPlugin;
 use crate::types::enumeration::Enumeration;
@@ -20,6 +21,8 @@ pub struct RocksPlugin {
 
 pub type RocksDB = Arc<DBWithThreadMode<SingleThreaded>>;
 
+const HEALTH_CHECK_KEY: &str = "health";
+
 message!((RocksMsg; {key: String}, {value: Value}); (RocksMethod; {Put: "put"}, {Delete: "delete"}));
 
 impl Plugin for RocksPlugin {
@@ -49,6 +52,14 @@ impl Plugin for RocksPlugin {
                 Box::new(futures::future::ok(value))
             });
         });
+
+        let db = self.db.as_ref().unwrap().clone();
+        APP.run_with::<JsonRpcPlugin, _, _>(|jsonrpc| {
+            jsonrpc.add_health_check(String::from("rocksdb"), move || {
+                let _ = db.get(HEALTH_CHECK_KEY.as_bytes())?;
+                Ok(())
+            });
+        });
     }
 
     fn startup(&mut self) {
@@ -93,6 +104,7 @@ impl RocksPlugin {
                     }
                 }
             }
+            CHANNEL_DEPTH.with_label_values(&["rocks"]).set(monitor.len() as i64);
             if !app.is_quitting() {
                 tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
                 Self::recv(db, monitor, app);
@@ -5,7 +5,6 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
 use crate::{enumeration, libs};
-use crate::libs::opt;
 use crate::libs::serde::get_str;
 use crate::message;
 use crate::types::enumeration::Enumeration;
@@ -19,6 +18,7 @@ pub struct SlackPlugin {
 pub type SlackHooks = HashMap<String, String>;
 
 message!(SlackMsg; {msg_level: String}, {msg: String});
+message!(SlackHookMsg; {hook: String}, {msg: String});
 enumeration!(SlackMsgLevel; {Info: "info"}, {Warn: "warn"}, {Error: "error"});
 
 impl Plugin for SlackPlugin {
@@ -36,9 +36,10 @@ impl Plugin for SlackPlugin {
 
     fn init(&mut self) {
         let mut slack_hooks: SlackHooks = HashMap::new();
-        slack_hooks.insert(String::from("info"), opt::get_value_str("slack::info").unwrap());
-        slack_hooks.insert(String::from("warn"), opt::get_value_str("slack::warn").unwrap());
-        slack_hooks.insert(String::from("error"), opt::get_value_str("slack::error").unwrap());
+        let config = libs::config::get();
+        slack_hooks.insert(String::from("info"), config.slack.info.clone());
+        slack_hooks.insert(String::from("warn"), config.slack.warn.clone());
+        slack_hooks.insert(String::from("error"), config.slack.error.clone());
 
         self.slack_hooks = Some(slack_hooks);
         self.monitor = Some(APP.channels.subscribe("slack"));
@@ -58,11 +59,15 @@ impl SlackPlugin {
     fn recv(slack_hooks: SlackHooks, mut monitor: Receiver, app: QuitHandle) {
         APP.spawn(async move {
             if let Ok(msg) = monitor.try_recv() {
-                if libs::opt::get_value::<bool>("slack::activate").unwrap_or(false) {
+                if libs::config::get().slack.activate {
                     let parsed_msg = msg.as_object().unwrap();
-                    let msg_level = SlackMsgLevel::find(get_str(parsed_msg, "msg_level").unwrap()).unwrap();
-                    let msg_level_value = msg_level.value();
-                    let slack_hook = slack_hooks.get(&msg_level_value).unwrap();
+                    let slack_hook = match get_str(parsed_msg, "hook") {
+                        Ok(hook) => hook,
+                        Err(_) => {
+                            let msg_level = SlackMsgLevel::find(get_str(parsed_msg, "msg_level").unwrap()).unwrap();
+                            slack_hooks.get(&msg_level.value()).unwrap().as_str()
+                        }
+                    };
                     let slack_msg = get_str(parsed_msg, "msg").unwrap();
 
                     let mut text = HashMap::new();
@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::SystemTime;
+
 use appbase::prelude::*;
 use jsonrpc_core::Params;
 use jsonrpc_core::serde_json::Map;
@@ -6,19 +10,30 @@ use serde_json::{json, Value};
 
 use crate::enumeration;
 use crate::error::error::ExpectedError;
+use crate::libs;
+use crate::libs::convert::hex_to_decimal;
 use crate::libs::opt::opt_to_result;
+use crate::libs::request;
+use crate::libs::request::adjust_url;
 use crate::libs::rocks::get_by_prefix_static;
-use crate::libs::serde::get_str;
+use crate::libs::serde::{get_str, get_string};
 use crate::message;
 use crate::plugin::jsonrpc::JsonRpcPlugin;
+use crate::plugin::l1_bridge_transfer::L1BridgeTransferMsg;
+use crate::plugin::l1_cross_domain_message::L1CrossDomainMessageMsg;
 use crate::plugin::l2_block_tx::L2BlockTxMsg;
+use crate::plugin::l2_chain_stat::L2ChainStatMsg;
 use crate::plugin::l2_enqueue::L2EnqueueMsg;
 use crate::plugin::l2_state_batch::L2StateBatchMsg;
+use crate::plugin::l2_state_batch_verify::L2StateBatchVerifyMsg;
+use crate::plugin::l2_state_root_verify::L2StateRootVerifyMsg;
 use crate::plugin::l2_tx_batch::L2TxBatchMsg;
-use crate::plugin::rocks::RocksPlugin;
+use crate::plugin::l2_tx_batch_verify::L2TxBatchVerifyMsg;
+use crate::plugin::rocks::{RocksDB, RocksPlugin};
 use crate::types::channel::MultiSender;
 use crate::types::enumeration::Enumeration;
-use crate::types::subscribe::TaskMethod;
+use crate::types::config::Config;
+use crate::types::subscribe::{SubscribeEvent, SubscribeStatus, SubscribeTask, TaskMethod, TaskStatus};
 use crate::validation::task;
 
 #[appbase_plugin(JsonRpcPlugin, RocksPlugin)]
@@ -27,13 +42,18 @@ pub struct TaskPlugin {
     senders: Option<MultiSender>,
 }
 
-const TASK_PREFIX: &str = "task:optimism";
+const TASK_PREFIXES: [&str; 2] = ["task:optimism", "task:ethereum"];
 
-enumeration!(TaskType; {L2BlockTx: "l2_block_tx"}, {L2TxBatch: "l2_tx_batch"}, {L2StateBatch: "l2_state_batch"}, {L2Enqueue: "l2_enqueue"});
+enumeration!(TaskType; {L2BlockTx: "l2_block_tx"}, {L2TxBatch: "l2_tx_batch"}, {L2StateBatch: "l2_state_batch"}, {L2Enqueue: "l2_enqueue"}, {L2StateRootVerify: "l2_state_root_verify"}, {L2TxBatchVerify: "l2_tx_batch_verify"}, {L2StateBatchVerify: "l2_state_batch_verify"}, {L1CrossDomainMessage: "l1_cross_domain_message"}, {L1BridgeTransfer: "l1_bridge_transfer"}, {L2ChainStat: "l2_chain_stat"});
 message!(TaskMsg; {method: String}, {task: String});
 
 impl Plugin for TaskPlugin {
     fn new() -> Self {
+        APP.options.arg(clap::Arg::new("task::head-poll-interval").long("task-head-poll-interval").takes_value(true));
+        APP.options.arg(clap::Arg::new("task::plugins").long("task-plugins").takes_value(true));
+        APP.options.arg(clap::Arg::new("reload::watch").long("reload-watch").takes_value(true));
+        APP.options.arg(clap::Arg::new("reload::poll-interval").long("reload-poll-interval").takes_value(true));
+        APP.options.arg(clap::Arg::new("reload::config-file").long("reload-config-file").takes_value(true));
         TaskPlugin {
             receiver: None,
             senders: None,
@@ -41,19 +61,23 @@ impl Plugin for TaskPlugin {
     }
 
     fn init(&mut self) {
-        let senders = MultiSender::new(vec!["task", "l2_block_tx", "l2_tx_batch", "l2_state_batch", "l2_enqueue"]);
+        let senders = MultiSender::new(vec!["task", "l2_block_tx", "l2_tx_batch", "l2_state_batch", "l2_enqueue", "l2_state_root_verify", "l2_tx_batch_verify", "l2_state_batch_verify", "l1_cross_domain_message", "l1_bridge_transfer", "l2_chain_stat"]);
         self.senders = Some(senders.to_owned());
         self.receiver = Some(APP.channels.subscribe("task"));
 
         self.jsonrpc_register();
+        self.health_check_register();
     }
 
     fn startup(&mut self) {
         let receiver = self.receiver.take().unwrap();
         let senders = self.senders.take().unwrap();
+        let rocks_db = APP.run_with::<RocksPlugin, _, _>(|rocks| rocks.get_db());
         let app = APP.quit_handle().unwrap();
 
+        Self::file_watcher(HashMap::new(), rocks_db.clone(), senders.get("task"), APP.quit_handle().unwrap());
         Self::recv(receiver, senders, app);
+        Self::head_syncer(rocks_db, APP.quit_handle().unwrap());
     }
 
     fn shutdown(&mut self) {}
@@ -72,6 +96,94 @@ impl TaskPlugin {
         });
     }
 
+    /*
+     * Exports the head index of every task as a metric, so the lag can be graphed without calling `get_task_status`.
+     * The current index is exported by the task itself on `next_idx`, since the copy in RocksDB lags behind it.
+     */
+    fn head_syncer(rocks_db: RocksDB, app: QuitHandle) {
+        APP.spawn(async move {
+            let tasks = Self::get_task_values(&rocks_db).into_iter()
+                .filter_map(|task| serde_json::from_value::<SubscribeTask>(task).ok())
+                .collect::<Vec<SubscribeTask>>();
+            for task in tasks.iter() {
+                match Self::head_fetcher(task).await {
+                    Ok(Some(head_idx)) => libs::metrics::TASK_HEAD_INDEX.with_label_values(&[&task.task]).set(head_idx as i64),
+                    Ok(None) => {}
+                    Err(err) => log::warn!("failed to fetch head! task={}, error={}", task.task, err.to_string()),
+                }
+            }
+            if !app.is_quitting() {
+                let poll_interval = libs::config::get().task.head_poll_interval;
+                tokio::time::sleep(tokio::time::Duration::from_millis(poll_interval)).await;
+                Self::head_syncer(rocks_db, app);
+            }
+        });
+    }
+
+    /*
+     * Reloads when the modified time of the config file or the file of an enabled task changes.
+     */
+    fn file_watcher(mut modified: HashMap<String, SystemTime>, rocks_db: RocksDB, task_sender: Sender, app: QuitHandle) {
+        APP.spawn(async move {
+            let config = libs::config::get();
+            if config.reload.watch {
+                let current = Self::watched_files(&config).into_iter()
+                    .filter_map(|file| fs::metadata(&file).and_then(|metadata| metadata.modified()).ok().map(|time| (file, time)))
+                    .collect::<HashMap<String, SystemTime>>();
+                if !modified.is_empty() && current != modified {
+                    match Self::reload_handler(&rocks_db, &task_sender) {
+                        Ok(report) => log::info!("reloaded! report={}", report),
+                        Err(err) => log::error!("failed to reload! error={}", err.to_string()),
+                    }
+                }
+                modified = current;
+            }
+            if !app.is_quitting() {
+                tokio::time::sleep(tokio::time::Duration::from_millis(config.reload.poll_interval)).await;
+                Self::file_watcher(modified, rocks_db, task_sender, app);
+            }
+        });
+    }
+
+    fn watched_files(config: &Config) -> Vec<String> {
+        let mut files = vec![config.reload.config_file.clone()];
+        files.extend(config.task.plugins.iter().filter(|plugin| TaskType::valid(plugin)).map(|plugin| libs::subscribe::task_file(plugin)));
+        files
+    }
+
+    /*
+     * The config file is applied first, and the reload is rejected as a whole when it is invalid.
+     * Task files are diffed against the stored tasks, and the changed tasks are reloaded by their plugins keeping `curr_idx`.
+     * A task which has never run is loaded from its task file on startup, so it has nothing to reload.
+     */
+    fn reload_handler(rocks_db: &RocksDB, task_sender: &Sender) -> Result<Value, ExpectedError> {
+        let config_file = libs::config::get().reload.config_file.clone();
+        let (applied, restart_required) = libs::config::reload(&config_file)?;
+        let mut tasks = Map::new();
+        let mut errors = Map::new();
+        let sub_events = Self::get_task_values(rocks_db).into_iter()
+            .filter_map(|task| task.as_object().and_then(|task| SubscribeEvent::from(task).ok()))
+            .filter(|sub_event| TaskType::valid(&sub_event.task) && libs::config::get().task.is_enabled(&sub_event.task))
+            .collect::<Vec<SubscribeEvent>>();
+        for sub_event in sub_events.iter() {
+            match libs::subscribe::reload_diff(sub_event) {
+                Ok(changed) if changed.is_empty() => {}
+                Ok(changed) => {
+                    let _ = task_sender.send(TaskMsg::new(TaskMethod::Reload.value(), sub_event.task.clone()))?;
+                    tasks.insert(sub_event.task.clone(), json!(changed));
+                }
+                Err(err) => {
+                    errors.insert(sub_event.task.clone(), Value::String(err.to_string()));
+                }
+            }
+        }
+        Ok(json!({
+            "config": {"applied": applied, "restart_required": restart_required},
+            "tasks": tasks,
+            "errors": errors,
+        }))
+    }
+
     fn jsonrpc_register(&self) {
         let senders = self.senders.as_ref().unwrap();
         let task_sender = senders.get("task");
@@ -108,20 +220,114 @@ impl TaskPlugin {
             });
         });
 
+        let task_sender = senders.get("task");
+        let rocks_db = APP.run_with::<RocksPlugin, _, _>(|rocks| rocks.get_db());
+        APP.run_with::<JsonRpcPlugin, _, _>(|jsonrpc| {
+            jsonrpc.add_method(String::from("reload"), move |_| {
+                let response = match Self::reload_handler(&rocks_db, &task_sender) {
+                    Ok(response) => response,
+                    Err(err) => json!({"error": err.to_string()}),
+                };
+                Box::new(futures::future::ok(response))
+            });
+        });
+
         let rocks_db = APP.run_with::<RocksPlugin, _, _>(|rocks| rocks.get_db());
         APP.run_with::<JsonRpcPlugin, _, _>(|jsonrpc| {
             jsonrpc.add_method(String::from("get_tasks"), move |_| {
-                let tasks = get_by_prefix_static(&rocks_db, TASK_PREFIX);
-                Box::new(futures::future::ok(tasks))
+                Box::new(futures::future::ok(Value::Array(Self::get_task_values(&rocks_db))))
+            });
+        });
+
+        let rocks_db = APP.run_with::<RocksPlugin, _, _>(|rocks| rocks.get_db());
+        APP.run_with::<JsonRpcPlugin, _, _>(|jsonrpc| {
+            jsonrpc.add_method(String::from("get_task_status"), move |_| {
+                let tasks = Self::get_task_values(&rocks_db).into_iter()
+                    .filter_map(|task| serde_json::from_value::<SubscribeTask>(task).ok())
+                    .collect::<Vec<SubscribeTask>>();
+                async move {
+                    let mut task_statuses = Vec::new();
+                    for task in tasks.iter() {
+                        let head_idx = match Self::head_fetcher(task).await {
+                            Ok(head_idx) => head_idx,
+                            Err(err) => {
+                                log::warn!("failed to fetch head! task={}, error={}", task.task, err.to_string());
+                                None
+                            }
+                        };
+                        task_statuses.push(json!(TaskStatus::new(task, head_idx)));
+                    }
+                    Ok::<Value, jsonrpc_core::Error>(Value::Array(task_statuses))
+                }
+            });
+        });
+    }
+
+    fn health_check_register(&self) {
+        let rocks_db = APP.run_with::<RocksPlugin, _, _>(|rocks| rocks.get_db());
+        APP.run_with::<JsonRpcPlugin, _, _>(|jsonrpc| {
+            jsonrpc.add_health_check(String::from("tasks"), move || {
+                let error_tasks = Self::get_task_values(&rocks_db).into_iter()
+                    .filter_map(|task| serde_json::from_value::<SubscribeTask>(task).ok())
+                    .filter(|task| task.status == SubscribeStatus::Error.value() && libs::config::get().task.is_enabled(&task.task))
+                    .map(|task| task.task)
+                    .collect::<Vec<String>>();
+                if !error_tasks.is_empty() {
+                    return Err(ExpectedError::ProcessError(format!("tasks stuck in error status! tasks={}", error_tasks.join(","))));
+                }
+                Ok(())
             });
         });
     }
 
+    fn get_task_values(rocks_db: &RocksDB) -> Vec<Value> {
+        TASK_PREFIXES.iter()
+            .flat_map(|task_prefix| get_by_prefix_static(rocks_db, task_prefix).as_array().cloned().unwrap_or_default())
+            .collect::<Vec<Value>>()
+    }
+
+    /*
+     * RPC tasks follow the block number of their node, and DTL tasks follow the latest index of the same DTL resource.
+     * Tasks reading PostgreSQL only have no upstream head.
+     */
+    async fn head_fetcher(task: &SubscribeTask) -> Result<Option<u64>, ExpectedError> {
+        let end_point = match task.active_node() {
+            Some(end_point) => end_point,
+            None => return Ok(None),
+        };
+        match opt_to_result(TaskType::find(task.task.as_str()))? {
+            TaskType::L2BlockTx | TaskType::L1CrossDomainMessage | TaskType::L1BridgeTransfer => {
+                let req_body = json!({
+                    "jsonrpc": "2.0",
+                    "method": "eth_blockNumber",
+                    "params": [],
+                    "id": 1
+                });
+                let response = request::post_async(end_point.as_str(), req_body.to_string().as_str()).await?;
+                let head_idx = hex_to_decimal(get_string(&response, "result")?)?;
+                Ok(Some(head_idx.parse::<u64>()?))
+            }
+            TaskType::L2TxBatch | TaskType::L2StateBatch | TaskType::L2Enqueue => {
+                let latest_url = format!("{}latest", adjust_url(end_point).trim_end_matches("index/"));
+                let response = request::get_async(latest_url.as_str()).await?;
+                let latest = match response.get("batch") {
+                    Some(batch) => batch.as_object(),
+                    None => Some(&response),
+                };
+                Ok(latest.and_then(|latest| latest.get("index")).and_then(|index| index.as_u64()))
+            }
+            _ => Ok(None),
+        }
+    }
+
     fn task_request_handler(method: TaskMethod, params: Params, sender: &Sender) -> Result<Value, ExpectedError> {
         let params: Map<String, Value> = params.parse().unwrap();
         let _ = task::verify(&params)?;
         let task_name = get_str(&params, "task")?;
         let task = opt_to_result(TaskType::find(task_name))?;
+        if !libs::config::get().task.is_enabled(task_name) {
+            return Err(ExpectedError::InvalidError(format!("task plugin is not enabled! enable it in task::plugins, task={}", task_name)));
+        }
         let _ = sender.send(TaskMsg::new(method.value(), task.value()))?;
 
         Ok(Value::String(format!("request registered! task={}", task_name)))
@@ -136,6 +342,12 @@ impl TaskPlugin {
             TaskType::L2TxBatch => L2TxBatchMsg::new(method.value()),
             TaskType::L2StateBatch => L2StateBatchMsg::new(method.value()),
             TaskType::L2Enqueue => L2EnqueueMsg::new(method.value()),
+            TaskType::L2StateRootVerify => L2StateRootVerifyMsg::new(method.value()),
+            TaskType::L2TxBatchVerify => L2TxBatchVerifyMsg::new(method.value()),
+            TaskType::L2StateBatchVerify => L2StateBatchVerifyMsg::new(method.value()),
+            TaskType::L1CrossDomainMessage => L1CrossDomainMessageMsg::new(method.value()),
+            TaskType::L1BridgeTransfer => L1BridgeTransferMsg::new(method.value()),
+            TaskType::L2ChainStat => L2ChainStatMsg::new(method.value()),
         };
         let sender = senders.get(&task.value());
         let _ = sender.send(message)?;
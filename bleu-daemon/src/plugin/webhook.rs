@@ -0,0 +1,101 @@
+use std::fs;
+
+use appbase::prelude::*;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::error::ExpectedError;
+use crate::libs;
+use crate::libs::serde::get_str;
+use crate::message;
+use crate::types::webhook::{WebhookDelivery, WebhookEndpoint, WebhookEvent};
+
+#[appbase_plugin]
+pub struct WebhookPlugin {
+    endpoints: Option<Vec<WebhookEndpoint>>,
+    monitor: Option<Receiver>,
+}
+
+message!(WebhookMsg; {event: String}, {task: String}, {msg: String}, {data: Value});
+
+impl Plugin for WebhookPlugin {
+    fn new() -> Self {
+        APP.options.arg(clap::Arg::new("webhook::activate").long("webhook-activate").takes_value(true));
+        APP.options.arg(clap::Arg::new("webhook::file").long("webhook-file").takes_value(true));
+        APP.options.arg(clap::Arg::new("webhook::retry-count").long("webhook-retry-count").takes_value(true));
+        APP.options.arg(clap::Arg::new("webhook::retry-backoff").long("webhook-retry-backoff").takes_value(true));
+
+        WebhookPlugin {
+            endpoints: None,
+            monitor: None,
+        }
+    }
+
+    fn init(&mut self) {
+        self.endpoints = Some(Self::load_endpoints().expect("failed to load webhook endpoints!"));
+        self.monitor = Some(APP.channels.subscribe("webhook"));
+    }
+
+    fn startup(&mut self) {
+        let endpoints = self.endpoints.take().unwrap();
+        let monitor = self.monitor.take().unwrap();
+        let app = APP.quit_handle().unwrap();
+        Self::recv(Vec::new(), endpoints, monitor, app);
+    }
+
+    fn shutdown(&mut self) {}
+}
+
+impl WebhookPlugin {
+    fn recv(mut deliveries: Vec<WebhookDelivery>, endpoints: Vec<WebhookEndpoint>, mut monitor: Receiver, app: QuitHandle) {
+        APP.spawn(async move {
+            let now = libs::time::current_timestamp();
+            if let Ok(msg) = monitor.try_recv() {
+                match Self::event_parser(&msg, now) {
+                    Ok(event) => {
+                        for (idx, endpoint) in endpoints.iter().enumerate().filter(|(_, endpoint)| endpoint.matches(&event)) {
+                            let body = endpoint.payload(&event).to_string();
+                            deliveries.push(WebhookDelivery::new(idx, event.event.clone(), body, now));
+                        }
+                    }
+                    Err(err) => log::error!("failed to parse webhook message! error={}", err.to_string()),
+                }
+            }
+            let (due, mut pending): (Vec<WebhookDelivery>, Vec<WebhookDelivery>) = deliveries.into_iter().partition(|delivery| delivery.is_due(now));
+            let results = join_all(due.iter().map(|delivery| libs::webhook::post(&endpoints[delivery.endpoint], &delivery.event, &delivery.body))).await;
+            for (mut delivery, result) in due.into_iter().zip(results) {
+                let endpoint = &endpoints[delivery.endpoint];
+                if let Err(err) = result {
+                    let config = libs::config::get();
+                    if delivery.retry(now, config.webhook.retry_count, config.webhook.retry_backoff) {
+                        log::warn!("webhook failed, retry at {}! attempt={}, error={}", delivery.next_attempt_at, delivery.attempt, err.to_string());
+                        pending.push(delivery);
+                    } else {
+                        log::error!("webhook dropped, no retry left! endpoint={}, event={}, error={}", endpoint.name, delivery.event, err.to_string());
+                    }
+                }
+            }
+            if !app.is_quitting() {
+                tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+                Self::recv(pending, endpoints, monitor, app);
+            }
+        });
+    }
+
+    fn event_parser(msg: &Value, now: u64) -> Result<WebhookEvent, ExpectedError> {
+        let parsed_msg = libs::opt::opt_to_result(msg.as_object())?;
+        Ok(WebhookEvent {
+            event: String::from(get_str(parsed_msg, "event")?),
+            task: String::from(get_str(parsed_msg, "task")?),
+            msg: String::from(get_str(parsed_msg, "msg")?),
+            data: parsed_msg.get("data").cloned().unwrap_or(Value::Null),
+            timestamp: now,
+        })
+    }
+
+    fn load_endpoints() -> Result<Vec<WebhookEndpoint>, ExpectedError> {
+        let json_str = fs::read_to_string(&libs::config::get().webhook.file)?;
+        WebhookEndpoint::load(json_str.as_str())
+    }
+}
@@ -3,3 +3,13 @@ pub mod channel;
 pub mod enumeration;
 pub mod message;
 pub mod postgres;
+pub mod liveness;
+pub mod health;
+pub mod alert;
+pub mod email;
+pub mod webhook;
+pub mod address_webhook;
+pub mod filter;
+pub mod config;
+pub mod migration;
+pub mod mapping;
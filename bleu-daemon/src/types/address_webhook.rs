@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::enumeration;
+use crate::types::enumeration::Enumeration;
+use crate::types::webhook::next_attempt_at;
+
+enumeration!(AddressDirection; {From: "from"}, {To: "to"}, {Any: "any"});
+enumeration!(AddressEvent; {Tx: "address_tx"}, {Log: "address_log"});
+enumeration!(DeliveryStatus; {Delivered: "delivered"}, {Retrying: "retrying"}, {Failed: "failed"});
+
+/*
+ * A subscription with `topic` matches the logs emitted by `address` with the topic as topic0,
+ * otherwise it matches the transactions sent from or to `address` by `direction`.
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressWebhook {
+    pub id: i64,
+    pub address: String,
+    pub direction: AddressDirection,
+    pub topic: Option<String>,
+    pub callback_url: String,
+    pub secret: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IndexedTx {
+    pub hash: String,
+    pub block_number: Option<String>,
+    pub from_address: Option<String>,
+    pub to_address: Option<String>,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IndexedLog {
+    pub tx_hash: String,
+    pub log_index: Option<String>,
+    pub block_number: Option<String>,
+    pub address: Option<String>,
+    pub topics: Vec<String>,
+    pub data: Option<String>,
+}
+
+impl AddressWebhook {
+    pub fn match_tx(&self, tx: &IndexedTx) -> bool {
+        if self.topic.is_some() {
+            return false;
+        }
+        let is_from = Self::is_same(&self.address, &tx.from_address);
+        let is_to = Self::is_same(&self.address, &tx.to_address);
+        match self.direction {
+            AddressDirection::From => is_from,
+            AddressDirection::To => is_to,
+            AddressDirection::Any => is_from || is_to,
+        }
+    }
+
+    pub fn match_log(&self, log: &IndexedLog) -> bool {
+        match &self.topic {
+            Some(topic) => Self::is_same(&self.address, &log.address) && matches!(log.topics.first(), Some(topic0) if topic0.eq_ignore_ascii_case(topic)),
+            None => false,
+        }
+    }
+
+    fn is_same(address: &str, target: &Option<String>) -> bool {
+        matches!(target, Some(target) if target.eq_ignore_ascii_case(address))
+    }
+}
+
+impl IndexedLog {
+    /*
+     * `topics` is stored as the json array string of the receipt log.
+     */
+    pub fn parse_topics(topics: Option<String>) -> Vec<String> {
+        topics.and_then(|topics| serde_json::from_str::<Vec<String>>(&topics).ok()).unwrap_or_default()
+    }
+}
+
+/*
+ * Kept in RocksDB until it is delivered or fails, so a restart does not drop the pending retries.
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressWebhookDelivery {
+    pub webhook: AddressWebhook,
+    pub event: AddressEvent,
+    pub tx_hash: String,
+    pub log_index: Option<String>,
+    pub body: String,
+    pub attempt: u32,
+    pub next_attempt_at: u64,
+}
+
+impl AddressWebhookDelivery {
+    pub fn tx(webhook: &AddressWebhook, tx: &IndexedTx, now: u64) -> Self {
+        let body = Self::payload(webhook, AddressEvent::Tx, json!(tx), now);
+        Self::new(webhook, AddressEvent::Tx, tx.hash.clone(), None, body, now)
+    }
+
+    pub fn log(webhook: &AddressWebhook, log: &IndexedLog, now: u64) -> Self {
+        let body = Self::payload(webhook, AddressEvent::Log, json!(log), now);
+        Self::new(webhook, AddressEvent::Log, log.tx_hash.clone(), log.log_index.clone(), body, now)
+    }
+
+    fn new(webhook: &AddressWebhook, event: AddressEvent, tx_hash: String, log_index: Option<String>, body: String, now: u64) -> Self {
+        AddressWebhookDelivery {
+            webhook: webhook.clone(),
+            event,
+            tx_hash,
+            log_index,
+            body,
+            attempt: 0,
+            next_attempt_at: now,
+        }
+    }
+
+    fn payload(webhook: &AddressWebhook, event: AddressEvent, data: Value, now: u64) -> String {
+        json!({
+            "webhook_id": webhook.id,
+            "event": event.value(),
+            "address": webhook.address,
+            "data": data,
+            "timestamp": now,
+        }).to_string()
+    }
+
+    pub fn delivery_key(&self, prefix: &str) -> String {
+        format!("{}{}:{}:{}", prefix, self.webhook.id, self.tx_hash, self.log_index.clone().unwrap_or_default())
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        self.next_attempt_at <= now
+    }
+
+    pub fn retry(&mut self, now: u64, retry_count: u32, backoff: u64) -> bool {
+        if self.attempt >= retry_count {
+            return false;
+        }
+        self.next_attempt_at = next_attempt_at(now, self.attempt, backoff);
+        self.attempt += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod address_webhook_test {
+    use serde_json::Value;
+
+    use crate::types::address_webhook::{AddressDirection, AddressWebhook, AddressWebhookDelivery, IndexedLog, IndexedTx};
+
+    const ADDRESS: &str = "0x4200000000000000000000000000000000000006";
+    const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+    fn example_webhook(direction: AddressDirection, topic: Option<&str>) -> AddressWebhook {
+        AddressWebhook {
+            id: 1,
+            address: String::from(ADDRESS),
+            direction,
+            topic: topic.map(String::from),
+            callback_url: String::from("http://localhost:8080"),
+            secret: String::from("secret"),
+        }
+    }
+
+    fn example_tx(from_address: &str, to_address: Option<&str>) -> IndexedTx {
+        IndexedTx {
+            hash: String::from("0x01"),
+            block_number: Some(String::from("10")),
+            from_address: Some(String::from(from_address)),
+            to_address: to_address.map(String::from),
+            value: Some(String::from("0")),
+        }
+    }
+
+    #[test]
+    fn match_tx_test() {
+        let sender = "0x0000000000000000000000000000000000000001";
+        let to_contract = example_tx(sender, Some("0x4200000000000000000000000000000000000006"));
+        let from_contract = example_tx(ADDRESS, Some(sender));
+        let create = example_tx(sender, None);
+
+        let to_webhook = example_webhook(AddressDirection::To, None);
+        assert!(to_webhook.match_tx(&to_contract));
+        assert!(!to_webhook.match_tx(&from_contract));
+        assert!(!to_webhook.match_tx(&create));
+
+        let from_webhook = example_webhook(AddressDirection::From, None);
+        assert!(!from_webhook.match_tx(&to_contract));
+        assert!(from_webhook.match_tx(&from_contract));
+
+        let any_webhook = example_webhook(AddressDirection::Any, None);
+        assert!(any_webhook.match_tx(&to_contract));
+        assert!(any_webhook.match_tx(&from_contract));
+
+        let topic_webhook = example_webhook(AddressDirection::Any, Some(TRANSFER_TOPIC));
+        assert!(!topic_webhook.match_tx(&to_contract));
+    }
+
+    #[test]
+    fn match_log_test() {
+        let topics = format!("[\"{}\", \"0x0000000000000000000000000000000000000000000000000000000000000001\"]", TRANSFER_TOPIC.to_uppercase().replace("0X", "0x"));
+        let log = IndexedLog {
+            tx_hash: String::from("0x01"),
+            log_index: Some(String::from("0")),
+            block_number: Some(String::from("10")),
+            address: Some(String::from(ADDRESS)),
+            topics: IndexedLog::parse_topics(Some(topics)),
+            data: None,
+        };
+        assert_eq!(log.topics.len(), 2);
+        assert!(example_webhook(AddressDirection::Any, Some(TRANSFER_TOPIC)).match_log(&log));
+        assert!(!example_webhook(AddressDirection::Any, Some("0x00")).match_log(&log));
+        assert!(!example_webhook(AddressDirection::Any, None).match_log(&log));
+        assert!(IndexedLog::parse_topics(None).is_empty());
+    }
+
+    #[test]
+    fn delivery_test() {
+        let webhook = example_webhook(AddressDirection::To, None);
+        let mut delivery = AddressWebhookDelivery::tx(&webhook, &example_tx(ADDRESS, None), 100);
+        let body: Value = serde_json::from_str(&delivery.body).unwrap();
+        assert_eq!(body["webhook_id"], 1);
+        assert_eq!(body["event"], "address_tx");
+        assert_eq!(body["data"]["hash"], "0x01");
+
+        assert!(delivery.retry(100, 1, 5));
+        assert_eq!(delivery.next_attempt_at, 105);
+        assert!(!delivery.retry(105, 1, 5));
+
+        assert_eq!(delivery.delivery_key("address_webhook:delivery:"), "address_webhook:delivery:1:0x01:");
+        let stored: AddressWebhookDelivery = serde_json::from_str(&serde_json::to_string(&delivery).unwrap()).unwrap();
+        assert_eq!(stored, delivery);
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::error::ExpectedError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    pub task: String,
+    pub variant: String,
+    pub msg_level: String,
+    pub msg: String,
+}
+
+impl Alert {
+    pub fn new(task: &str, variant: &str, msg_level: &str, msg: &str) -> Self {
+        Alert {
+            task: String::from(task),
+            variant: String::from(variant),
+            msg_level: String::from(msg_level),
+            msg: String::from(msg),
+        }
+    }
+
+    pub fn fingerprint(&self) -> String {
+        format!("{}:{}", self.task, self.variant)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertNotice {
+    pub task: String,
+    pub msg_level: String,
+    pub msg: String,
+}
+
+#[derive(Debug, Clone)]
+struct ActiveAlert {
+    alert: Alert,
+    suppressed: u64,
+    notified_at: u64,
+    last_seen_at: u64,
+    recovered_at: Option<u64>,
+}
+
+/*
+ * The first alert of a fingerprint is sent right away and duplicates are counted instead.
+ * Every `dedup_window` seconds the count is sent as a summary.
+ * An alert resolves only after the task succeeded again and `resolve_after` seconds passed without a duplicate, silence alone does not resolve it.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct AlertState {
+    active: HashMap<String, ActiveAlert>,
+}
+
+impl AlertState {
+    pub fn fire(&mut self, alert: Alert, now: u64) -> Option<AlertNotice> {
+        match self.active.get_mut(&alert.fingerprint()) {
+            Some(active) => {
+                active.suppressed += 1;
+                active.last_seen_at = now;
+                active.recovered_at = None;
+                active.alert = alert;
+                None
+            }
+            None => {
+                let notice = AlertNotice {
+                    task: alert.task.clone(),
+                    msg_level: alert.msg_level.clone(),
+                    msg: alert.msg.clone(),
+                };
+                self.active.insert(alert.fingerprint(), ActiveAlert { alert, suppressed: 0, notified_at: now, last_seen_at: now, recovered_at: None });
+                Some(notice)
+            }
+        }
+    }
+
+    pub fn recover(&mut self, task: &str, now: u64) {
+        for active in self.active.values_mut().filter(|active| active.alert.task == task && active.recovered_at.is_none()) {
+            active.recovered_at = Some(now);
+        }
+    }
+
+    /*
+     * Tasks which have an alert and have not succeeded since.
+     */
+    pub fn failing_tasks(&self) -> HashSet<String> {
+        self.active.values()
+            .filter(|active| active.recovered_at.is_none())
+            .map(|active| active.alert.task.clone())
+            .collect()
+    }
+
+    pub fn tick(&mut self, now: u64, dedup_window: u64, resolve_after: u64) -> Vec<AlertNotice> {
+        let mut notices = Vec::new();
+        let mut resolved = Vec::new();
+        for (fingerprint, active) in self.active.iter_mut() {
+            let alert = &active.alert;
+            if active.recovered_at.is_some() && now.saturating_sub(active.last_seen_at) >= resolve_after {
+                notices.push(AlertNotice {
+                    task: alert.task.clone(),
+                    msg_level: String::from("info"),
+                    msg: format!("resolved! task={}, variant={}, succeeded again and no occurrence for {}s", alert.task, alert.variant, now - active.last_seen_at),
+                });
+                resolved.push(fingerprint.clone());
+            } else if active.suppressed > 0 && now.saturating_sub(active.notified_at) >= dedup_window {
+                notices.push(AlertNotice {
+                    task: alert.task.clone(),
+                    msg_level: alert.msg_level.clone(),
+                    msg: format!("still failing ({} occurrences in {}s)! task={}, variant={}, last_error={}", active.suppressed, now - active.notified_at, alert.task, alert.variant, alert.msg),
+                });
+                active.suppressed = 0;
+                active.notified_at = now;
+            }
+        }
+        for fingerprint in resolved.iter() {
+            self.active.remove(fingerprint);
+        }
+        notices
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRoute {
+    pub task: String,
+    pub msg_level: String,
+    pub hook: String,
+}
+
+impl AlertRoute {
+    /*
+     * Routes are written as `<task>:<msg_level>=<hook>` separated by `,`, and `*` matches any task or level.
+     */
+    pub fn parse(routes: &str) -> Result<Vec<AlertRoute>, ExpectedError> {
+        routes.split(',')
+            .map(|route| route.trim())
+            .filter(|route| !route.is_empty())
+            .map(|route| {
+                let (selector, hook) = route.split_once('=').ok_or(ExpectedError::InvalidError(format!("route must be <task>:<msg_level>=<hook>! route={}", route)))?;
+                let (task, msg_level) = selector.split_once(':').ok_or(ExpectedError::InvalidError(format!("route must be <task>:<msg_level>=<hook>! route={}", route)))?;
+                Ok(AlertRoute {
+                    task: String::from(task.trim()),
+                    msg_level: String::from(msg_level.trim()),
+                    hook: String::from(hook.trim()),
+                })
+            })
+            .collect()
+    }
+
+    pub fn find<'a>(routes: &'a [AlertRoute], task: &str, msg_level: &str) -> Option<&'a str> {
+        routes.iter()
+            .find(|route| (route.task == "*" || route.task == task) && (route.msg_level == "*" || route.msg_level == msg_level))
+            .map(|route| route.hook.as_str())
+    }
+}
+
+#[cfg(test)]
+mod alert_test {
+    use crate::types::alert::{Alert, AlertRoute, AlertState};
+
+    #[test]
+    fn alert_dedup_test() {
+        let mut state = AlertState::default();
+        let alert = Alert::new("l2_block_tx", "RequestError", "error", "connection refused");
+        assert_eq!(state.fire(alert.clone(), 100).unwrap().msg, "connection refused");
+        assert!(state.fire(alert.clone(), 101).is_none());
+        assert!(state.fire(alert.clone(), 102).is_none());
+        assert!(state.fire(Alert::new("l2_block_tx", "ParsingError", "error", "invalid json"), 102).is_some());
+        assert!(state.tick(110, 60, 30).is_empty());
+
+        let notices = state.tick(160, 60, 100);
+        assert_eq!(notices.len(), 1);
+        assert_eq!(notices[0].msg_level, "error");
+        assert!(notices[0].msg.starts_with("still failing (2 occurrences in 60s)!"));
+        assert!(state.tick(161, 60, 100).is_empty());
+    }
+
+    #[test]
+    fn alert_resolve_test() {
+        let mut state = AlertState::default();
+        let alert = Alert::new("l1_tx_log", "RetryFailError", "error", "retry failed");
+        assert!(state.fire(alert.clone(), 100).is_some());
+        assert!(state.fire(alert.clone(), 110).is_none());
+        assert!(state.tick(130, 600, 30).is_empty());
+        assert!(state.tick(200, 600, 30).is_empty());
+        assert!(state.failing_tasks().contains("l1_tx_log"));
+
+        state.recover("l2_block_tx", 210);
+        assert!(state.tick(210, 600, 30).is_empty());
+        state.recover("l1_tx_log", 210);
+        assert!(state.failing_tasks().is_empty());
+
+        let notices = state.tick(210, 600, 30);
+        assert_eq!(notices.len(), 1);
+        assert_eq!(notices[0].msg_level, "info");
+        assert!(notices[0].msg.starts_with("resolved!"));
+        assert!(state.fire(alert, 220).is_some());
+    }
+
+    #[test]
+    fn alert_recover_then_fail_test() {
+        let mut state = AlertState::default();
+        let alert = Alert::new("l2_tx_receipt", "RequestError", "error", "connection refused");
+        assert!(state.fire(alert.clone(), 100).is_some());
+        state.recover("l2_tx_receipt", 105);
+        assert!(state.tick(120, 600, 30).is_empty());
+        assert!(state.fire(alert, 125).is_none());
+        assert!(state.failing_tasks().contains("l2_tx_receipt"));
+        assert!(state.tick(200, 600, 30).is_empty());
+    }
+
+    #[test]
+    fn alert_route_test() {
+        let routes = AlertRoute::parse("l2_block_tx:error=https://hooks.slack.com/services/a, *:error=https://hooks.slack.com/services/b,").unwrap();
+        assert_eq!(routes.len(), 2);
+        assert_eq!(AlertRoute::find(&routes, "l2_block_tx", "error"), Some("https://hooks.slack.com/services/a"));
+        assert_eq!(AlertRoute::find(&routes, "l1_tx_log", "error"), Some("https://hooks.slack.com/services/b"));
+        assert_eq!(AlertRoute::find(&routes, "l1_tx_log", "warn"), None);
+        assert!(AlertRoute::parse("").unwrap().is_empty());
+        assert!(AlertRoute::parse("l2_block_tx=https://hooks.slack.com").is_err());
+    }
+}
@@ -0,0 +1,764 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+use std::net::Ipv4Addr;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::error::error::ExpectedError;
+use crate::types::alert::AlertRoute;
+
+pub type ConfigLookup<'a> = &'a dyn Fn(&str) -> Result<String, ExpectedError>;
+
+/*
+ * Reads options through `lookup` and collects every error, so that all of them are reported at once by `finish`.
+ * An empty value is treated as a missing value.
+ */
+pub struct ConfigReader<'a> {
+    lookup: ConfigLookup<'a>,
+    values: BTreeMap<String, String>,
+    errors: Vec<String>,
+}
+
+impl<'a> ConfigReader<'a> {
+    pub fn new(lookup: ConfigLookup<'a>) -> Self {
+        ConfigReader {
+            lookup,
+            values: BTreeMap::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn optional<T>(&mut self, key: &str) -> Option<T> where T: FromStr, <T as FromStr>::Err: Display {
+        let value = (self.lookup)(key);
+        if let Ok(value) = &value {
+            self.values.insert(String::from(key), value.clone());
+        }
+        match value {
+            Ok(value) if value.is_empty() => None,
+            Ok(value) => match value.parse::<T>() {
+                Ok(parsed) => Some(parsed),
+                Err(err) => {
+                    self.errors.push(format!("{} is not valid! value={}, error={}", key, value, err));
+                    None
+                }
+            },
+            Err(ExpectedError::NoneError(_)) => None,
+            Err(err) => {
+                self.errors.push(format!("{} can not be read! error={}", key, err));
+                None
+            }
+        }
+    }
+
+    pub fn required<T>(&mut self, key: &str) -> T where T: FromStr + Default, <T as FromStr>::Err: Display {
+        let error_count = self.errors.len();
+        match self.optional(key) {
+            Some(value) => value,
+            None => {
+                if self.errors.len() == error_count {
+                    self.errors.push(format!("{} is required!", key));
+                }
+                T::default()
+            }
+        }
+    }
+
+    pub fn or<T>(&mut self, key: &str, default: T) -> T where T: FromStr, <T as FromStr>::Err: Display {
+        self.optional(key).unwrap_or(default)
+    }
+
+    pub fn check(&mut self, key: &str, valid: bool, reason: &str) {
+        if !valid {
+            self.errors.push(format!("{} {}!", key, reason));
+        }
+    }
+
+    pub fn finish<T>(self, config: T) -> Result<T, ExpectedError> {
+        if self.errors.is_empty() {
+            return Ok(config);
+        }
+        Err(ExpectedError::InvalidError(format!("invalid config! {} error(s)\n  {}", self.errors.len(), self.errors.join("\n  "))))
+    }
+
+    fn poll_interval(&mut self, key: &str, default: u64) -> u64 {
+        let poll_interval = self.or(key, default);
+        self.check(key, poll_interval > 0, "must be greater than 0");
+        poll_interval
+    }
+
+    fn url(&mut self, key: &str, url: &str) {
+        self.check(key, url.starts_with("http://") || url.starts_with("https://"), "must be a http or https url");
+    }
+}
+
+fn is_address(address: &str) -> bool {
+    address.len() == 42 && address.starts_with("0x") && address[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[derive(Clone)]
+pub struct JsonRpcConfig {
+    pub host: Ipv4Addr,
+    pub port: u16,
+}
+
+impl JsonRpcConfig {
+    fn read(reader: &mut ConfigReader) -> Self {
+        JsonRpcConfig {
+            host: reader.or("jsonrpc::host", Ipv4Addr::UNSPECIFIED),
+            port: reader.or("jsonrpc::port", 9999),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PostgresConfig {
+    pub host: String,
+    pub port: u16,
+    pub dbname: String,
+    pub user: String,
+    pub password: String,
+    pub allow_destructive: bool,
+    pub migration_dry_run: bool,
+    pub schema_dir: String,
+    pub schema_files: Vec<String>,
+}
+
+impl PostgresConfig {
+    /*
+     * `schema-files` is a comma separated list of schema files, loaded instead of every json file in `schema-dir` when it is set.
+     */
+    fn read(reader: &mut ConfigReader) -> Self {
+        let schema_files = reader.or("postgres::schema-files", String::new()).split(',')
+            .map(|file| file.trim())
+            .filter(|file| !file.is_empty())
+            .map(String::from)
+            .collect::<Vec<String>>();
+        for file in schema_files.iter() {
+            reader.check("postgres::schema-files", Path::new(file).is_file(), &format!("has a missing file, file={}", file));
+        }
+        let schema_dir = reader.or("postgres::schema-dir", String::from("schema"));
+        reader.check("postgres::schema-dir", !schema_files.is_empty() || Path::new(&schema_dir).is_dir(), "is not a directory");
+        PostgresConfig {
+            host: reader.required("postgres::host"),
+            port: reader.or("postgres::port", 5432),
+            dbname: reader.required("postgres::dbname"),
+            user: reader.required("postgres::user"),
+            password: reader.or("postgres::password", String::new()),
+            allow_destructive: reader.or("postgres::allow-destructive", false),
+            migration_dry_run: reader.or("postgres::migration-dry-run", false),
+            schema_dir,
+            schema_files,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SlackConfig {
+    pub activate: bool,
+    pub info: String,
+    pub warn: String,
+    pub error: String,
+}
+
+impl SlackConfig {
+    /*
+     * Hooks are only required when slack is activated.
+     */
+    fn read(reader: &mut ConfigReader) -> Self {
+        let activate = reader.or("slack::activate", false);
+        let mut hook = |key: &str| {
+            if !activate {
+                return reader.or(key, String::new());
+            }
+            let hook: String = reader.required(key);
+            if !hook.is_empty() {
+                reader.url(key, &hook);
+            }
+            hook
+        };
+        SlackConfig {
+            activate,
+            info: hook("slack::info"),
+            warn: hook("slack::warn"),
+            error: hook("slack::error"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AlertConfig {
+    pub dedup_window: u64,
+    pub resolve_after: u64,
+    pub routes: Vec<AlertRoute>,
+}
+
+impl AlertConfig {
+    fn read(reader: &mut ConfigReader) -> Self {
+        let routes = reader.or("alert::routes", String::new());
+        let routes = match AlertRoute::parse(&routes) {
+            Ok(routes) => routes,
+            Err(err) => {
+                reader.check("alert::routes", false, &format!("is not valid, {}", err));
+                Vec::new()
+            }
+        };
+        AlertConfig {
+            dedup_window: reader.or("alert::dedup-window", 600),
+            resolve_after: reader.or("alert::resolve-after", 120),
+            routes,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct EmailConfig {
+    pub activate: bool,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    pub smtp_relay: String,
+    pub smtp_port: Option<u16>,
+    pub smtp_tls: bool,
+    pub from: String,
+    pub reply_to: String,
+    pub info: String,
+    pub warn: String,
+    pub error: String,
+    pub escalation: String,
+    pub digest_interval: u64,
+}
+
+impl EmailConfig {
+    /*
+     * The relay and the senders are only required when email is activated.
+     */
+    fn read(reader: &mut ConfigReader) -> Self {
+        let activate = reader.or("email::activate", false);
+        let mut required = |key: &str| if activate { reader.required(key) } else { reader.or(key, String::new()) };
+        let smtp_relay = required("email::smtp-relay");
+        let from = required("email::from");
+        let reply_to = required("email::reply-to");
+        EmailConfig {
+            activate,
+            smtp_username: reader.optional("email::smtp-username"),
+            smtp_password: reader.optional("email::smtp-password"),
+            smtp_relay,
+            smtp_port: reader.optional("email::smtp-port"),
+            smtp_tls: reader.or("email::smtp-tls", true),
+            from,
+            reply_to,
+            info: reader.or("email::info", String::new()),
+            warn: reader.or("email::warn", String::new()),
+            error: reader.or("email::error", String::new()),
+            escalation: reader.or("email::escalation", String::new()),
+            digest_interval: reader.poll_interval("email::digest-interval", 600),
+        }
+    }
+
+    pub fn recipients(&self, msg_level: &str) -> &str {
+        match msg_level {
+            "info" => &self.info,
+            "warn" => &self.warn,
+            "error" => &self.error,
+            "escalation" => &self.escalation,
+            _ => "",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookConfig {
+    pub activate: bool,
+    pub file: String,
+    pub retry_count: u32,
+    pub retry_backoff: u64,
+}
+
+impl WebhookConfig {
+    fn read(reader: &mut ConfigReader) -> Self {
+        let activate = reader.or("webhook::activate", false);
+        let file = reader.or("webhook::file", String::from("webhook/webhook.json"));
+        if activate {
+            reader.check("webhook::file", Path::new(&file).is_file(), "does not exist");
+        }
+        WebhookConfig {
+            activate,
+            file,
+            retry_count: reader.or("webhook::retry-count", 3),
+            retry_backoff: reader.or("webhook::retry-backoff", 5),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AddressWebhookConfig {
+    pub activate: bool,
+    pub poll_interval: u64,
+    pub batch_size: i64,
+    pub retry_count: u32,
+    pub retry_backoff: u64,
+    pub delivery_budget: u64,
+}
+
+impl AddressWebhookConfig {
+    fn read(reader: &mut ConfigReader) -> Self {
+        let batch_size = reader.or("addresswebhook::batch-size", 100);
+        reader.check("addresswebhook::batch-size", batch_size > 0, "must be greater than 0");
+        AddressWebhookConfig {
+            activate: reader.or("addresswebhook::activate", false),
+            poll_interval: reader.poll_interval("addresswebhook::poll-interval", 1000),
+            batch_size,
+            retry_count: reader.or("addresswebhook::retry-count", 5),
+            retry_backoff: reader.or("addresswebhook::retry-backoff", 5),
+            delivery_budget: reader.or("addresswebhook::delivery-budget", 5000),
+        }
+    }
+}
+
+pub const TASK_PLUGINS: [&str; 12] = [
+    "l2_block_tx", "l2_tx_batch", "l2_state_batch", "l2_tx_receipt", "l2_enqueue", "l1_tx_log",
+    "l1_cross_domain_message", "l1_bridge_transfer", "l2_state_root_verify", "l2_tx_batch_verify", "l2_state_batch_verify", "l2_chain_stat",
+];
+
+/*
+ * Task plugins depending on another task plugin in `#[appbase_plugin(...)]`, which appbase initializes together.
+ * `task_plugin_dependencies_test` checks it against the plugin declarations.
+ */
+const TASK_PLUGIN_DEPENDENCIES: [(&str, &str); 2] = [("l2_block_tx", "l2_tx_receipt"), ("l2_enqueue", "l1_tx_log")];
+
+/*
+ * The selected plugins and every plugin they depend on, directly or through another dependency.
+ */
+fn with_dependencies(selected: &[&str], dependencies: &[(&str, &str)]) -> Vec<String> {
+    let mut required = selected.iter().map(|plugin| String::from(*plugin)).collect::<Vec<String>>();
+    let mut idx = 0;
+    while idx < required.len() {
+        let found = dependencies.iter()
+            .filter(|(dependent, dependency)| *dependent == required[idx] && !required.iter().any(|plugin| plugin == dependency))
+            .map(|(_, dependency)| String::from(*dependency))
+            .collect::<Vec<String>>();
+        required.extend(found);
+        idx += 1;
+    }
+    required
+}
+
+#[derive(Clone)]
+pub struct TaskConfig {
+    pub head_poll_interval: u64,
+    pub plugins: Vec<String>,
+}
+
+impl TaskConfig {
+    /*
+     * `plugins` is a comma separated list of task plugins, and every task plugin is enabled when it is empty.
+     */
+    fn read(reader: &mut ConfigReader) -> Self {
+        let selected = reader.or("task::plugins", String::new());
+        let selected = selected.split(',')
+            .map(|plugin| plugin.trim())
+            .filter(|plugin| !plugin.is_empty())
+            .collect::<Vec<&str>>();
+        for plugin in selected.iter() {
+            reader.check("task::plugins", TASK_PLUGINS.contains(plugin), &format!("has an unknown plugin, plugin={}", plugin));
+        }
+        let required = with_dependencies(&selected, &TASK_PLUGIN_DEPENDENCIES);
+        let plugins = TASK_PLUGINS.iter()
+            .filter(|plugin| selected.is_empty() || required.iter().any(|required| required == *plugin))
+            .map(|plugin| String::from(*plugin))
+            .collect();
+        TaskConfig {
+            head_poll_interval: reader.poll_interval("task::head-poll-interval", 10000),
+            plugins,
+        }
+    }
+
+    pub fn is_enabled(&self, plugin: &str) -> bool {
+        self.plugins.iter().any(|enabled| enabled == plugin)
+    }
+}
+
+#[derive(Clone)]
+pub struct LivenessConfig {
+    pub activate: bool,
+    pub poll_interval: u64,
+    pub block_threshold: i64,
+    pub tx_batch_threshold: i64,
+    pub state_batch_threshold: i64,
+    pub batch_lag_threshold: i64,
+}
+
+impl LivenessConfig {
+    fn read(reader: &mut ConfigReader) -> Self {
+        LivenessConfig {
+            activate: reader.or("liveness::activate", false),
+            poll_interval: reader.poll_interval("liveness::poll-interval", 10000),
+            block_threshold: reader.or("liveness::block-threshold", 300),
+            tx_batch_threshold: reader.or("liveness::tx-batch-threshold", 1800),
+            state_batch_threshold: reader.or("liveness::state-batch-threshold", 1800),
+            batch_lag_threshold: reader.or("liveness::batch-lag-threshold", 1000),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PollConfig {
+    pub poll_interval: u64,
+}
+
+impl PollConfig {
+    fn read(reader: &mut ConfigReader, section: &str, default_poll_interval: u64) -> Self {
+        PollConfig {
+            poll_interval: reader.poll_interval(&format!("{}::poll-interval", section), default_poll_interval),
+        }
+    }
+}
+
+/*
+ * `address` is the L1 contract whose logs are polled, e.g. `l1crossdomainmessage::messenger-address`.
+ * It is only required when the plugin is enabled by `task::plugins`.
+ */
+#[derive(Clone)]
+pub struct ContractPollConfig {
+    pub poll_interval: u64,
+    pub address: String,
+}
+
+impl ContractPollConfig {
+    fn read(reader: &mut ConfigReader, section: &str, address_key: &str, enabled: bool) -> Self {
+        ContractPollConfig {
+            poll_interval: reader.poll_interval(&format!("{}::poll-interval", section), 1000),
+            address: contract_address(reader, &format!("{}::{}", section, address_key), enabled),
+        }
+    }
+}
+
+fn contract_address(reader: &mut ConfigReader, key: &str, enabled: bool) -> String {
+    let address: String = if enabled { reader.required(key) } else { reader.or(key, String::new()) };
+    reader.check(key, address.is_empty() || is_address(&address), "must be a 20 bytes hex address");
+    address
+}
+
+/*
+ * `ctc_address` is the CanonicalTransactionChain, whose `TransactionEnqueued` logs give the queue index of each deposit.
+ */
+#[derive(Clone)]
+pub struct BridgeTransferConfig {
+    pub poll_interval: u64,
+    pub address: String,
+    pub ctc_address: String,
+}
+
+impl BridgeTransferConfig {
+    fn read(reader: &mut ConfigReader, enabled: bool) -> Self {
+        let contract = ContractPollConfig::read(reader, "l1bridgetransfer", "bridge-address", enabled);
+        BridgeTransferConfig {
+            poll_interval: contract.poll_interval,
+            address: contract.address,
+            ctc_address: contract_address(reader, "l1bridgetransfer::ctc-address", enabled),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RetryConfig {
+    pub retry_count: u32,
+    pub retry_endpoint: String,
+}
+
+impl RetryConfig {
+    fn read(reader: &mut ConfigReader, section: &str) -> Self {
+        let key = format!("{}::retry-endpoint", section);
+        let retry_endpoint = reader.or(&key, String::from("http://0.0.0.0:9999"));
+        reader.url(&key, &retry_endpoint);
+        RetryConfig {
+            retry_count: reader.or(&format!("{}::retry-count", section), 3),
+            retry_endpoint,
+        }
+    }
+}
+
+/*
+ * `config-file` is read again on reload, because options parsed by appbase are not refreshed.
+ */
+#[derive(Clone)]
+pub struct ReloadConfig {
+    pub watch: bool,
+    pub poll_interval: u64,
+    pub config_file: String,
+}
+
+impl ReloadConfig {
+    fn read(reader: &mut ConfigReader) -> Self {
+        ReloadConfig {
+            watch: reader.or("reload::watch", false),
+            poll_interval: reader.poll_interval("reload::poll-interval", 5000),
+            config_file: reader.or("reload::config-file", String::from("config.toml")),
+        }
+    }
+}
+
+/*
+ * Options read when a plugin is initialized, so that their changes are only applied by a restart.
+ */
+const RESTART_REQUIRED: [&str; 16] = [
+    "jsonrpc::", "postgres::", "slack::info", "slack::warn", "slack::error", "alert::routes", "email::activate", "email::smtp-",
+    "email::from", "email::reply-to", "webhook::activate", "webhook::file", "addresswebhook::activate", "task::plugins", "reload::watch", "reload::config-file",
+];
+
+#[derive(Clone)]
+pub struct Config {
+    pub jsonrpc: JsonRpcConfig,
+    pub postgres: PostgresConfig,
+    pub slack: SlackConfig,
+    pub alert: AlertConfig,
+    pub email: EmailConfig,
+    pub webhook: WebhookConfig,
+    pub address_webhook: AddressWebhookConfig,
+    pub task: TaskConfig,
+    pub liveness: LivenessConfig,
+    pub l2_block_tx: PollConfig,
+    pub l2_tx_batch: PollConfig,
+    pub l2_state_batch: PollConfig,
+    pub l2_enqueue: PollConfig,
+    pub l2_state_root_verify: PollConfig,
+    pub l2_chain_stat: PollConfig,
+    pub l2_tx_batch_verify: PollConfig,
+    pub l2_state_batch_verify: PollConfig,
+    pub l1_cross_domain_message: ContractPollConfig,
+    pub l1_bridge_transfer: BridgeTransferConfig,
+    pub l1_tx_log: RetryConfig,
+    pub l2_tx_receipt: RetryConfig,
+    pub reload: ReloadConfig,
+    values: BTreeMap<String, String>,
+}
+
+impl Config {
+    pub fn load(lookup: ConfigLookup) -> Result<Self, ExpectedError> {
+        let mut reader = ConfigReader::new(lookup);
+        let task = TaskConfig::read(&mut reader);
+        let config = Config {
+            jsonrpc: JsonRpcConfig::read(&mut reader),
+            postgres: PostgresConfig::read(&mut reader),
+            slack: SlackConfig::read(&mut reader),
+            alert: AlertConfig::read(&mut reader),
+            email: EmailConfig::read(&mut reader),
+            webhook: WebhookConfig::read(&mut reader),
+            address_webhook: AddressWebhookConfig::read(&mut reader),
+            liveness: LivenessConfig::read(&mut reader),
+            l2_block_tx: PollConfig::read(&mut reader, "l2blocktx", 100),
+            l2_tx_batch: PollConfig::read(&mut reader, "l2txbatch", 1000),
+            l2_state_batch: PollConfig::read(&mut reader, "l2statebatch", 1000),
+            l2_enqueue: PollConfig::read(&mut reader, "l2enqueue", 100),
+            l2_state_root_verify: PollConfig::read(&mut reader, "l2staterootverify", 1000),
+            l2_chain_stat: PollConfig::read(&mut reader, "l2chainstat", 1000),
+            l2_tx_batch_verify: PollConfig::read(&mut reader, "l2txbatchverify", 1000),
+            l2_state_batch_verify: PollConfig::read(&mut reader, "l2statebatchverify", 1000),
+            l1_cross_domain_message: ContractPollConfig::read(&mut reader, "l1crossdomainmessage", "messenger-address", task.is_enabled("l1_cross_domain_message")),
+            l1_bridge_transfer: BridgeTransferConfig::read(&mut reader, task.is_enabled("l1_bridge_transfer")),
+            l1_tx_log: RetryConfig::read(&mut reader, "l1txlog"),
+            l2_tx_receipt: RetryConfig::read(&mut reader, "l2txreceipt"),
+            reload: ReloadConfig::read(&mut reader),
+            task,
+            values: reader.values.clone(),
+        };
+        reader.finish(config)
+    }
+
+    /*
+     * Returns the options changed in `other`, split into the ones applied on the fly and the ones requiring a restart.
+     */
+    pub fn diff(&self, other: &Config) -> (Vec<String>, Vec<String>) {
+        let changed = self.values.keys().chain(other.values.keys())
+            .filter(|key| self.values.get(*key) != other.values.get(*key))
+            .cloned()
+            .collect::<BTreeSet<String>>();
+        changed.into_iter().partition(|key| !is_restart_required(key))
+    }
+
+    /*
+     * Loads the config again through `lookup`, but the options requiring a restart keep their current values.
+     * Returns the reloaded config with the changes reported by `diff`.
+     */
+    pub fn reload(&self, lookup: ConfigLookup) -> Result<(Config, (Vec<String>, Vec<String>)), ExpectedError> {
+        let changes = self.diff(&Config::load(lookup)?);
+        let config = Config::load(&|key| match is_restart_required(key) {
+            true => self.values.get(key).cloned().ok_or_else(|| ExpectedError::NoneError(format!("{} is not set!", key))),
+            false => lookup(key),
+        })?;
+        Ok((config, changes))
+    }
+}
+
+fn is_restart_required(key: &str) -> bool {
+    RESTART_REQUIRED.iter().any(|prefix| key.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod config_test {
+    use std::collections::HashMap;
+    use std::fs;
+
+    use crate::error::error::ExpectedError;
+    use crate::types::config::{Config, TASK_PLUGIN_DEPENDENCIES, TASK_PLUGINS, with_dependencies};
+
+    fn load(values: &[(&str, &str)]) -> Result<Config, ExpectedError> {
+        let mut options: HashMap<String, String> = HashMap::new();
+        options.insert(String::from("postgres::host"), String::from("localhost"));
+        options.insert(String::from("postgres::dbname"), String::from("postgres"));
+        options.insert(String::from("postgres::user"), String::from("root"));
+        options.insert(String::from("l1crossdomainmessage::messenger-address"), String::from("0x25ace71c97B33Cc4729CF772ae268934F7ab5fA1"));
+        options.insert(String::from("l1bridgetransfer::bridge-address"), String::from("0x99C9fc46f92E8a1c0deC1b1747d010903E884bE1"));
+        options.insert(String::from("l1bridgetransfer::ctc-address"), String::from("0x5E4e65926BA27467555EB562121fac00D24E9dD2"));
+        for (key, value) in values {
+            options.insert(String::from(*key), String::from(*value));
+        }
+        Config::load(&|key| options.get(key).cloned().ok_or(ExpectedError::NoneError(String::from("argument is null!"))))
+    }
+
+    #[test]
+    fn config_default_test() {
+        let config = load(&[]).unwrap();
+        assert_eq!(config.postgres.port, 5432);
+        assert_eq!(config.jsonrpc.port, 9999);
+        assert_eq!(config.l2_block_tx.poll_interval, 100);
+        assert_eq!(config.l1_tx_log.retry_endpoint, "http://0.0.0.0:9999");
+        assert!(!config.slack.activate);
+        assert!(config.email.smtp_tls);
+        assert!(!config.postgres.allow_destructive);
+        assert!(!config.postgres.migration_dry_run);
+        assert_eq!(config.postgres.schema_dir, "schema");
+        assert!(config.postgres.schema_files.is_empty());
+    }
+
+    #[test]
+    fn config_contract_address_test() {
+        let without_address = [("l1crossdomainmessage::messenger-address", ""), ("l1bridgetransfer::bridge-address", ""), ("l1bridgetransfer::ctc-address", "")];
+        let config = load(&[without_address[0], without_address[1], without_address[2], ("task::plugins", "l2_block_tx")]).unwrap();
+        assert!(config.l1_bridge_transfer.address.is_empty());
+        assert!(config.l1_bridge_transfer.ctc_address.is_empty());
+
+        let err = load(&[without_address[0], without_address[1], without_address[2], ("task::plugins", "l1_bridge_transfer")]).err().unwrap().to_string();
+        assert!(err.contains("2 error(s)"));
+        assert!(err.contains("l1bridgetransfer::bridge-address is required!"));
+        assert!(err.contains("l1bridgetransfer::ctc-address is required!"));
+
+        let err = load(&[("l1bridgetransfer::ctc-address", "0x5E4e"), ("task::plugins", "l1_bridge_transfer")]).err().unwrap().to_string();
+        assert!(err.contains("l1bridgetransfer::ctc-address"));
+    }
+
+    /*
+     * `L2TxReceiptPlugin` in `#[appbase_plugin(...)]` of `src/plugin/l2_block_tx.rs` is the dependency (l2_block_tx, l2_tx_receipt).
+     */
+    #[test]
+    fn task_plugin_dependencies_test() {
+        let plugin_name = |dependency: &str| dependency.trim().trim_end_matches("Plugin").chars()
+            .enumerate()
+            .flat_map(|(index, c)| if index > 0 && c.is_ascii_uppercase() { vec!['_', c.to_ascii_lowercase()] } else { vec![c.to_ascii_lowercase()] })
+            .collect::<String>();
+        let mut declared = Vec::new();
+        for plugin in TASK_PLUGINS.iter() {
+            let source = fs::read_to_string(format!("src/plugin/{}.rs", plugin)).unwrap();
+            let declaration = source.lines().find(|line| line.starts_with("#[appbase_plugin(")).unwrap();
+            let dependencies = declaration.trim_start_matches("#[appbase_plugin(").trim_end_matches(")]").split(',').map(plugin_name);
+            for dependency in dependencies.filter(|dependency| TASK_PLUGINS.contains(&dependency.as_str())) {
+                declared.push((String::from(*plugin), dependency));
+            }
+        }
+        let mut expected = TASK_PLUGIN_DEPENDENCIES.iter().map(|(dependent, dependency)| (String::from(*dependent), String::from(*dependency))).collect::<Vec<(String, String)>>();
+        declared.sort();
+        expected.sort();
+        assert_eq!(declared, expected);
+    }
+
+    #[test]
+    fn with_dependencies_test() {
+        let dependencies = [("a", "b"), ("b", "c"), ("c", "a"), ("d", "e")];
+        assert_eq!(with_dependencies(&["a"], &dependencies), vec!["a", "b", "c"]);
+        assert_eq!(with_dependencies(&["d", "b"], &dependencies), vec!["d", "b", "e", "c", "a"]);
+        assert!(with_dependencies(&[], &dependencies).is_empty());
+    }
+
+    #[test]
+    fn config_schema_files_test() {
+        let config = load(&[("postgres::schema-files", "schema/optimism.json, schema/ethereum.json")]).unwrap();
+        assert_eq!(config.postgres.schema_files, vec!["schema/optimism.json", "schema/ethereum.json"]);
+
+        let err = load(&[("postgres::schema-files", "schema/unknown.json")]).err().unwrap().to_string();
+        assert!(err.contains("postgres::schema-files has a missing file, file=schema/unknown.json!"));
+        let err = load(&[("postgres::schema-dir", "not_found")]).err().unwrap().to_string();
+        assert!(err.contains("postgres::schema-dir is not a directory!"));
+    }
+
+    #[test]
+    fn config_error_test() {
+        let err = load(&[
+            ("postgres::host", ""),
+            ("postgres::port", "port"),
+            ("l2blocktx::poll-interval", "0"),
+            ("slack::activate", "true"),
+            ("slack::info", "hooks.slack.com"),
+            ("alert::routes", "l2_block_tx=hook"),
+        ]).err().unwrap().to_string();
+        assert!(err.contains("7 error(s)"));
+        assert!(err.contains("postgres::host is required!"));
+        assert!(err.contains("postgres::port is not valid! value=port"));
+        assert!(err.contains("l2blocktx::poll-interval must be greater than 0!"));
+        assert!(err.contains("slack::info must be a http or https url!"));
+        assert!(err.contains("slack::warn is required!"));
+        assert!(err.contains("alert::routes is not valid"));
+    }
+
+    #[test]
+    fn config_task_plugins_test() {
+        assert_eq!(load(&[]).unwrap().task.plugins.len(), 12);
+
+        let config = load(&[("task::plugins", "l2_tx_batch, l2_block_tx")]).unwrap();
+        assert_eq!(config.task.plugins, vec!["l2_block_tx", "l2_tx_batch", "l2_tx_receipt"]);
+        assert!(config.task.is_enabled("l2_tx_receipt"));
+        assert!(!config.task.is_enabled("l2_enqueue"));
+
+        let err = load(&[("task::plugins", "l2_block_tx,l2_blocks")]).err().unwrap().to_string();
+        assert!(err.contains("task::plugins has an unknown plugin, plugin=l2_blocks!"));
+    }
+
+    #[test]
+    fn config_diff_test() {
+        let config = load(&[]).unwrap();
+        assert_eq!(config.diff(&load(&[]).unwrap()), (vec![], vec![]));
+
+        let reloaded = load(&[("l2blocktx::poll-interval", "200"), ("postgres::host", "postgres"), ("email::smtp-relay", "smtp.gmail.com")]).unwrap();
+        let (applied, restart_required) = config.diff(&reloaded);
+        assert_eq!(applied, vec!["l2blocktx::poll-interval"]);
+        assert_eq!(restart_required, vec!["email::smtp-relay", "postgres::host"]);
+    }
+
+    #[test]
+    fn config_reload_test() {
+        let config = load(&[]).unwrap();
+        let mut options: HashMap<String, String> = HashMap::new();
+        options.insert(String::from("postgres::host"), String::from("postgres"));
+        options.insert(String::from("postgres::dbname"), String::from("postgres"));
+        options.insert(String::from("postgres::user"), String::from("root"));
+        options.insert(String::from("l1crossdomainmessage::messenger-address"), String::from("0x25ace71c97B33Cc4729CF772ae268934F7ab5fA1"));
+        options.insert(String::from("l1bridgetransfer::bridge-address"), String::from("0x99C9fc46f92E8a1c0deC1b1747d010903E884bE1"));
+        options.insert(String::from("l1bridgetransfer::ctc-address"), String::from("0x5E4e65926BA27467555EB562121fac00D24E9dD2"));
+        options.insert(String::from("l2blocktx::poll-interval"), String::from("200"));
+        options.insert(String::from("jsonrpc::port"), String::from("8888"));
+        let (reloaded, (applied, restart_required)) = config.reload(&|key| options.get(key).cloned().ok_or(ExpectedError::NoneError(String::from("argument is null!")))).unwrap();
+        assert_eq!(applied, vec!["l2blocktx::poll-interval"]);
+        assert_eq!(restart_required, vec!["jsonrpc::port", "postgres::host"]);
+        assert_eq!(reloaded.l2_block_tx.poll_interval, 200);
+        assert_eq!(reloaded.postgres.host, "localhost");
+        assert_eq!(reloaded.jsonrpc.port, 9999);
+        assert_eq!(config.diff(&reloaded), (vec![String::from("l2blocktx::poll-interval")], vec![]));
+    }
+
+    #[test]
+    fn config_activate_test() {
+        assert!(load(&[("email::activate", "false")]).is_ok());
+        let err = load(&[("email::activate", "true"), ("email::smtp-relay", "smtp.gmail.com")]).err().unwrap().to_string();
+        assert!(err.contains("email::from is required!"));
+        assert!(err.contains("email::reply-to is required!"));
+        assert!(!err.contains("email::smtp-relay"));
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailDigestEntry {
+    pub task: String,
+    pub msg: String,
+    pub received_at: u64,
+}
+
+/*
+ * Collects alerts by level, so each level is sent as a single email every digest interval.
+ */
+#[derive(Debug, Clone)]
+pub struct EmailDigest {
+    entries: BTreeMap<String, Vec<EmailDigestEntry>>,
+    flushed_at: u64,
+}
+
+impl EmailDigest {
+    pub fn new(now: u64) -> Self {
+        EmailDigest {
+            entries: BTreeMap::new(),
+            flushed_at: now,
+        }
+    }
+
+    pub fn push(&mut self, msg_level: &str, task: &str, msg: &str, now: u64) {
+        self.entries.entry(String::from(msg_level)).or_insert_with(Vec::new).push(EmailDigestEntry {
+            task: String::from(task),
+            msg: String::from(msg),
+            received_at: now,
+        });
+    }
+
+    pub fn is_due(&self, now: u64, digest_interval: u64) -> bool {
+        !self.entries.is_empty() && now.saturating_sub(self.flushed_at) >= digest_interval
+    }
+
+    /*
+     * Returns (msg_level, subject, body) for every level with alerts.
+     */
+    pub fn flush(&mut self, now: u64) -> Vec<(String, String, String)> {
+        self.flushed_at = now;
+        let entries = std::mem::take(&mut self.entries);
+        entries.into_iter()
+            .map(|(msg_level, entries)| {
+                let subject = format!("[bleu-daemon] {} digest ({} alerts)", msg_level, entries.len());
+                let body = entries.iter()
+                    .map(|entry| format!("[{}] task={}, msg={}", entry.received_at, entry.task, entry.msg))
+                    .collect::<Vec<String>>()
+                    .join("\n");
+                (msg_level, subject, body)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod email_test {
+    use crate::types::email::EmailDigest;
+
+    #[test]
+    fn email_digest_test() {
+        let mut digest = EmailDigest::new(100);
+        assert!(!digest.is_due(1000, 600));
+
+        digest.push("error", "l2_block_tx", "connection refused", 110);
+        digest.push("warn", "postgres", "duplicate key", 120);
+        digest.push("error", "l1_tx_log", "retry failed", 130);
+        assert!(!digest.is_due(699, 600));
+        assert!(digest.is_due(700, 600));
+
+        let emails = digest.flush(700);
+        assert_eq!(emails.len(), 2);
+        assert_eq!(emails[0].0, "error");
+        assert_eq!(emails[0].1, "[bleu-daemon] error digest (2 alerts)");
+        assert_eq!(emails[0].2, "[110] task=l2_block_tx, msg=connection refused\n[130] task=l1_tx_log, msg=retry failed");
+        assert_eq!(emails[1].1, "[bleu-daemon] warn digest (1 alerts)");
+
+        assert!(digest.flush(800).is_empty());
+        digest.push("error", "l2_block_tx", "connection refused", 810);
+        assert!(!digest.is_due(810, 600));
+    }
+}
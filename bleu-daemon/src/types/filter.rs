@@ -0,0 +1,542 @@
+use std::cmp::Ordering;
+
+use primitive_types::U256;
+use regex::Regex;
+use serde_json::{Map, Value};
+
+use crate::error::error::ExpectedError;
+use crate::libs::serde::{find_value, get_value_by_path};
+use crate::types::mapping::JsonPath;
+
+/*
+ * A task filter, parsed once when the task is loaded.
+ *
+ *   expr       := or
+ *   or         := and (('|' | 'or') and)*
+ *   and        := unary (('&' | 'and') unary)*
+ *   unary      := ('!' | 'not') unary | '(' expr ')' | comparison
+ *   comparison := path ('=' | '!=' | '<' | '<=' | '>' | '>=') value
+ *               | path 'in' '[' value (',' value)* ']'
+ *               | path 'contains' value
+ *               | path 'matches' /regex/[i]
+ *
+ * `key` searches the response for the first `key`, `a.b` and `$.a.b[0]` are paths from the root.
+ * Values are bare words or quoted strings. Numbers, including hex, are compared as U256 when both sides are numbers,
+ * and hex strings are compared case-insensitively, so addresses match regardless of their checksum.
+ */
+#[derive(Debug, Clone)]
+pub struct Filter {
+    source: String,
+    expr: Option<Expr>,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare(Path, Comparison),
+}
+
+#[derive(Debug, Clone)]
+enum Path {
+    Search(String),
+    Dotted(String),
+    Root(JsonPath),
+}
+
+#[derive(Debug, Clone)]
+enum Comparison {
+    Eq(String),
+    Ne(String),
+    Lt(String),
+    Le(String),
+    Gt(String),
+    Ge(String),
+    In(Vec<String>),
+    Contains(String),
+    Matches(Regex),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    And,
+    Or,
+    Not,
+    Op(String),
+    Word(String),
+    Quoted(String),
+    Regex(String, bool),
+}
+
+impl Filter {
+    pub fn parse(source: &str) -> Result<Self, ExpectedError> {
+        let tokens = tokenize(source)?;
+        if tokens.is_empty() {
+            return Ok(Filter { source: String::from(source), expr: None });
+        }
+        let mut parser = Parser { source, tokens, pos: 0 };
+        let expr = parser.or()?;
+        if let Some((token, at)) = parser.tokens.get(parser.pos) {
+            return Err(syntax_error(source, *at, &format!("unexpected {:?}", token)));
+        }
+        Ok(Filter { source: String::from(source), expr: Some(expr) })
+    }
+
+    pub fn matches(&self, values: &Map<String, Value>) -> bool {
+        match &self.expr {
+            Some(expr) => expr.eval(values),
+            None => true,
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+fn syntax_error(source: &str, at: usize, msg: &str) -> ExpectedError {
+    ExpectedError::InvalidError(format!("filter syntax error! {} at {}, filter={}", msg, at, source))
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ExpectedError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens: Vec<(Token, usize)> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).cloned();
+        let start = i;
+        let token = match c {
+            _ if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            ',' => Token::Comma,
+            '&' => {
+                if next == Some('&') { i += 1; }
+                Token::And
+            }
+            '|' => {
+                if next == Some('|') { i += 1; }
+                Token::Or
+            }
+            '!' if next == Some('=') => {
+                i += 1;
+                Token::Op(String::from("!="))
+            }
+            '!' => Token::Not,
+            '=' => {
+                if next == Some('=') { i += 1; }
+                Token::Op(String::from("="))
+            }
+            '<' | '>' => {
+                if next == Some('=') {
+                    i += 1;
+                    Token::Op(format!("{}=", c))
+                } else {
+                    Token::Op(c.to_string())
+                }
+            }
+            '"' | '\'' => {
+                let (text, end) = read_until(&chars, i + 1, c).ok_or_else(|| syntax_error(source, start, "unterminated string"))?;
+                i = end;
+                Token::Quoted(text)
+            }
+            '/' if matches!(tokens.last(), Some((Token::Word(word), _)) if word == "matches") => {
+                let (pattern, end) = read_until(&chars, i + 1, '/').ok_or_else(|| syntax_error(source, start, "unterminated regex"))?;
+                i = end;
+                let case_insensitive = chars.get(i + 1) == Some(&'i');
+                if case_insensitive { i += 1; }
+                Token::Regex(pattern, case_insensitive)
+            }
+            _ => {
+                let is_path = c == '$';
+                let mut word = String::new();
+                while i < chars.len() && !is_delimiter(chars[i], is_path) {
+                    word.push(chars[i]);
+                    i += 1;
+                }
+                i -= 1;
+                match word.as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => Token::Word(word),
+                }
+            }
+        };
+        tokens.push((token, start));
+        i += 1;
+    }
+    Ok(tokens)
+}
+
+fn is_delimiter(c: char, is_path: bool) -> bool {
+    c.is_whitespace() || "()&|,=!<>\"'".contains(c) || (!is_path && "[]".contains(c))
+}
+
+/*
+ * Returns the text until the unescaped `end`, and the position of `end`.
+ */
+fn read_until(chars: &[char], from: usize, end: char) -> Option<(String, usize)> {
+    let mut text = String::new();
+    let mut i = from;
+    while i < chars.len() {
+        if chars[i] == '\\' && chars.get(i + 1) == Some(&end) {
+            text.push(end);
+            i += 2;
+            continue;
+        }
+        if chars[i] == end {
+            return Some((text, i));
+        }
+        text.push(chars[i]);
+        i += 1;
+    }
+    None
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn next(&mut self, expected: &str) -> Result<Token, ExpectedError> {
+        match self.tokens.get(self.pos) {
+            Some((token, _)) => {
+                self.pos += 1;
+                Ok(token.clone())
+            }
+            None => Err(syntax_error(self.source, self.source.chars().count(), &format!("expected {} but reached the end", expected))),
+        }
+    }
+
+    fn error(&self, msg: &str) -> ExpectedError {
+        let at = self.tokens.get(self.pos.saturating_sub(1)).map_or(0, |(_, at)| *at);
+        syntax_error(self.source, at, msg)
+    }
+
+    fn or(&mut self) -> Result<Expr, ExpectedError> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, ExpectedError> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ExpectedError> {
+        match self.next("an expression")? {
+            Token::Not => Ok(Expr::Not(Box::new(self.unary()?))),
+            Token::LParen => {
+                let expr = self.or()?;
+                match self.next("')'")? {
+                    Token::RParen => Ok(expr),
+                    token => Err(self.error(&format!("expected ')' but found {:?}", token))),
+                }
+            }
+            Token::Word(path) => {
+                let path = self.path(path)?;
+                Ok(Expr::Compare(path, self.comparison()?))
+            }
+            token => Err(self.error(&format!("expected a path but found {:?}", token))),
+        }
+    }
+
+    fn path(&self, path: String) -> Result<Path, ExpectedError> {
+        if !path.starts_with('$') {
+            return Ok(if path.contains('.') { Path::Dotted(path) } else { Path::Search(path) });
+        }
+        match JsonPath::parse(&path) {
+            Ok(json_path) => Ok(Path::Root(json_path)),
+            Err(err) => Err(self.error(&err.to_string())),
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Comparison, ExpectedError> {
+        let comparison = match self.next("an operator")? {
+            Token::Op(op) => {
+                let value = self.value()?;
+                match op.as_str() {
+                    "=" => Comparison::Eq(value),
+                    "!=" => Comparison::Ne(value),
+                    "<" => Comparison::Lt(value),
+                    "<=" => Comparison::Le(value),
+                    ">" => Comparison::Gt(value),
+                    _ => Comparison::Ge(value),
+                }
+            }
+            Token::Word(op) if op == "in" => Comparison::In(self.list()?),
+            Token::Word(op) if op == "contains" => Comparison::Contains(self.value()?),
+            Token::Word(op) if op == "matches" => match self.next("a regex")? {
+                Token::Regex(pattern, case_insensitive) => {
+                    let pattern = if case_insensitive { format!("(?i){}", pattern) } else { pattern };
+                    Comparison::Matches(Regex::new(&pattern).map_err(|err| self.error(&format!("invalid regex, {}", err)))?)
+                }
+                token => return Err(self.error(&format!("expected /regex/ but found {:?}", token))),
+            },
+            token => return Err(self.error(&format!("expected an operator but found {:?}", token))),
+        };
+        Ok(comparison)
+    }
+
+    fn value(&mut self) -> Result<String, ExpectedError> {
+        match self.next("a value")? {
+            Token::Word(value) | Token::Quoted(value) => Ok(value),
+            token => Err(self.error(&format!("expected a value but found {:?}", token))),
+        }
+    }
+
+    fn list(&mut self) -> Result<Vec<String>, ExpectedError> {
+        if self.next("'['")? != Token::LBracket {
+            return Err(self.error("expected '['"));
+        }
+        let mut values = Vec::new();
+        loop {
+            values.push(self.value()?);
+            match self.next("']'")? {
+                Token::Comma => continue,
+                Token::RBracket => return Ok(values),
+                token => return Err(self.error(&format!("expected ',' or ']' but found {:?}", token))),
+            }
+        }
+    }
+}
+
+impl Expr {
+    fn eval(&self, values: &Map<String, Value>) -> bool {
+        match self {
+            Expr::And(left, right) => left.eval(values) && right.eval(values),
+            Expr::Or(left, right) => left.eval(values) || right.eval(values),
+            Expr::Not(expr) => !expr.eval(values),
+            Expr::Compare(path, comparison) => comparison.eval(&path.resolve(values)),
+        }
+    }
+}
+
+impl Path {
+    fn resolve(&self, values: &Map<String, Value>) -> Value {
+        match self {
+            Path::Search(key) => find_value(values, key),
+            Path::Dotted(path) => get_value_by_path(values, path).cloned().unwrap_or(Value::Null),
+            Path::Root(json_path) => json_path.resolve(values),
+        }
+    }
+}
+
+impl Comparison {
+    fn eval(&self, found: &Value) -> bool {
+        match self {
+            Comparison::Eq(expected) => is_equal(found, expected),
+            Comparison::Ne(expected) => !is_equal(found, expected),
+            Comparison::Lt(expected) => compare(found, expected) == Some(Ordering::Less),
+            Comparison::Le(expected) => matches!(compare(found, expected), Some(Ordering::Less) | Some(Ordering::Equal)),
+            Comparison::Gt(expected) => compare(found, expected) == Some(Ordering::Greater),
+            Comparison::Ge(expected) => matches!(compare(found, expected), Some(Ordering::Greater) | Some(Ordering::Equal)),
+            Comparison::In(expected) => expected.iter().any(|expected| is_equal(found, expected)),
+            Comparison::Contains(expected) => match found {
+                Value::Array(items) => items.iter().any(|item| is_equal(item, expected)),
+                Value::Null => false,
+                _ => to_text(found).contains(expected.as_str()),
+            },
+            Comparison::Matches(regex) => !found.is_null() && regex.is_match(&to_text(found)),
+        }
+    }
+}
+
+fn to_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        _ => value.to_string(),
+    }
+}
+
+fn is_equal(found: &Value, expected: &str) -> bool {
+    let found = to_text(found);
+    if let Some(ordering) = compare_number(&found, expected) {
+        return ordering == Ordering::Equal;
+    }
+    if found.starts_with("0x") && expected.starts_with("0x") {
+        return found.eq_ignore_ascii_case(expected);
+    }
+    found == expected
+}
+
+fn compare(found: &Value, expected: &str) -> Option<Ordering> {
+    if found.is_null() {
+        return None;
+    }
+    compare_number(&to_text(found), expected)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Number {
+    Int(U256),
+    Float(f64),
+}
+
+impl Number {
+    fn parse(text: &str) -> Option<Number> {
+        if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+            if hex.is_empty() || hex.len() > 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+            return U256::from_str_radix(hex, 16).ok().map(Number::Int);
+        }
+        if !text.is_empty() && text.chars().all(|c| c.is_ascii_digit()) {
+            return U256::from_dec_str(text).ok().map(Number::Int);
+        }
+        if text.chars().any(|c| c.is_ascii_digit()) && text.chars().all(|c| c.is_ascii_digit() || "+-.eE".contains(c)) {
+            return text.parse::<f64>().ok().map(Number::Float);
+        }
+        None
+    }
+
+    fn to_f64(&self) -> f64 {
+        match self {
+            Number::Int(int) => int.to_string().parse::<f64>().unwrap_or(f64::MAX),
+            Number::Float(float) => *float,
+        }
+    }
+}
+
+fn compare_number(found: &str, expected: &str) -> Option<Ordering> {
+    match (Number::parse(found)?, Number::parse(expected)?) {
+        (Number::Int(found), Number::Int(expected)) => Some(found.cmp(&expected)),
+        (found, expected) => found.to_f64().partial_cmp(&expected.to_f64()),
+    }
+}
+
+#[cfg(test)]
+mod filter_test {
+    use serde_json::{json, Map, Value};
+
+    use crate::types::filter::Filter;
+
+    fn example_response() -> Map<String, Value> {
+        json!({
+            "result": {
+                "from": "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF",
+                "to": "0x4200000000000000000000000000000000000007",
+                "value": "0x1bc16d674ec80000",
+                "gas": 21000,
+                "queueOrigin": "l1",
+                "input": "0xa9059cbb000000000000000000000000",
+                "logs": [{"topics": ["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"]}],
+                "tags": ["bridge", "deposit"]
+            }
+        }).as_object().unwrap().clone()
+    }
+
+    fn matches(filter: &str) -> bool {
+        Filter::parse(filter).unwrap().matches(&example_response())
+    }
+
+    #[test]
+    fn filter_success_test() {
+        let mut test_map = Map::new();
+        test_map.insert(String::from("key1"), Value::String(String::from("val1")));
+        test_map.insert(String::from("key2"), json!({"sub_key1": "sub_val1"}));
+        test_map.insert(String::from("key3"), json!(100));
+
+        let filter = Filter::parse("(key1 = val1 & sub_key1 = sub_val1 & key3 =101) | key4=null | key3=101").unwrap();
+        assert!(filter.matches(&test_map));
+    }
+
+    #[test]
+    fn filter_fail_test() {
+        let mut test_map = Map::new();
+        test_map.insert(String::from("key1"), Value::String(String::from("val1")));
+        test_map.insert(String::from("key2"), json!({"sub_key1": "sub_val1"}));
+        test_map.insert(String::from("key3"), json!(100));
+        test_map.insert(String::from("key4"), Value::String(String::from("not_null")));
+
+        let filter = Filter::parse("(key1 = val1 & sub_key1 = sub_val1 & key3 =100) & key4=null").unwrap();
+        assert!(!filter.matches(&test_map));
+    }
+
+    #[test]
+    fn filter_empty_test() {
+        assert!(matches(""));
+        assert!(matches("   "));
+    }
+
+    #[test]
+    fn filter_numeric_test() {
+        assert!(matches("gas = 21000"));
+        assert!(matches("gas >= 21000 and gas < 0x5209"));
+        assert!(!matches("gas > 21000"));
+        assert!(matches("value > 1000000000000000000"));
+        assert!(matches("value = 2000000000000000000"));
+        assert!(matches("gas <= 21000.5"));
+        assert!(!matches("queueOrigin > 1"));
+        assert!(!matches("unknown < 1"));
+    }
+
+    #[test]
+    fn filter_string_test() {
+        assert!(matches("from = 0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+        assert!(matches("to != 0x4200000000000000000000000000000000000010"));
+        assert!(matches("queueOrigin in [l1, \"l2\"]"));
+        assert!(!matches("queueOrigin in [sequencer]"));
+        assert!(matches("tags contains deposit"));
+        assert!(matches("input contains a9059cbb"));
+        assert!(matches("input matches /^0xa9059cbb/"));
+        assert!(matches("from matches /^0xdead/i"));
+        assert!(!matches("from matches /^0xdead/"));
+        assert!(matches("not (queueOrigin = sequencer) && !(gas < 100)"));
+        assert!(matches("queueOrigin = 'l1' || to = 0x00"));
+    }
+
+    #[test]
+    fn filter_path_test() {
+        assert!(matches("result.queueOrigin = l1"));
+        assert!(matches("$.result.to = 0x4200000000000000000000000000000000000007"));
+        assert!(matches("$.result.logs[0].topics[0] = 0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"));
+        assert!(matches("$.result.logs[1].topics[0] = null"));
+        assert!(matches("$.result.tags[1] = deposit"));
+    }
+
+    #[test]
+    fn filter_syntax_error_test() {
+        assert!(Filter::parse("to = ").is_err());
+        assert!(Filter::parse("(to = 0x00").is_err());
+        assert!(Filter::parse("to = 0x00)").is_err());
+        assert!(Filter::parse("to 0x00").is_err());
+        assert!(Filter::parse("to in [0x00").is_err());
+        assert!(Filter::parse("to matches /(/").is_err());
+        assert!(Filter::parse("to matches /abc").is_err());
+        assert!(Filter::parse("to = 'abc").is_err());
+        assert!(Filter::parse("$result = 1").is_err());
+        assert!(Filter::parse("$.logs[a] = 1").is_err());
+
+        let err = Filter::parse("to = 0x00 &").unwrap_err().to_string();
+        assert!(err.contains("filter syntax error!"));
+        assert!(err.contains("filter=to = 0x00 &"));
+    }
+}
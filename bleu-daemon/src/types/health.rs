@@ -0,0 +1,69 @@
+use serde::Serialize;
+
+use crate::error::error::ExpectedError;
+
+pub type HealthChecker = Box<dyn Fn() -> Result<(), ExpectedError> + Send + Sync>;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthCheck {
+    pub name: String,
+    pub ok: bool,
+    pub message: String,
+}
+
+impl HealthCheck {
+    pub fn new(name: &str, result: Result<(), ExpectedError>) -> Self {
+        let (ok, message) = match result {
+            Ok(_) => (true, String::from("ok")),
+            Err(err) => (false, err.to_string()),
+        };
+        HealthCheck {
+            name: String::from(name),
+            ok,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub status: String,
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    pub fn new(checks: Vec<HealthCheck>) -> Self {
+        let status = if checks.iter().all(|check| check.ok) { "ok" } else { "fail" };
+        HealthReport {
+            status: String::from(status),
+            checks,
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.status == "ok"
+    }
+}
+
+#[cfg(test)]
+mod health_test {
+    use crate::error::error::ExpectedError;
+    use crate::types::health::{HealthCheck, HealthReport};
+
+    #[test]
+    fn health_report_test() {
+        let report = HealthReport::new(vec![HealthCheck::new("rocksdb", Ok(()))]);
+        assert!(report.is_ok());
+        assert_eq!(report.checks[0].message, "ok");
+
+        let report = HealthReport::new(vec![
+            HealthCheck::new("rocksdb", Ok(())),
+            HealthCheck::new("postgres", Err(ExpectedError::PostgresError(String::from("connection refused")))),
+        ]);
+        assert!(!report.is_ok());
+        assert_eq!(report.status, "fail");
+        assert_eq!(report.checks[1].message, "connection refused");
+
+        assert!(HealthReport::new(vec![]).is_ok());
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::HashSet;
+
+pub enum LivenessEvent {
+    Alert,
+    Recovery,
+}
+
+#[derive(Debug, Clone)]
+pub struct LivenessCheck {
+    pub name: String,
+    pub value: Option<i64>,
+    pub threshold: i64,
+}
+
+impl LivenessCheck {
+    pub fn new(name: &str, value: Option<i64>, threshold: i64) -> Self {
+        LivenessCheck {
+            name: String::from(name),
+            value,
+            threshold,
+        }
+    }
+
+    /*
+     * A check without value (e.g. nothing indexed yet) is never violated.
+     */
+    pub fn is_violated(&self) -> bool {
+        self.value.map_or(false, |value| value > self.threshold)
+    }
+}
+
+/*
+ * Keeps the checks currently alerting, so an alert is sent once when a threshold is crossed and a recovery once when it returns to normal.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct LivenessState {
+    alerting: HashSet<String>,
+}
+
+impl LivenessState {
+    pub fn update(&mut self, check: &LivenessCheck) -> Option<LivenessEvent> {
+        match (self.alerting.contains(&check.name), check.is_violated()) {
+            (false, true) => {
+                self.alerting.insert(check.name.clone());
+                Some(LivenessEvent::Alert)
+            }
+            (true, false) => {
+                self.alerting.remove(&check.name);
+                Some(LivenessEvent::Recovery)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod liveness_test {
+    use crate::types::liveness::{LivenessCheck, LivenessEvent, LivenessState};
+
+    #[test]
+    fn liveness_check_test() {
+        assert!(LivenessCheck::new("l2_block", Some(301), 300).is_violated());
+        assert!(!LivenessCheck::new("l2_block", Some(300), 300).is_violated());
+        assert!(!LivenessCheck::new("l2_block", None, 300).is_violated());
+    }
+
+    #[test]
+    fn liveness_state_update_test() {
+        let mut state = LivenessState::default();
+        assert!(state.update(&LivenessCheck::new("tx_batch", Some(10), 100)).is_none());
+        assert!(matches!(state.update(&LivenessCheck::new("tx_batch", Some(101), 100)), Some(LivenessEvent::Alert)));
+        assert!(state.update(&LivenessCheck::new("tx_batch", Some(200), 100)).is_none());
+        assert!(state.update(&LivenessCheck::new("state_batch", Some(10), 100)).is_none());
+        assert!(matches!(state.update(&LivenessCheck::new("tx_batch", Some(50), 100)), Some(LivenessEvent::Recovery)));
+        assert!(state.update(&LivenessCheck::new("tx_batch", Some(50), 100)).is_none());
+    }
+}
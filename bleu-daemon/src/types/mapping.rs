@@ -0,0 +1,266 @@
+use primitive_types::U256;
+use serde_json::{Map, Value};
+
+use crate::error::error::ExpectedError;
+use crate::libs::abi::{address_to_hex, topic_to_address};
+use crate::libs::convert::{hex_to_decimal, value_to_u256};
+use crate::libs::opt::opt_to_result;
+
+const WEI_DECIMALS: usize = 18;
+
+type TransformFn = fn(&Value) -> Result<Value, ExpectedError>;
+
+/*
+ * Transforms that are not generic enough to have their own name. `custom:<name>` in the schema selects one of them.
+ */
+const CUSTOM_TRANSFORMS: [(&str, TransformFn); 1] = [
+    ("topic_to_address", custom_topic_to_address),
+];
+
+/*
+ * A path from the root of a message, such as `$.logs[0].topics[1]`.
+ * Unlike `find_value`, a missing key resolves to null instead of a key of the same name at another depth.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone)]
+pub enum Transform {
+    HexToDecimal,
+    NumberToString,
+    Lowercase,
+    Json,
+    WeiToEther,
+    Custom(TransformFn),
+}
+
+/*
+ * Where an attribute takes its value from a message, and how the value is transformed before it is converted by the attribute type.
+ * `default` is saved as it is when the path resolves to null.
+ */
+#[derive(Debug, Clone)]
+pub struct FieldMapping {
+    pub path: JsonPath,
+    pub transforms: Vec<Transform>,
+    pub default: Option<Value>,
+}
+
+impl JsonPath {
+    pub fn parse(path: &str) -> Result<Self, ExpectedError> {
+        let invalid = |msg: &str| ExpectedError::InvalidError(format!("invalid json path! {}, path={}", msg, path));
+        let rest = match path.strip_prefix('$') {
+            Some(rest) if rest.is_empty() || rest.starts_with('.') => rest,
+            _ => return Err(invalid("path must start with '$.'")),
+        };
+        let mut segments = Vec::new();
+        for part in rest.split('.').skip(1) {
+            let mut split = part.split('[');
+            let key = split.next().unwrap_or_default();
+            if !key.is_empty() {
+                segments.push(Segment::Key(String::from(key)));
+            }
+            for index in split {
+                let index = index.strip_suffix(']').and_then(|index| index.parse::<usize>().ok())
+                    .ok_or_else(|| invalid(&format!("invalid index {}", index)))?;
+                segments.push(Segment::Index(index));
+            }
+        }
+        if segments.is_empty() {
+            return Err(invalid("path is empty"));
+        }
+        Ok(JsonPath { source: String::from(path), segments })
+    }
+
+    pub fn resolve(&self, values: &Map<String, Value>) -> Value {
+        let mut value = match self.segments.first() {
+            Some(Segment::Key(key)) => values.get(key),
+            _ => None,
+        };
+        for segment in self.segments.iter().skip(1) {
+            value = match segment {
+                Segment::Key(key) => value.and_then(|value| value.get(key)),
+                Segment::Index(index) => value.and_then(|value| value.get(index)),
+            };
+        }
+        value.cloned().unwrap_or(Value::Null)
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl Transform {
+    pub fn parse(name: &str) -> Result<Self, ExpectedError> {
+        let transform = match name {
+            "hex_to_decimal" => Transform::HexToDecimal,
+            "number_to_string" => Transform::NumberToString,
+            "lowercase" => Transform::Lowercase,
+            "json" => Transform::Json,
+            "wei_to_ether" => Transform::WeiToEther,
+            _ => {
+                let custom_name = opt_to_result(name.strip_prefix("custom:"))
+                    .map_err(|_| ExpectedError::InvalidError(format!("unknown transform! transform={}", name)))?;
+                match CUSTOM_TRANSFORMS.iter().find(|(custom, _)| *custom == custom_name) {
+                    Some((_, function)) => Transform::Custom(*function),
+                    None => return Err(ExpectedError::InvalidError(format!("unknown custom transform! transform={}", name))),
+                }
+            }
+        };
+        Ok(transform)
+    }
+
+    pub fn apply(&self, value: &Value) -> Result<Value, ExpectedError> {
+        let transformed = match self {
+            Transform::HexToDecimal => Value::String(hex_to_decimal(String::from(expect_str(value, "hex_to_decimal")?))?),
+            Transform::NumberToString => match value {
+                Value::Number(number) => Value::String(number.to_string()),
+                _ => return Err(ExpectedError::TypeError(format!("number_to_string needs a number! value={}", value))),
+            },
+            Transform::Lowercase => Value::String(expect_str(value, "lowercase")?.to_lowercase()),
+            Transform::Json => Value::String(value.to_string()),
+            Transform::WeiToEther => Value::String(wei_to_ether(value_to_u256(value)?)),
+            Transform::Custom(function) => function(value)?,
+        };
+        Ok(transformed)
+    }
+}
+
+impl FieldMapping {
+    /*
+     * `path` defaults to the column name at the root of the message.
+     * `transform` is a name or an array of names applied in order.
+     */
+    pub fn from(name: &str, attribute: &Map<String, Value>) -> Result<Self, ExpectedError> {
+        let path = match attribute.get("path") {
+            None => JsonPath::parse(&format!("$.{}", name))?,
+            Some(Value::String(path)) => JsonPath::parse(path)?,
+            Some(path) => return Err(ExpectedError::TypeError(format!("path must be string! path={}", path))),
+        };
+        let transforms = match attribute.get("transform") {
+            None => Vec::new(),
+            Some(Value::String(transform)) => vec![Transform::parse(transform)?],
+            Some(Value::Array(transforms)) => transforms.iter()
+                .map(|transform| Transform::parse(expect_str(transform, "transform")?))
+                .collect::<Result<Vec<Transform>, ExpectedError>>()?,
+            Some(transform) => return Err(ExpectedError::TypeError(format!("transform must be string or array! transform={}", transform))),
+        };
+        Ok(FieldMapping {
+            path,
+            transforms,
+            default: attribute.get("default").cloned(),
+        })
+    }
+
+    pub fn map(&self, values: &Map<String, Value>) -> Result<Value, ExpectedError> {
+        let value = self.path.resolve(values);
+        if value.is_null() {
+            return Ok(self.default.clone().unwrap_or(Value::Null));
+        }
+        self.transforms.iter().try_fold(value, |value, transform| transform.apply(&value))
+            .map_err(|err| ExpectedError::InvalidError(format!("{} path={}", err, self.path.source())))
+    }
+}
+
+fn expect_str<'a>(value: &'a Value, transform: &str) -> Result<&'a str, ExpectedError> {
+    opt_to_result(value.as_str()).map_err(|_| ExpectedError::TypeError(format!("{} needs a string! value={}", transform, value)))
+}
+
+/*
+ * Formats wei as a decimal ether amount without trailing zeros, e.g. 1500000000000000000 -> 1.5.
+ */
+fn wei_to_ether(wei: U256) -> String {
+    let digits = format!("{:0>width$}", wei.to_string(), width = WEI_DECIMALS + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - WEI_DECIMALS);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        String::from(integer)
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
+fn custom_topic_to_address(value: &Value) -> Result<Value, ExpectedError> {
+    let address = topic_to_address(expect_str(value, "topic_to_address")?)?;
+    Ok(Value::String(address_to_hex(&address)))
+}
+
+#[cfg(test)]
+mod mapping_test {
+    use serde_json::{json, Map, Value};
+
+    use crate::types::mapping::{FieldMapping, JsonPath, Transform};
+
+    fn example_message() -> Map<String, Value> {
+        json!({
+            "hash": "0xABCDEF",
+            "index": 3,
+            "value": "0x14d1120d7b160000",
+            "receipt": { "index": 7, "status": "0x1" },
+            "topics": [
+                "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                "0x000000000000000000000000deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+            ]
+        }).as_object().unwrap().clone()
+    }
+
+    fn map(attribute: Value) -> Value {
+        FieldMapping::from("hash", attribute.as_object().unwrap()).unwrap().map(&example_message()).unwrap()
+    }
+
+    #[test]
+    fn json_path_test() {
+        let message = example_message();
+        assert_eq!(JsonPath::parse("$.index").unwrap().resolve(&message), json!(3));
+        assert_eq!(JsonPath::parse("$.receipt.index").unwrap().resolve(&message), json!(7));
+        assert_eq!(JsonPath::parse("$.topics[1]").unwrap().resolve(&message), json!("0x000000000000000000000000deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+        assert_eq!(JsonPath::parse("$.status").unwrap().resolve(&message), Value::Null);
+        assert!(JsonPath::parse("receipt.index").is_err());
+        assert!(JsonPath::parse("$").is_err());
+        assert!(JsonPath::parse("$.topics[a]").is_err());
+    }
+
+    #[test]
+    fn transform_test() {
+        assert_eq!(Transform::parse("hex_to_decimal").unwrap().apply(&json!("0x16345785d8a0000")).unwrap(), json!("100000000000000000"));
+        assert_eq!(Transform::parse("number_to_string").unwrap().apply(&json!(21000)).unwrap(), json!("21000"));
+        assert_eq!(Transform::parse("lowercase").unwrap().apply(&json!("0xABCD")).unwrap(), json!("0xabcd"));
+        assert_eq!(Transform::parse("json").unwrap().apply(&json!(["a", "b"])).unwrap(), json!("[\"a\",\"b\"]"));
+        assert_eq!(Transform::parse("wei_to_ether").unwrap().apply(&json!("1500000000000000000")).unwrap(), json!("1.5"));
+        assert_eq!(Transform::parse("wei_to_ether").unwrap().apply(&json!(1)).unwrap(), json!("0.000000000000000001"));
+        assert_eq!(Transform::parse("wei_to_ether").unwrap().apply(&json!("0x0")).unwrap(), json!("0"));
+        assert!(Transform::parse("number_to_string").unwrap().apply(&json!("21000")).is_err());
+        assert!(Transform::parse("hex_to_decimal").unwrap().apply(&json!("pending")).is_err());
+        assert!(Transform::parse("unknown").is_err());
+        assert!(Transform::parse("custom:unknown").is_err());
+    }
+
+    #[test]
+    fn field_mapping_test() {
+        assert_eq!(map(json!({ "type": "hash" })), json!("0xABCDEF"));
+        assert_eq!(map(json!({ "type": "uint64", "path": "$.receipt.index" })), json!(7));
+        assert_eq!(map(json!({ "type": "string", "path": "$.receipt.status", "transform": "hex_to_decimal" })), json!("1"));
+        assert_eq!(map(json!({ "type": "string", "path": "$.value", "transform": [ "hex_to_decimal", "wei_to_ether" ] })), json!("1.5"));
+        assert_eq!(map(json!({ "type": "address", "path": "$.topics[1]", "transform": "custom:topic_to_address" })), json!("0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+        assert_eq!(map(json!({ "type": "uint64", "path": "$.status", "default": 0 })), json!(0));
+        assert_eq!(map(json!({ "type": "uint64", "path": "$.status" })), Value::Null);
+    }
+
+    #[test]
+    fn field_mapping_fail_test() {
+        assert!(FieldMapping::from("hash", json!({ "path": 1 }).as_object().unwrap()).is_err());
+        assert!(FieldMapping::from("hash", json!({ "transform": "unknown" }).as_object().unwrap()).is_err());
+        let mapping = FieldMapping::from("hash", json!({ "path": "$.index", "transform": "lowercase" }).as_object().unwrap()).unwrap();
+        let err = mapping.map(&example_message()).unwrap_err().to_string();
+        assert!(err.contains("path=$.index"));
+    }
+}
@@ -0,0 +1,330 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::types::postgres::{Attribute, PostgresSchema};
+
+/*
+ * PostgreSQL truncates identifiers longer than 63 bytes, so index and constraint names are compared truncated.
+ */
+const MAX_IDENTIFIER_LENGTH: usize = 63;
+
+pub const MIGRATION_TABLE: &str = "bleu_schema_migrations";
+
+/*
+ * Converts the hex or decimal text saved before the column had a numeric type.
+ */
+pub const CREATE_HEX_TO_NUMERIC: &str = "CREATE OR REPLACE FUNCTION bleu_hex_to_numeric(value text) RETURNS numeric AS $$
+DECLARE
+    result numeric := 0;
+BEGIN
+    IF value IS NULL OR value = '' THEN
+        RETURN NULL;
+    END IF;
+    IF lower(value) NOT LIKE '0x%' THEN
+        RETURN value::numeric;
+    END IF;
+    FOR i IN 3..length(value) LOOP
+        result := result * 16 + ('x' || lpad(substr(value, i, 1), 8, '0'))::bit(32)::int;
+    END LOOP;
+    RETURN result;
+END;
+$$ LANGUAGE plpgsql IMMUTABLE";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnState {
+    pub data_type: String,
+    pub max_length: Option<u32>,
+    pub nullable: bool,
+}
+
+/*
+ * The current state of a table read from `information_schema` and `pg_indexes`.
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableState {
+    pub columns: HashMap<String, ColumnState>,
+    pub indexes: HashSet<String>,
+    pub constraints: HashSet<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MigrationPlan {
+    pub schema_name: String,
+    pub statements: Vec<String>,
+    pub destructive: Vec<String>,
+}
+
+impl MigrationPlan {
+    /*
+     * Plans the DDL that brings the table to the schema. `table` is None when the table does not exist.
+     * Dropping a column, changing its type (except widening a varchar) and setting NOT NULL on it are destructive.
+     * Indexes and constraints that are not in the schema are left as they are.
+     * The namespace of a new table is created with it.
+     */
+    pub fn new(schema: &PostgresSchema, table: Option<&TableState>) -> Self {
+        let mut plan = MigrationPlan {
+            schema_name: schema.schema_name.clone(),
+            ..Default::default()
+        };
+        let table = match table {
+            None => {
+                if let Some(namespace) = &schema.namespace {
+                    plan.statements.push(format!("CREATE SCHEMA IF NOT EXISTS {}", namespace));
+                }
+                plan.statements.push(schema.create_table.clone());
+                plan.statements.extend(schema.create_index.iter().cloned());
+                return plan;
+            }
+            Some(table) => table,
+        };
+
+        let table_name = &schema.schema_name;
+        for attribute in schema.attributes.iter() {
+            match table.columns.get(&attribute.name) {
+                None => plan.statements.push(format!("ALTER TABLE {} ADD COLUMN {}", table_name, attribute.column_definition())),
+                Some(column) => {
+                    if column.data_type != attribute.data_type() || column.max_length != attribute.max_length() {
+                        let statement = format!("ALTER TABLE {} ALTER COLUMN {} TYPE {} USING {}", table_name, attribute.name, attribute.column_type(), Self::cast(attribute));
+                        if Self::is_widening(column, attribute.data_type(), attribute.max_length()) {
+                            plan.statements.push(statement);
+                        } else {
+                            plan.push_destructive(statement);
+                        }
+                    }
+                    if column.nullable && !attribute.is_nullable() {
+                        plan.push_destructive(format!("ALTER TABLE {} ALTER COLUMN {} SET NOT NULL", table_name, attribute.name));
+                    } else if !column.nullable && attribute.is_nullable() {
+                        plan.statements.push(format!("ALTER TABLE {} ALTER COLUMN {} DROP NOT NULL", table_name, attribute.name));
+                    }
+                }
+            }
+        }
+
+        let id_column = schema.id_column();
+        let mut dropped: Vec<&String> = table.columns.keys()
+            .filter(|column| **column != id_column && !schema.attributes.iter().any(|attribute| &attribute.name == *column))
+            .collect();
+        dropped.sort();
+        for column in dropped {
+            plan.push_destructive(format!("ALTER TABLE {} DROP COLUMN {}", table_name, column));
+        }
+
+        for unique_vec in schema.uniques.iter() {
+            let unique_name = schema.unique_name(unique_vec);
+            if !table.constraints.contains(&Self::identifier(&unique_name)) {
+                plan.statements.push(format!("ALTER TABLE {} ADD CONSTRAINT {} UNIQUE ({})", table_name, unique_name, unique_vec.join(", ")));
+            }
+        }
+        for index_vec in schema.indexes.iter() {
+            let index_name = schema.index_name(index_vec);
+            if !table.indexes.contains(&Self::identifier(&index_name)) {
+                plan.statements.push(schema.index_query(index_vec));
+            }
+        }
+        plan
+    }
+
+    fn push_destructive(&mut self, statement: String) {
+        self.statements.push(statement.clone());
+        self.destructive.push(statement);
+    }
+
+    /*
+     * Removing or raising the length limit of a varchar column keeps every existing value.
+     */
+    fn is_widening(column: &ColumnState, data_type: String, max_length: Option<u32>) -> bool {
+        if column.data_type != data_type {
+            return false;
+        }
+        match (column.max_length, max_length) {
+            (Some(_), None) => true,
+            (Some(from), Some(to)) => from <= to,
+            _ => false,
+        }
+    }
+
+    fn cast(attribute: &Attribute) -> String {
+        match attribute.get_type().as_str() {
+            "uint64" | "timestamp" | "uint256" => format!("bleu_hex_to_numeric({}::text)::{}", attribute.name, attribute.column_type()),
+            "address" | "hash" | "bytes" => format!("lower({})::{}", attribute.name, attribute.column_type()),
+            _ => format!("{}::{}", attribute.name, attribute.column_type()),
+        }
+    }
+
+    fn identifier(name: &str) -> String {
+        name.chars().take(MAX_IDENTIFIER_LENGTH).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    pub fn is_destructive(&self) -> bool {
+        !self.destructive.is_empty()
+    }
+
+    /*
+     * The planned DDL as a sql script, with destructive statements marked.
+     */
+    pub fn to_sql(&self) -> String {
+        let mut lines = vec![format!("-- {}", self.schema_name)];
+        for statement in self.statements.iter() {
+            if self.destructive.contains(statement) {
+                lines.push(String::from("-- destructive"));
+            }
+            lines.push(format!("{};", statement));
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod migration_test {
+    use std::collections::{HashMap, HashSet};
+
+    use serde_json::json;
+
+    use crate::types::migration::{ColumnState, MigrationPlan, TableState};
+    use crate::types::postgres::PostgresSchema;
+
+    fn example_schema() -> PostgresSchema {
+        let values = json!({
+            "attributes": {
+                "block_number": { "type": "integer" },
+                "hash": { "type": "string", "maxLength": 66 },
+                "memo": { "type": [ "string", "null" ] }
+            },
+            "indexes": [ [ "block_number" ] ],
+            "uniques": [ [ "hash" ] ]
+        });
+        PostgresSchema::from(String::from("example_blocks"), &values).unwrap()
+    }
+
+    fn column(data_type: &str, max_length: Option<u32>, nullable: bool) -> ColumnState {
+        ColumnState {
+            data_type: String::from(data_type),
+            max_length,
+            nullable,
+        }
+    }
+
+    fn example_table() -> TableState {
+        let mut columns = HashMap::new();
+        columns.insert(String::from("example_blocks_id"), column("bigint", None, false));
+        columns.insert(String::from("block_number"), column("bigint", None, false));
+        columns.insert(String::from("hash"), column("character varying", Some(66), false));
+        columns.insert(String::from("memo"), column("character varying", None, true));
+        TableState {
+            columns,
+            indexes: HashSet::from([String::from("example_blocks_pk"), String::from("example_blocks_hash_un"), String::from("example_blocks_block_number_idx")]),
+            constraints: HashSet::from([String::from("example_blocks_hash_un")]),
+        }
+    }
+
+    #[test]
+    fn create_plan_test() {
+        let schema = example_schema();
+        let plan = MigrationPlan::new(&schema, None);
+        assert_eq!(plan.statements.len(), 2);
+        assert_eq!(plan.statements[0], schema.create_table);
+        assert_eq!(plan.statements[1], "CREATE INDEX example_blocks_block_number_idx ON example_blocks USING btree (block_number)");
+        assert!(!plan.is_destructive());
+    }
+
+    #[test]
+    fn namespace_plan_test() {
+        let schema_file = json!({
+            "$namespace": "goerli",
+            "example_blocks": {
+                "attributes": { "block_number": { "type": "integer" } },
+                "indexes": [ [ "block_number" ] ],
+                "uniques": []
+            }
+        });
+        let schema = PostgresSchema::from_file(&schema_file).unwrap().remove(0);
+        let plan = MigrationPlan::new(&schema, None);
+        assert_eq!(plan.statements[0], "CREATE SCHEMA IF NOT EXISTS goerli");
+        assert_eq!(plan.statements[2], "CREATE INDEX example_blocks_block_number_idx ON goerli.example_blocks USING btree (block_number)");
+
+        let mut table = TableState::default();
+        table.columns.insert(String::from("example_blocks_id"), column("bigint", None, false));
+        table.columns.insert(String::from("block_number"), column("bigint", None, false));
+        table.indexes.insert(String::from("example_blocks_block_number_idx"));
+        assert!(MigrationPlan::new(&schema, Some(&table)).is_empty());
+    }
+
+    #[test]
+    fn empty_plan_test() {
+        let plan = MigrationPlan::new(&example_schema(), Some(&example_table()));
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn additive_plan_test() {
+        let mut table = example_table();
+        table.columns.remove("memo");
+        table.indexes.clear();
+        table.constraints.clear();
+        table.columns.insert(String::from("hash"), column("character varying", Some(42), false));
+        let plan = MigrationPlan::new(&example_schema(), Some(&table));
+        assert_eq!(plan.statements, vec![
+            "ALTER TABLE example_blocks ALTER COLUMN hash TYPE varchar(66) USING hash::varchar(66)",
+            "ALTER TABLE example_blocks ADD COLUMN memo varchar NULL",
+            "ALTER TABLE example_blocks ADD CONSTRAINT example_blocks_hash_un UNIQUE (hash)",
+            "CREATE INDEX example_blocks_block_number_idx ON example_blocks USING btree (block_number)",
+        ]);
+        assert!(!plan.is_destructive());
+    }
+
+    #[test]
+    fn destructive_plan_test() {
+        let mut table = example_table();
+        table.columns.insert(String::from("block_number"), column("character varying", None, true));
+        table.columns.insert(String::from("hash"), column("character varying", None, false));
+        table.columns.insert(String::from("removed"), column("boolean", None, true));
+        let plan = MigrationPlan::new(&example_schema(), Some(&table));
+        assert_eq!(plan.destructive, vec![
+            "ALTER TABLE example_blocks ALTER COLUMN block_number TYPE bigint USING block_number::bigint",
+            "ALTER TABLE example_blocks ALTER COLUMN block_number SET NOT NULL",
+            "ALTER TABLE example_blocks ALTER COLUMN hash TYPE varchar(66) USING hash::varchar(66)",
+            "ALTER TABLE example_blocks DROP COLUMN removed",
+        ]);
+        assert!(plan.to_sql().contains("-- destructive\nALTER TABLE example_blocks DROP COLUMN removed;"));
+    }
+
+    #[test]
+    fn identifier_test() {
+        let values = json!({
+            "attributes": { "very_long_column_name_for_identifier": { "type": "string" } },
+            "indexes": [ [ "very_long_column_name_for_identifier" ] ],
+            "uniques": []
+        });
+        let schema = PostgresSchema::from(String::from("example_long_table_name"), &values).unwrap();
+        let mut table = TableState::default();
+        table.columns.insert(String::from("very_long_column_name_for_identifier"), column("character varying", None, false));
+        table.indexes.insert(String::from("example_long_table_name_very_long_column_name_for_identifier_id"));
+        assert!(MigrationPlan::new(&schema, Some(&table)).is_empty());
+    }
+
+    #[test]
+    fn numeric_cast_test() {
+        let values = json!({
+            "attributes": {
+                "block_number": { "type": "uint64" },
+                "miner": { "type": [ "address", "null" ] },
+                "value": { "type": "uint256" }
+            },
+            "indexes": [],
+            "uniques": []
+        });
+        let schema = PostgresSchema::from(String::from("example_txs"), &values).unwrap();
+        let mut table = TableState::default();
+        table.columns.insert(String::from("block_number"), column("character varying", None, false));
+        table.columns.insert(String::from("miner"), column("character varying", None, true));
+        table.columns.insert(String::from("value"), column("numeric", None, false));
+        let plan = MigrationPlan::new(&schema, Some(&table));
+        assert_eq!(plan.destructive, vec![
+            "ALTER TABLE example_txs ALTER COLUMN block_number TYPE bigint USING bleu_hex_to_numeric(block_number::text)::bigint",
+            "ALTER TABLE example_txs ALTER COLUMN miner TYPE varchar(42) USING lower(miner)::varchar(42)",
+        ]);
+    }
+}
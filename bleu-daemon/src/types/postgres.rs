@@ -1,24 +1,34 @@
 use std::fmt::Debug;
 
 use jsonrpc_core::Value;
+use serde_json::Map;
 
 use crate::error::error::ExpectedError;
 use crate::libs::postgres::convert_type;
+use crate::libs::opt::opt_to_result;
 use crate::libs::serde::{get_array, get_object};
+use crate::types::mapping::FieldMapping;
+
+const SCHEMA_FILE_OPTIONS: [&str; 2] = ["$namespace", "$tablePrefix"];
 
 #[derive(Clone, Debug)]
 pub struct PostgresSchema {
+    pub name: String,
     pub schema_name: String,
+    pub table_name: String,
+    pub namespace: Option<String>,
     pub attributes: Vec<Attribute>,
     pub create_table: String,
     pub create_index: Vec<String>,
     pub insert_query: String,
+    pub uniques: Vec<Vec<String>>,
+    pub indexes: Vec<Vec<String>>,
 }
 
 #[derive(Clone, Debug)]
 pub struct Attribute {
     pub name: String,
-    pub description: String,
+    pub mapping: FieldMapping,
     _type: String,
     max_length: Option<u32>,
     nullable: bool,
@@ -26,6 +36,37 @@ pub struct Attribute {
 
 impl PostgresSchema {
     pub fn from(schema_name: String, values: &Value) -> Result<PostgresSchema, ExpectedError> {
+        Self::build(None, "", schema_name, values)
+    }
+
+    /*
+     * A schema file maps table names to schemas. `$namespace` creates its tables in that postgres schema,
+     * and `$tablePrefix` is prepended to their names, while `name` keeps the table name written in the file.
+     */
+    pub fn from_file(values: &Value) -> Result<Vec<PostgresSchema>, ExpectedError> {
+        let map = opt_to_result(values.as_object()).map_err(|_| ExpectedError::TypeError(String::from("schema file is not object type!")))?;
+        if let Some(key) = map.keys().find(|key| key.starts_with('$') && !SCHEMA_FILE_OPTIONS.contains(&key.as_str())) {
+            return Err(ExpectedError::InvalidError(format!("unknown schema file option! key={}", key)));
+        }
+        let namespace = Self::identifier_option(map, "$namespace")?;
+        let table_prefix = Self::identifier_option(map, "$tablePrefix")?.unwrap_or_default();
+
+        let mut schemas = Vec::new();
+        for (table_name, values) in map.iter().filter(|(key, _)| !key.starts_with('$')) {
+            schemas.push(Self::build(namespace.clone(), &table_prefix, table_name.clone(), values)?);
+        }
+        Ok(schemas)
+    }
+
+    fn identifier_option(map: &Map<String, Value>, key: &str) -> Result<Option<String>, ExpectedError> {
+        match map.get(key) {
+            None => Ok(None),
+            Some(Value::String(identifier)) if is_identifier(identifier) => Ok(Some(identifier.clone())),
+            Some(identifier) => Err(ExpectedError::InvalidError(format!("{} must be a lowercase identifier! value={}", key, identifier))),
+        }
+    }
+
+    fn build(namespace: Option<String>, table_prefix: &str, name: String, values: &Value) -> Result<PostgresSchema, ExpectedError> {
         if !values.is_object() {
             return Err(ExpectedError::TypeError(String::from("input values is not object type!")));
         }
@@ -39,10 +80,7 @@ impl PostgresSchema {
                 None => None,
                 Some(size) => Some(size.as_u64().unwrap() as u32)
             };
-            let description = match parsed_value.get("description") {
-                None => key.clone(),
-                Some(description) => String::from(description.as_str().unwrap())
-            };
+            let mapping = FieldMapping::from(key, parsed_value)?;
             let type_value = match parsed_value.get("type") {
                 None => return Err(ExpectedError::NoneError(String::from("schema attribute must include type!"))),
                 Some(type_value) => type_value
@@ -62,74 +100,104 @@ impl PostgresSchema {
                 _ => return Err(ExpectedError::TypeError(String::from("type only can be string or array!")))
             };
 
+            let max_length = size.or_else(|| Self::fixed_length(&_type));
             let attribute = Attribute {
                 name: key.clone(),
-                description,
+                mapping,
                 _type,
-                max_length: size,
+                max_length,
                 nullable,
             };
             attributes.push(attribute);
         }
 
-        let uniques = get_array(map, "uniques")?;
-        let indexes = get_array(map, "indexes")?;
-        let create_table = Self::create_table(schema_name.clone(), &attributes, uniques);
-        let create_index = Self::create_index(schema_name.clone(), indexes);
-        let insert_query = Self::insert_query(schema_name.clone(), &attributes);
+        let uniques = Self::column_lists(get_array(map, "uniques")?);
+        let indexes = Self::column_lists(get_array(map, "indexes")?);
+        let table_name = format!("{}{}", table_prefix, name);
+        let schema_name = match &namespace {
+            Some(namespace) => format!("{}.{}", namespace, table_name),
+            None => table_name.clone(),
+        };
 
-        Ok(PostgresSchema {
-            schema_name: schema_name.clone(),
+        let mut schema = PostgresSchema {
+            name,
+            schema_name,
+            table_name,
+            namespace,
             attributes,
-            create_table,
-            create_index,
-            insert_query,
-        })
+            create_table: String::new(),
+            create_index: Vec::new(),
+            insert_query: String::new(),
+            uniques,
+            indexes,
+        };
+        schema.create_table = schema.create_table_query();
+        schema.create_index = schema.indexes.iter().map(|index_vec| schema.index_query(index_vec)).collect();
+        schema.insert_query = schema.insert_values_query();
+        Ok(schema)
     }
 
-    fn create_table(schema_name: String, attributes: &Vec<Attribute>, uniques: &Vec<Value>) -> String {
+    /*
+     * `address` and `hash` are saved as lowercase 0x prefixed hex, so their columns have a fixed length.
+     */
+    fn fixed_length(_type: &str) -> Option<u32> {
+        match _type {
+            "address" => Some(42),
+            "hash" => Some(66),
+            _ => None,
+        }
+    }
+
+    fn column_lists(raw_lists: &Vec<Value>) -> Vec<Vec<String>> {
+        raw_lists.iter().map(|raw_keys| raw_keys.as_array().unwrap().iter().map(|v| { String::from(v.as_str().unwrap()) }).collect()).collect()
+    }
+
+    /*
+     * Tables are referenced by `schema_name`, which includes the namespace,
+     * while the id column and constraint names only use the unqualified `table_name`.
+     */
+    fn create_table_query(&self) -> String {
         let mut query_line: Vec<String> = Vec::new();
-        query_line.push(format!("{}_id serial8", schema_name));
-        for attribute in attributes.iter() {
-            let converted_type = convert_type(attribute._type.clone()).unwrap();
-            if attribute.max_length.is_none() {
-                query_line.push(format!("{} {} {}", attribute.name, converted_type, Self::null_or_not(attribute.nullable)));
-            } else {
-                query_line.push(format!("{} {}({}) {}", attribute.name, converted_type, attribute.max_length.unwrap(), Self::null_or_not(attribute.nullable)));
-            }
+        query_line.push(format!("{} serial8", self.id_column()));
+        for attribute in self.attributes.iter() {
+            query_line.push(attribute.column_definition());
         }
-        query_line.push(format!("CONSTRAINT {schema_name}_pk PRIMARY KEY ({schema_name}_id)", schema_name = schema_name));
+        query_line.push(format!("CONSTRAINT {}_pk PRIMARY KEY ({})", self.table_name, self.id_column()));
 
-        for raw_keys in uniques.iter() {
-            let unique_vec: Vec<String> = raw_keys.as_array().unwrap().iter().map(|v| { String::from(v.as_str().unwrap()) }).collect();
-            let unique_name = format!("{}_{}_un", schema_name, unique_vec.join("_"));
-            query_line.push(format!("CONSTRAINT {} UNIQUE ({})", unique_name, unique_vec.join(", ")));
+        for unique_vec in self.uniques.iter() {
+            query_line.push(format!("CONSTRAINT {} UNIQUE ({})", self.unique_name(unique_vec), unique_vec.join(", ")));
         }
         let full_query = query_line.join(", ");
-        format!("CREATE TABLE {} ({})", schema_name, full_query)
+        format!("CREATE TABLE {} ({})", self.schema_name, full_query)
     }
 
-    fn create_index(schema_name: String, indexes: &Vec<Value>) -> Vec<String> {
-        let mut index_query = Vec::new();
-        for raw_keys in indexes.iter() {
-            let index_vec: Vec<String> = raw_keys.as_array().unwrap().iter().map(|v| { String::from(v.as_str().unwrap()) }).collect();
-            let index_name = format!("{}_{}_idx", schema_name, index_vec.join("_"));
-            index_query.push(format!("CREATE INDEX {} ON {} USING btree ({})", index_name, schema_name, index_vec.join(", ")));
-        }
-        index_query
+    pub fn index_query(&self, index_vec: &Vec<String>) -> String {
+        format!("CREATE INDEX {} ON {} USING btree ({})", self.index_name(index_vec), self.schema_name, index_vec.join(", "))
+    }
+
+    pub fn unique_name(&self, unique_vec: &Vec<String>) -> String {
+        format!("{}_{}_un", self.table_name, unique_vec.join("_"))
+    }
+
+    pub fn index_name(&self, index_vec: &Vec<String>) -> String {
+        format!("{}_{}_idx", self.table_name, index_vec.join("_"))
+    }
+
+    pub fn id_column(&self) -> String {
+        format!("{}_id", self.table_name)
     }
 
-    fn insert_query(schema_name: String, attributes: &Vec<Attribute>) -> String {
+    fn insert_values_query(&self) -> String {
         let mut column_vec = Vec::new();
         let mut value_vec = Vec::new();
-        for attribute in attributes.iter() {
+        for attribute in self.attributes.iter() {
             column_vec.push(attribute.name.clone());
-            value_vec.push(format!("${}$", attribute.description.clone()));
+            value_vec.push(format!("${}$", attribute.name.clone()));
         }
         let columns = column_vec.join(", ");
         let values = value_vec.join(", ");
 
-        format!("INSERT INTO {} ({}) VALUES ({})", schema_name, columns, values)
+        format!("INSERT INTO {} ({}) VALUES ({})", self.schema_name, columns, values)
     }
 
     fn null_or_not(nullable: bool) -> String {
@@ -141,12 +209,59 @@ impl PostgresSchema {
     }
 }
 
+fn is_identifier(identifier: &str) -> bool {
+    let mut chars = identifier.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+impl Attribute {
+    /*
+     * `max_length` only applies to `varchar` columns, so it is dropped for the other types.
+     */
+    pub fn get_type(&self) -> String {
+        self._type.clone()
+    }
+
+    pub fn column_type(&self) -> String {
+        let converted_type = convert_type(self._type.clone()).unwrap();
+        match self.max_length {
+            Some(max_length) if converted_type == "varchar" => format!("{}({})", converted_type, max_length),
+            _ => converted_type,
+        }
+    }
+
+    pub fn column_definition(&self) -> String {
+        format!("{} {} {}", self.name, self.column_type(), PostgresSchema::null_or_not(self.nullable))
+    }
+
+    /*
+     * The `data_type` of the column as reported by `information_schema.columns`.
+     */
+    pub fn data_type(&self) -> String {
+        let converted_type = convert_type(self._type.clone()).unwrap();
+        match converted_type.as_str() {
+            "varchar" => String::from("character varying"),
+            _ if converted_type.starts_with("numeric") => String::from("numeric"),
+            _ => converted_type,
+        }
+    }
+
+    pub fn max_length(&self) -> Option<u32> {
+        if self.data_type() == "character varying" { self.max_length } else { None }
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        self.nullable
+    }
+}
+
 #[cfg(test)]
 mod postgres_test {
     use std::collections::HashMap;
     use std::fs;
 
-    use serde_json::Value;
+    use serde_json::{json, Value};
 
     use crate::types::postgres::PostgresSchema;
 
@@ -177,6 +292,39 @@ mod postgres_test {
         }
         let selected_schema = result_map.get("eth_blocks").unwrap().to_owned();
         let created_insert_query = selected_schema.insert_query;
-        assert_eq!(created_insert_query, "INSERT INTO eth_blocks (base_fee_per_gas, block_number, block_size, block_timestamp, difficulty, extra_data, gas_limit, gas_used, hash, is_forked, logs_bloom, miner, mix_hash, nonce, parent_hash, receipts_root, sha3_uncles, state_root, total_difficulty) VALUES ($baseFeePerGas$, $number$, $size$, $timestamp$, $difficulty$, $extraData$, $gasLimit$, $gasUsed$, $hash$, $is_forked$, $logsBloom$, $miner$, $mixHash$, $nonce$, $parentHash$, $receiptsRoot$, $sha3Uncles$, $stateRoot$, $totalDifficulty$)");
+        assert_eq!(created_insert_query, "INSERT INTO eth_blocks (base_fee_per_gas, block_number, block_size, block_timestamp, difficulty, extra_data, gas_limit, gas_used, hash, is_forked, logs_bloom, miner, mix_hash, nonce, parent_hash, receipts_root, sha3_uncles, state_root, total_difficulty) VALUES ($base_fee_per_gas$, $block_number$, $block_size$, $block_timestamp$, $difficulty$, $extra_data$, $gas_limit$, $gas_used$, $hash$, $is_forked$, $logs_bloom$, $miner$, $mix_hash$, $nonce$, $parent_hash$, $receipts_root$, $sha3_uncles$, $state_root$, $total_difficulty$)");
+    }
+
+    #[test]
+    fn schema_file_test() {
+        let schema_file = json!({
+            "$namespace": "goerli",
+            "$tablePrefix": "op_",
+            "blocks": {
+                "attributes": {
+                    "block_number": { "type": "uint64" },
+                    "hash": { "type": "hash" }
+                },
+                "uniques": [["hash"]],
+                "indexes": [["block_number"]]
+            }
+        });
+        let schemas = PostgresSchema::from_file(&schema_file).unwrap();
+        assert_eq!(schemas.len(), 1);
+        let schema = schemas.first().unwrap();
+        assert_eq!(schema.name, "blocks");
+        assert_eq!(schema.schema_name, "goerli.op_blocks");
+        assert_eq!(schema.id_column(), "op_blocks_id");
+        assert_eq!(schema.create_table, "CREATE TABLE goerli.op_blocks (op_blocks_id serial8, block_number bigint NOT NULL, hash varchar(66) NOT NULL, CONSTRAINT op_blocks_pk PRIMARY KEY (op_blocks_id), CONSTRAINT op_blocks_hash_un UNIQUE (hash))");
+        assert_eq!(schema.create_index, vec!["CREATE INDEX op_blocks_block_number_idx ON goerli.op_blocks USING btree (block_number)"]);
+        assert!(schema.insert_query.starts_with("INSERT INTO goerli.op_blocks "));
+    }
+
+    #[test]
+    fn schema_file_fail_test() {
+        assert!(PostgresSchema::from_file(&json!([])).is_err());
+        assert!(PostgresSchema::from_file(&json!({ "$namespace": "Goerli" })).is_err());
+        assert!(PostgresSchema::from_file(&json!({ "$tablePrefix": "op-" })).is_err());
+        assert!(PostgresSchema::from_file(&json!({ "$prefix": "op_" })).is_err());
     }
-}
\ No newline at end of file
+}
@@ -3,9 +3,12 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Map, Value};
 
 use crate::enumeration;
+use crate::error::error::ExpectedError;
+use crate::libs;
 use crate::libs::serde::{get_str, get_string, get_string_vec, get_u64};
 use crate::plugin::rocks::{RocksMethod, RocksMsg};
 use crate::types::enumeration::Enumeration;
+use crate::types::filter::Filter;
 use crate::types::subscribe::SubscribeStatus::Working;
 
 #[derive(Debug, Clone)]
@@ -17,13 +20,51 @@ pub struct SubscribeEvent {
     pub curr_idx: u64,
     pub end_points: Vec<String>,
     pub end_point_idx: u16,
-    pub filter: String,
+    pub filter: Filter,
     pub status: SubscribeStatus,
+    pub progress: SubscribeProgress,
+}
+
+/*
+ * Kept with the task state so `get_task_status` can report the ingest rate and the last success or error.
+ * The ingest rate is refreshed once per `RATE_WINDOW` seconds.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SubscribeProgress {
+    pub ingest_rate: f64,
+    pub last_success_at: u64,
+    pub last_err_msg: String,
+    pub last_err_at: u64,
+    #[serde(skip)]
+    rate_window_idx: u64,
+    #[serde(skip)]
+    rate_window_at: u64,
+}
+
+const RATE_WINDOW: u64 = 60;
+
+impl SubscribeProgress {
+    pub fn success(&mut self, curr_idx: u64, now: u64) {
+        self.last_success_at = now;
+        if self.rate_window_at == 0 {
+            self.rate_window_idx = curr_idx;
+            self.rate_window_at = now;
+        } else if now - self.rate_window_at >= RATE_WINDOW {
+            self.ingest_rate = (curr_idx - self.rate_window_idx) as f64 / (now - self.rate_window_at) as f64;
+            self.rate_window_idx = curr_idx;
+            self.rate_window_at = now;
+        }
+    }
+
+    pub fn error(&mut self, err_msg: String, now: u64) {
+        self.last_err_msg = err_msg;
+        self.last_err_at = now;
+    }
 }
 
 impl SubscribeEvent {
-    pub fn from(params: &Map<String, Value>) -> Self {
-        SubscribeEvent {
+    pub fn from(params: &Map<String, Value>) -> Result<Self, ExpectedError> {
+        Ok(SubscribeEvent {
             task_id: get_string(params, "task_id").unwrap(),
             chain: get_string(params, "chain").unwrap(),
             task: get_string(params, "task").unwrap(),
@@ -31,16 +72,17 @@ impl SubscribeEvent {
             curr_idx: get_u64(params, "curr_idx").unwrap(),
             end_points: get_string_vec(params, "end_points"),
             end_point_idx: get_u64(params, "end_point_idx").unwrap() as u16,
-            filter: get_string(params, "filter").unwrap(),
+            filter: Filter::parse(get_str(params, "filter")?)?,
             status: SubscribeStatus::find(get_str(params, "status").unwrap()).unwrap(),
-        }
+            progress: params.get("progress").and_then(|progress| serde_json::from_value(progress.clone()).ok()).unwrap_or_default(),
+        })
     }
 
-    pub fn load(task_id: String, task: String, chain: String, task_map: &Map<String, Value>) -> Self {
+    pub fn load(task_id: String, task: String, chain: String, task_map: &Map<String, Value>) -> Result<Self, ExpectedError> {
         let start_idx = get_u64(task_map, "start_idx").unwrap();
         let end_points = get_string_vec(task_map, "end_points");
-        let filter = get_string(task_map, "filter").unwrap();
-        SubscribeEvent {
+        let filter = Filter::parse(get_str(task_map, "filter")?)?;
+        Ok(SubscribeEvent {
             task_id,
             chain,
             task,
@@ -50,7 +92,8 @@ impl SubscribeEvent {
             end_point_idx: 0,
             filter,
             status: SubscribeStatus::Working,
-        }
+            progress: SubscribeProgress::default(),
+        })
     }
 
     pub fn is_workable(&self) -> bool {
@@ -63,6 +106,7 @@ impl SubscribeEvent {
         } else {
             self.status = SubscribeStatus::Error;
         }
+        self.progress.error(err_msg.clone(), libs::time::current_timestamp());
         let task = SubscribeTask::from(self, err_msg.clone());
         let _ = rocks_channel.send(RocksMsg::new(RocksMethod::Put, self.task_id.clone(), Value::String(json!(task).to_string())));
     }
@@ -72,16 +116,43 @@ impl SubscribeEvent {
         self.end_points[usize_idx].clone()
     }
 
-    pub fn next_idx(&mut self) { self.curr_idx += 1; }
+    pub fn next_idx(&mut self) {
+        self.curr_idx += 1;
+        self.progress.success(self.curr_idx, libs::time::current_timestamp());
+        libs::metrics::TASK_PROCESSED.with_label_values(&[&self.task]).inc();
+        libs::metrics::TASK_CURRENT_INDEX.with_label_values(&[&self.task]).set(self.curr_idx as i64);
+    }
 
     pub fn get_task_id(&self) -> String { self.task_id.clone() }
 
     pub fn status(&mut self, status: SubscribeStatus) { self.status = status; }
 
-    pub fn get_filter(&self) -> String { self.filter.clone() }
+    pub fn get_filter(&self) -> &Filter { &self.filter }
+
+    /*
+     * Applies `end_points`, `filter` and `start_idx` of the task definition, keeping `curr_idx`, `status` and `progress`.
+     * Returns the names of the changed fields.
+     */
+    pub fn reload(&mut self, definition: SubscribeEvent) -> Vec<String> {
+        let mut changed = Vec::new();
+        if self.end_points != definition.end_points {
+            self.end_points = definition.end_points;
+            self.end_point_idx = 0;
+            changed.push(String::from("end_points"));
+        }
+        if self.filter.source() != definition.filter.source() {
+            self.filter = definition.filter;
+            changed.push(String::from("filter"));
+        }
+        if self.start_idx != definition.start_idx {
+            self.start_idx = definition.start_idx;
+            changed.push(String::from("start_idx"));
+        }
+        changed
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct SubscribeTask {
     pub task_id: String,
     pub chain: String,
@@ -93,6 +164,8 @@ pub struct SubscribeTask {
     pub filter: String,
     pub status: String,
     pub err_msg: String,
+    #[serde(default)]
+    pub progress: SubscribeProgress,
 }
 
 impl SubscribeTask {
@@ -105,15 +178,66 @@ impl SubscribeTask {
             curr_idx: sub_event.curr_idx,
             end_points: sub_event.end_points.clone(),
             end_point_idx: sub_event.end_point_idx,
-            filter: sub_event.filter.clone(),
+            filter: String::from(sub_event.filter.source()),
             status: sub_event.status.value(),
             err_msg,
+            progress: sub_event.progress.clone(),
         }
     }
 
     pub fn get_task_id(&self) -> String {
         self.task_id.clone()
     }
+
+    pub fn active_node(&self) -> Option<String> {
+        self.end_points.get(usize::from(self.end_point_idx)).cloned()
+    }
+}
+
+/*
+ * `head_idx` is the newest index of the upstream, so the task is at the tip when `curr_idx` is `head_idx + 1`.
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskStatus {
+    pub task_id: String,
+    pub task: String,
+    pub status: String,
+    pub curr_idx: u64,
+    pub head_idx: Option<u64>,
+    pub lag: Option<u64>,
+    pub ingest_rate: f64,
+    pub eta_seconds: Option<u64>,
+    pub active_end_point: Option<String>,
+    pub last_err_msg: String,
+    pub last_err_at: Option<u64>,
+    pub last_success_at: Option<u64>,
+}
+
+impl TaskStatus {
+    pub fn new(task: &SubscribeTask, head_idx: Option<u64>) -> Self {
+        let lag = head_idx.map(|head_idx| (head_idx + 1).saturating_sub(task.curr_idx));
+        let ingest_rate = task.progress.ingest_rate;
+        let eta_seconds = match lag {
+            Some(0) => Some(0),
+            Some(lag) if ingest_rate > 0.0 => Some((lag as f64 / ingest_rate).ceil() as u64),
+            _ => None,
+        };
+        let non_zero = |timestamp: u64| if timestamp == 0 { None } else { Some(timestamp) };
+        TaskStatus {
+            task_id: task.task_id.clone(),
+            task: task.task.clone(),
+            status: task.status.clone(),
+            curr_idx: task.curr_idx,
+            head_idx,
+            lag,
+            ingest_rate,
+            eta_seconds,
+            active_end_point: task.active_node(),
+            last_err_msg: task.progress.last_err_msg.clone(),
+            last_err_at: non_zero(task.progress.last_err_at),
+            last_success_at: non_zero(task.progress.last_success_at),
+        }
+    }
 }
 
 pub trait RetryJob {
@@ -124,13 +248,13 @@ pub trait RetryJob {
 }
 
 enumeration!(SubscribeStatus; {Working: "working"}, {Stopped: "stopped"}, {Removed: "removed"}, {Error: "error"});
-enumeration!(TaskMethod; {Start: "start"}, {Stop: "stop"}, {Remove: "remove"});
+enumeration!(TaskMethod; {Start: "start"}, {Stop: "stop"}, {Remove: "remove"}, {Reload: "reload"});
 
 #[cfg(test)]
 mod subscribe_test {
     use serde_json::{json, Map, Value};
 
-    use crate::types::subscribe::SubscribeEvent;
+    use crate::types::subscribe::{SubscribeEvent, SubscribeProgress, SubscribeTask, TaskStatus};
 
     #[test]
     fn subscribe_event_task_id_test() {
@@ -155,4 +279,65 @@ mod subscribe_test {
         let subscribe_event = SubscribeEvent::new("tendermint", &params);
         assert!(subscribe_event.is_workable());
     }
+
+    #[test]
+    fn subscribe_progress_test() {
+        let mut progress = SubscribeProgress::default();
+        progress.success(100, 1000);
+        assert_eq!(progress.ingest_rate, 0.0);
+        progress.success(130, 1030);
+        assert_eq!(progress.ingest_rate, 0.0);
+        progress.success(220, 1060);
+        assert_eq!(progress.ingest_rate, 2.0);
+        assert_eq!(progress.last_success_at, 1060);
+
+        progress.error(String::from("timeout"), 1070);
+        assert_eq!(progress.last_err_msg, "timeout");
+        assert_eq!(progress.last_err_at, 1070);
+    }
+
+    #[test]
+    fn subscribe_event_reload_test() {
+        let definition = |end_points: Value, filter: &str| {
+            let task_map = json!({"start_idx": 0, "end_points": end_points, "filter": filter});
+            SubscribeEvent::load(String::from("task:optimism:l2_block_tx"), String::from("l2_block_tx"), String::from("optimism"), task_map.as_object().unwrap()).unwrap()
+        };
+        let mut sub_event = definition(json!(["http://localhost:8545", "http://localhost:8546"]), "");
+        sub_event.curr_idx = 91;
+        sub_event.end_point_idx = 1;
+
+        assert!(sub_event.reload(definition(json!(["http://localhost:8545", "http://localhost:8546"]), "")).is_empty());
+        let changed = sub_event.reload(definition(json!(["http://localhost:8547"]), "to=0x01"));
+        assert_eq!(changed, vec!["end_points", "filter"]);
+        assert_eq!(sub_event.curr_idx, 91);
+        assert_eq!(sub_event.end_point_idx, 0);
+        assert_eq!(sub_event.active_node(), "http://localhost:8547");
+        assert_eq!(sub_event.get_filter().source(), "to=0x01");
+    }
+
+    #[test]
+    fn task_status_test() {
+        let mut task: SubscribeTask = serde_json::from_value(json!({
+            "task_id": "task:optimism:l2_block_tx",
+            "chain": "optimism",
+            "task": "l2_block_tx",
+            "start_idx": 0,
+            "curr_idx": 91,
+            "end_points": ["http://localhost:8545"],
+            "end_point_idx": 0,
+            "filter": "",
+            "status": "working",
+            "err_msg": ""
+        })).unwrap();
+        let status = TaskStatus::new(&task, Some(100));
+        assert_eq!(status.lag, Some(10));
+        assert_eq!(status.eta_seconds, None);
+        assert_eq!(status.active_end_point, Some(String::from("http://localhost:8545")));
+        assert_eq!(status.last_success_at, None);
+
+        task.progress.ingest_rate = 4.0;
+        assert_eq!(TaskStatus::new(&task, Some(100)).eta_seconds, Some(3));
+        assert_eq!(TaskStatus::new(&task, Some(50)).eta_seconds, Some(0));
+        assert_eq!(TaskStatus::new(&task, None).lag, None);
+    }
 }
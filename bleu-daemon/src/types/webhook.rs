@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::enumeration;
+use crate::error::error::ExpectedError;
+use crate::types::enumeration::Enumeration;
+
+enumeration!(WebhookEventType; {TaskStatus: "task_status"}, {VerificationFailure: "verification_failure"}, {RetryExhausted: "retry_exhausted"});
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    pub event: String,
+    pub task: String,
+    pub msg: String,
+    pub data: Value,
+    pub timestamp: u64,
+}
+
+/*
+ * Loaded from the webhook file. Empty `events` or `tasks` match every event or task,
+ * and an endpoint without `template` receives the event itself.
+ */
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WebhookEndpoint {
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub secret: Option<String>,
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(default)]
+    pub tasks: Vec<String>,
+    #[serde(default)]
+    pub template: Option<Value>,
+}
+
+impl WebhookEndpoint {
+    pub fn load(json_str: &str) -> Result<Vec<Self>, ExpectedError> {
+        let endpoints: Vec<WebhookEndpoint> = serde_json::from_str(json_str)?;
+        for endpoint in endpoints.iter() {
+            if let Some(event) = endpoint.events.iter().find(|event| !WebhookEventType::valid(event)) {
+                return Err(ExpectedError::InvalidError(format!("invalid webhook event! endpoint={}, event={}", endpoint.name, event)));
+            }
+        }
+        Ok(endpoints)
+    }
+
+    pub fn matches(&self, event: &WebhookEvent) -> bool {
+        (self.events.is_empty() || self.events.contains(&event.event))
+            && (self.tasks.is_empty() || self.tasks.contains(&event.task))
+    }
+
+    pub fn payload(&self, event: &WebhookEvent) -> Value {
+        match &self.template {
+            Some(template) => render(template, event),
+            None => serde_json::to_value(event).unwrap_or(Value::Null),
+        }
+    }
+}
+
+/*
+ * A string which is exactly `{{data}}` is replaced by the event data as it is.
+ * Otherwise `{{event}}`, `{{task}}`, `{{msg}}`, `{{timestamp}}` and `{{data.<key>}}` are replaced inside strings.
+ */
+fn render(template: &Value, event: &WebhookEvent) -> Value {
+    match template {
+        Value::String(text) if text == "{{data}}" => event.data.clone(),
+        Value::String(text) => Value::String(render_str(text, event)),
+        Value::Array(values) => Value::Array(values.iter().map(|value| render(value, event)).collect()),
+        Value::Object(map) => Value::Object(map.iter().map(|(key, value)| (key.clone(), render(value, event))).collect::<Map<String, Value>>()),
+        _ => template.clone(),
+    }
+}
+
+fn render_str(text: &str, event: &WebhookEvent) -> String {
+    let mut rendered = text.replace("{{event}}", &event.event)
+        .replace("{{task}}", &event.task)
+        .replace("{{msg}}", &event.msg)
+        .replace("{{timestamp}}", &event.timestamp.to_string());
+    if let Some(data) = event.data.as_object() {
+        for (key, value) in data.iter() {
+            let value = match value {
+                Value::String(value) => value.clone(),
+                _ => value.to_string(),
+            };
+            rendered = rendered.replace(&format!("{{{{data.{}}}}}", key), &value);
+        }
+    }
+    rendered
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookDelivery {
+    pub endpoint: usize,
+    pub event: String,
+    pub body: String,
+    pub attempt: u32,
+    pub next_attempt_at: u64,
+}
+
+impl WebhookDelivery {
+    pub fn new(endpoint: usize, event: String, body: String, now: u64) -> Self {
+        WebhookDelivery {
+            endpoint,
+            event,
+            body,
+            attempt: 0,
+            next_attempt_at: now,
+        }
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        self.next_attempt_at <= now
+    }
+
+    /*
+     * Returns false when no retry is left, otherwise schedules the next attempt.
+     */
+    pub fn retry(&mut self, now: u64, retry_count: u32, backoff: u64) -> bool {
+        if self.attempt >= retry_count {
+            return false;
+        }
+        self.next_attempt_at = next_attempt_at(now, self.attempt, backoff);
+        self.attempt += 1;
+        true
+    }
+}
+
+/*
+ * Attempts are spaced by `backoff * 2^attempt` seconds.
+ */
+pub fn next_attempt_at(now: u64, attempt: u32, backoff: u64) -> u64 {
+    now + backoff.saturating_mul(1u64 << attempt.min(16))
+}
+
+#[cfg(test)]
+mod webhook_test {
+    use serde_json::json;
+
+    use crate::types::webhook::{WebhookDelivery, WebhookEndpoint, WebhookEvent};
+
+    fn example_event() -> WebhookEvent {
+        WebhookEvent {
+            event: String::from("verification_failure"),
+            task: String::from("l2_state_root_verify"),
+            msg: String::from("state root mismatched!"),
+            data: json!({"index": "10", "block_number": 11}),
+            timestamp: 1000,
+        }
+    }
+
+    #[test]
+    fn webhook_endpoint_load_test() {
+        let endpoints = WebhookEndpoint::load(r#"[{"name": "bot", "url": "http://localhost:8080", "events": ["task_status"]}]"#).unwrap();
+        assert_eq!(endpoints[0].name, "bot");
+        assert!(endpoints[0].secret.is_none());
+        assert!(endpoints[0].tasks.is_empty());
+
+        assert!(WebhookEndpoint::load(r#"[{"name": "bot", "url": "http://localhost:8080", "events": ["unknown"]}]"#).is_err());
+    }
+
+    #[test]
+    fn webhook_endpoint_matches_test() {
+        let mut endpoints = WebhookEndpoint::load(r#"[{"name": "all", "url": "http://localhost:8080"}]"#).unwrap();
+        let event = example_event();
+        assert!(endpoints[0].matches(&event));
+
+        endpoints[0].events = vec![String::from("task_status")];
+        assert!(!endpoints[0].matches(&event));
+
+        endpoints[0].events = vec![String::from("verification_failure")];
+        endpoints[0].tasks = vec![String::from("l2_tx_batch_verify")];
+        assert!(!endpoints[0].matches(&event));
+
+        endpoints[0].tasks.push(String::from("l2_state_root_verify"));
+        assert!(endpoints[0].matches(&event));
+    }
+
+    #[test]
+    fn webhook_endpoint_payload_test() {
+        let endpoints = WebhookEndpoint::load(r#"[
+            {"name": "raw", "url": "http://localhost:8080"},
+            {"name": "discord", "url": "http://localhost:8080", "template": {"content": "[{{event}}] {{task}}: {{msg}} (index={{data.index}}, block={{data.block_number}})", "data": "{{data}}", "tts": false}}
+        ]"#).unwrap();
+        let event = example_event();
+
+        assert_eq!(endpoints[0].payload(&event), serde_json::to_value(&event).unwrap());
+        assert_eq!(endpoints[1].payload(&event), json!({
+            "content": "[verification_failure] l2_state_root_verify: state root mismatched! (index=10, block=11)",
+            "data": {"index": "10", "block_number": 11},
+            "tts": false,
+        }));
+    }
+
+    #[test]
+    fn webhook_delivery_retry_test() {
+        let mut delivery = WebhookDelivery::new(0, String::from("verification_failure"), String::from("{}"), 100);
+        assert!(delivery.is_due(100));
+
+        assert!(delivery.retry(100, 3, 2));
+        assert_eq!(delivery.next_attempt_at, 102);
+        assert!(!delivery.is_due(101));
+        assert!(delivery.retry(102, 3, 2));
+        assert_eq!(delivery.next_attempt_at, 106);
+        assert!(delivery.retry(106, 3, 2));
+        assert_eq!(delivery.next_attempt_at, 114);
+        assert!(!delivery.retry(114, 3, 2));
+    }
+}
@@ -0,0 +1,229 @@
+use std::fmt;
+
+use primitive_types::U256;
+use tiny_keccak::{Hasher, Keccak};
+
+pub type Bytes32 = [u8; 32];
+
+#[derive(Debug)]
+pub enum MerkleError {
+    EmptyTree,
+    OutOfRange { index: usize, size: usize },
+    NotBytes32(String),
+    HexError(hex::FromHexError),
+}
+
+impl fmt::Display for MerkleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MerkleError::EmptyTree => write!(f, "merkle tree must have at least one leaf!"),
+            MerkleError::OutOfRange { index, size } => write!(f, "leaf index is out of range! index={}, size={}", index, size),
+            MerkleError::NotBytes32(input) => write!(f, "input value is not bytes32! input={}", input),
+            MerkleError::HexError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<hex::FromHexError> for MerkleError {
+    fn from(err: hex::FromHexError) -> Self {
+        MerkleError::HexError(err)
+    }
+}
+
+pub fn keccak256(data: &[u8]) -> Bytes32 {
+    let mut hasher = Keccak::v256();
+    let mut output = [0u8; 32];
+    hasher.update(data);
+    hasher.finalize(&mut output);
+    output
+}
+
+/*
+ * Same as `Lib_MerkleTree.getMerkleRoot`.
+ * A single leaf is the root itself, and an odd row is padded with the default hash of its depth.
+ */
+pub fn get_merkle_root(leaves: &[Bytes32]) -> Result<Bytes32, MerkleError> {
+    if leaves.is_empty() {
+        return Err(MerkleError::EmptyTree);
+    }
+    let mut row = leaves.to_vec();
+    let mut default_hash = keccak256(&[0u8; 32]);
+    while row.len() > 1 {
+        if row.len() % 2 == 1 {
+            row.push(default_hash);
+        }
+        row = row.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+        default_hash = hash_pair(&default_hash, &default_hash);
+    }
+    Ok(row[0])
+}
+
+/*
+ * Sibling path accepted by `Lib_MerkleTree.verify`, ordered from the leaf to the root.
+ */
+pub fn get_merkle_proof(leaves: &[Bytes32], leaf_index: usize) -> Result<Vec<Bytes32>, MerkleError> {
+    if leaf_index >= leaves.len() {
+        return Err(MerkleError::OutOfRange { index: leaf_index, size: leaves.len() });
+    }
+    let mut siblings = Vec::new();
+    let mut row = leaves.to_vec();
+    let mut index = leaf_index;
+    let mut default_hash = keccak256(&[0u8; 32]);
+    while row.len() > 1 {
+        if row.len() % 2 == 1 {
+            row.push(default_hash);
+        }
+        siblings.push(row[index ^ 1]);
+        row = row.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+        index /= 2;
+        default_hash = hash_pair(&default_hash, &default_hash);
+    }
+    Ok(siblings)
+}
+
+/*
+ * Same as `Lib_OVMCodec.hashTransactionChainElement`.
+ * keccak256(abi.encodePacked(isSequenced, queueIndex, timestamp, blockNumber, txData))
+ */
+pub fn hash_tx_chain_element(is_sequenced: bool, queue_index: U256, timestamp: U256, block_number: U256, tx_data: &[u8]) -> Bytes32 {
+    let mut encoded = Vec::with_capacity(97 + tx_data.len());
+    encoded.push(is_sequenced as u8);
+    encoded.extend_from_slice(&u256_to_bytes32(queue_index));
+    encoded.extend_from_slice(&u256_to_bytes32(timestamp));
+    encoded.extend_from_slice(&u256_to_bytes32(block_number));
+    encoded.extend_from_slice(tx_data);
+    keccak256(&encoded)
+}
+
+pub fn hex_to_bytes(hex_str: &str) -> Result<Vec<u8>, MerkleError> {
+    let prefix_removed = hex_str.trim_start_matches("0x");
+    Ok(hex::decode(prefix_removed)?)
+}
+
+pub fn hex_to_bytes32(hex_str: &str) -> Result<Bytes32, MerkleError> {
+    let bytes = hex_to_bytes(hex_str)?;
+    if bytes.len() != 32 {
+        return Err(MerkleError::NotBytes32(hex_str.to_owned()));
+    }
+    let mut bytes32 = [0u8; 32];
+    bytes32.copy_from_slice(&bytes);
+    Ok(bytes32)
+}
+
+pub fn bytes32_to_hex(bytes32: &Bytes32) -> String {
+    format!("0x{}", hex::encode(bytes32))
+}
+
+pub fn hash_pair(left: &Bytes32, right: &Bytes32) -> Bytes32 {
+    let mut buf = [0u8; 64];
+    buf[..32].copy_from_slice(left);
+    buf[32..].copy_from_slice(right);
+    keccak256(&buf)
+}
+
+fn u256_to_bytes32(value: U256) -> Bytes32 {
+    let mut bytes32 = [0u8; 32];
+    value.to_big_endian(&mut bytes32);
+    bytes32
+}
+
+#[cfg(test)]
+mod merkle {
+    use primitive_types::U256;
+
+    use crate::{Bytes32, bytes32_to_hex, get_merkle_proof, get_merkle_root, hash_pair, hash_tx_chain_element, hex_to_bytes32, keccak256};
+
+    #[test]
+    fn keccak256_test() {
+        let hashed = keccak256(&[0u8; 32]);
+        assert_eq!(bytes32_to_hex(&hashed), "0x290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563");
+    }
+
+    #[test]
+    fn get_merkle_root_single_leaf_test() {
+        let leaf = keccak256("bleu".as_bytes());
+        assert_eq!(get_merkle_root(&[leaf]).unwrap(), leaf);
+    }
+
+    #[test]
+    fn get_merkle_root_odd_leaves_test() {
+        let leaves = [keccak256("a".as_bytes()), keccak256("b".as_bytes()), keccak256("c".as_bytes())];
+        let default_hash = keccak256(&[0u8; 32]);
+        let expected = hash_pair(&hash_pair(&leaves[0], &leaves[1]), &hash_pair(&leaves[2], &default_hash));
+        assert_eq!(get_merkle_root(&leaves).unwrap(), expected);
+    }
+
+    #[test]
+    fn get_merkle_root_empty_test() {
+        assert!(get_merkle_root(&[]).is_err());
+    }
+
+    #[test]
+    fn hash_tx_chain_element_test() {
+        let hashed = hash_tx_chain_element(false, U256::from(1), U256::zero(), U256::zero(), &[]);
+        let mut encoded = vec![0u8; 97];
+        encoded[32] = 1;
+        assert_eq!(hashed, keccak256(&encoded));
+    }
+
+    #[test]
+    fn hex_to_bytes32_test() {
+        let hex_str = "0x290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563";
+        assert_eq!(bytes32_to_hex(&hex_to_bytes32(hex_str).unwrap()), hex_str);
+        assert!(hex_to_bytes32("0x1234").is_err());
+    }
+
+    fn leaves(size: usize) -> Vec<Bytes32> {
+        (0..size).map(|index| keccak256(format!("leaf{}", index).as_bytes())).collect()
+    }
+
+    /*
+     * Same as `Lib_MerkleTree.verify`.
+     */
+    fn root_from_proof(leaf: Bytes32, leaf_index: usize, siblings: &[Bytes32]) -> Bytes32 {
+        let mut node = leaf;
+        let mut index = leaf_index;
+        for sibling in siblings.iter() {
+            node = if index & 1 == 0 { hash_pair(&node, sibling) } else { hash_pair(sibling, &node) };
+            index /= 2;
+        }
+        node
+    }
+
+    fn assert_proofs(size: usize, depth: usize) {
+        let leaves = leaves(size);
+        let root = get_merkle_root(&leaves).unwrap();
+        for (index, leaf) in leaves.iter().enumerate() {
+            let siblings = get_merkle_proof(&leaves, index).unwrap();
+            assert_eq!(siblings.len(), depth);
+            assert_eq!(root_from_proof(*leaf, index, &siblings), root, "size={}, index={}", size, index);
+        }
+    }
+
+    #[test]
+    fn get_merkle_proof_single_leaf_test() {
+        assert_proofs(1, 0);
+    }
+
+    #[test]
+    fn get_merkle_proof_odd_leaves_test() {
+        assert_proofs(3, 2);
+        assert_proofs(5, 3);
+        let leaves = leaves(3);
+        assert_eq!(get_merkle_proof(&leaves, 2).unwrap()[0], keccak256(&[0u8; 32]));
+    }
+
+    #[test]
+    fn get_merkle_proof_power_of_two_test() {
+        assert_proofs(2, 1);
+        assert_proofs(4, 2);
+        assert_proofs(8, 3);
+    }
+
+    #[test]
+    fn get_merkle_proof_out_of_range_test() {
+        let err = get_merkle_proof(&leaves(4), 4).unwrap_err();
+        assert_eq!(err.to_string(), "leaf index is out of range! index=4, size=4");
+        assert!(get_merkle_proof(&[], 0).is_err());
+    }
+}
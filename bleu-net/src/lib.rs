@@ -0,0 +1,151 @@
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
+
+#[derive(Debug)]
+pub enum NetError {
+    InvalidUrl(String),
+    Unresolved(String),
+    NotPublic { host: String, ip: IpAddr },
+}
+
+impl fmt::Display for NetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NetError::InvalidUrl(url) => write!(f, "callback url must be http or https without user info! callback_url={}", url),
+            NetError::Unresolved(host) => write!(f, "callback host is not resolved! host={}", host),
+            NetError::NotPublic { host, ip } => write!(f, "callback host is not a public address! host={}, ip={}", host, ip),
+        }
+    }
+}
+
+/*
+ * Host of a callback url and the addresses it resolved to, all of which are public.
+ * The caller connects to `addrs` only, so the host cannot be rebound to a private address after the check.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublicTarget {
+    pub host: String,
+    pub addrs: Vec<SocketAddr>,
+}
+
+pub fn resolve_public(url: &str) -> Result<PublicTarget, NetError> {
+    let (host, port) = split_host_port(url)?;
+    let addrs = (host.as_str(), port).to_socket_addrs()
+        .map_err(|_| NetError::Unresolved(host.clone()))?
+        .collect::<Vec<SocketAddr>>();
+    if addrs.is_empty() {
+        return Err(NetError::Unresolved(host));
+    }
+    if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(&addr.ip())) {
+        return Err(NetError::NotPublic { host, ip: addr.ip() });
+    }
+    Ok(PublicTarget { host, addrs })
+}
+
+/*
+ * Rejects loopback, private, link-local, shared (100.64.0.0/10), unspecified, broadcast, multicast and documentation addresses,
+ * and an IPv4-mapped IPv6 address is checked as IPv4.
+ */
+pub fn is_public_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => is_public_ipv6(ip),
+    }
+}
+
+fn is_public_ipv4(ip: &Ipv4Addr) -> bool {
+    let octets = ip.octets();
+    let is_shared = octets[0] == 100 && (octets[1] & 0xc0) == 64;
+    !(octets[0] == 0 || ip.is_loopback() || ip.is_private() || ip.is_link_local() || is_shared
+        || ip.is_broadcast() || ip.is_multicast() || ip.is_documentation())
+}
+
+fn is_public_ipv6(ip: &Ipv6Addr) -> bool {
+    let segments = ip.segments();
+    if segments[..5].iter().all(|segment| *segment == 0) && segments[5] == 0xffff {
+        let [a, b] = segments[6].to_be_bytes();
+        let [c, d] = segments[7].to_be_bytes();
+        return is_public_ipv4(&Ipv4Addr::new(a, b, c, d));
+    }
+    let is_unique_local = (segments[0] & 0xfe00) == 0xfc00;
+    let is_link_local = (segments[0] & 0xffc0) == 0xfe80;
+    let is_documentation = segments[0] == 0x2001 && segments[1] == 0x0db8;
+    !(ip.is_loopback() || ip.is_unspecified() || ip.is_multicast() || is_unique_local || is_link_local || is_documentation)
+}
+
+fn split_host_port(url: &str) -> Result<(String, u16), NetError> {
+    let invalid = || NetError::InvalidUrl(String::from(url));
+    let lower = url.to_ascii_lowercase();
+    let (rest, default_port) = if lower.starts_with("https://") {
+        (&url[8..], 443)
+    } else if lower.starts_with("http://") {
+        (&url[7..], 80)
+    } else {
+        return Err(invalid());
+    };
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if authority.contains('@') || authority.contains('\\') {
+        return Err(invalid());
+    }
+    let (host, port) = match authority.strip_prefix('[') {
+        Some(bracketed) => {
+            let (host, port) = bracketed.split_once(']').ok_or_else(invalid)?;
+            (host, port.strip_prefix(':'))
+        }
+        None => match authority.split_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+    if host.is_empty() {
+        return Err(invalid());
+    }
+    let port = match port {
+        Some(port) => port.parse::<u16>().map_err(|_| invalid())?,
+        None => default_port,
+    };
+    Ok((host.to_ascii_lowercase(), port))
+}
+
+#[cfg(test)]
+mod net {
+    use std::net::IpAddr;
+
+    use crate::{is_public_ip, NetError, resolve_public, split_host_port};
+
+    fn is_public(ip: &str) -> bool {
+        is_public_ip(&ip.parse::<IpAddr>().unwrap())
+    }
+
+    #[test]
+    fn is_public_ip_test() {
+        assert!(is_public("8.8.8.8"));
+        assert!(is_public("2606:4700:4700::1111"));
+        for ip in ["127.0.0.1", "10.0.0.1", "172.16.0.1", "192.168.1.1", "169.254.169.254", "100.64.0.1", "0.0.0.0", "255.255.255.255", "224.0.0.1",
+            "::1", "::", "fc00::1", "fe80::1", "::ffff:127.0.0.1", "::ffff:169.254.169.254"].iter() {
+            assert!(!is_public(ip), "ip={}", ip);
+        }
+    }
+
+    #[test]
+    fn split_host_port_test() {
+        assert_eq!(split_host_port("https://example.com/hook?a=1").unwrap(), (String::from("example.com"), 443));
+        assert_eq!(split_host_port("HTTP://Example.com:8080").unwrap(), (String::from("example.com"), 8080));
+        assert_eq!(split_host_port("http://[::1]:8080/hook").unwrap(), (String::from("::1"), 8080));
+        assert!(split_host_port("ftp://example.com").is_err());
+        assert!(split_host_port("http://user@example.com").is_err());
+        assert!(split_host_port("http://example.com\\@127.0.0.1").is_err());
+        assert!(split_host_port("http://:80").is_err());
+        assert!(split_host_port("http://example.com:port").is_err());
+    }
+
+    #[test]
+    fn resolve_public_test() {
+        assert!(matches!(resolve_public("http://127.0.0.1:8080/hook"), Err(NetError::NotPublic { .. })));
+        assert!(matches!(resolve_public("http://[::1]/hook"), Err(NetError::NotPublic { .. })));
+        assert!(matches!(resolve_public("http://localhost/hook"), Err(NetError::NotPublic { .. })));
+        let target = resolve_public("https://8.8.8.8/hook").unwrap();
+        assert_eq!(target.host, "8.8.8.8");
+        assert_eq!(target.addrs[0].port(), 443);
+    }
+}
@@ -1,3 +1,6 @@
 pub mod swagger;
 pub mod postgres;
-pub mod server;
\ No newline at end of file
+pub mod server;
+pub mod finality;
+pub mod health;
+pub mod webhook;
\ No newline at end of file
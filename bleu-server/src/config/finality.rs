@@ -0,0 +1,24 @@
+use std::env;
+
+const DEFAULT_CHALLENGE_PERIOD: i64 = 604800;
+
+#[derive(Clone)]
+pub struct FinalityConfig {
+    challenge_period: i64,
+}
+
+impl FinalityConfig {
+    pub fn load() -> Self {
+        let challenge_period = match env::var("CHALLENGE_PERIOD") {
+            Ok(challenge_period) => challenge_period.parse::<i64>().expect("CHALLENGE_PERIOD must be seconds!"),
+            Err(_) => DEFAULT_CHALLENGE_PERIOD,
+        };
+        Self {
+            challenge_period,
+        }
+    }
+
+    pub fn get_challenge_period(&self) -> i64 {
+        self.challenge_period
+    }
+}
@@ -0,0 +1,36 @@
+use std::env;
+use std::time::Duration;
+
+const DEFAULT_READY_FRESHNESS_THRESHOLD: i64 = 600;
+const DEFAULT_READY_POOL_TIMEOUT: u64 = 2;
+
+#[derive(Clone)]
+pub struct HealthConfig {
+    freshness_threshold: i64,
+    pool_timeout: u64,
+}
+
+impl HealthConfig {
+    pub fn load() -> Self {
+        let freshness_threshold = match env::var("READY_FRESHNESS_THRESHOLD") {
+            Ok(freshness_threshold) => freshness_threshold.parse::<i64>().expect("READY_FRESHNESS_THRESHOLD must be seconds!"),
+            Err(_) => DEFAULT_READY_FRESHNESS_THRESHOLD,
+        };
+        let pool_timeout = match env::var("READY_POOL_TIMEOUT") {
+            Ok(pool_timeout) => pool_timeout.parse::<u64>().expect("READY_POOL_TIMEOUT must be seconds!"),
+            Err(_) => DEFAULT_READY_POOL_TIMEOUT,
+        };
+        Self {
+            freshness_threshold,
+            pool_timeout,
+        }
+    }
+
+    pub fn get_freshness_threshold(&self) -> i64 {
+        self.freshness_threshold
+    }
+
+    pub fn get_pool_timeout(&self) -> Duration {
+        Duration::from_secs(self.pool_timeout)
+    }
+}
@@ -7,7 +7,7 @@ pub struct SwaggerConfig {
 impl SwaggerConfig {
     pub fn load() -> Self {
         let mut spec = DefaultApiRaw::default();
-        spec.tags = SwaggerSpec::simple_tags(vec!["TxBatch", "StateRootBatch", "Tx", "L1ToL2", "TxLogs", "BoardSummary"]);
+        spec.tags = SwaggerSpec::simple_tags(vec!["TxBatch", "StateRootBatch", "Tx", "L1ToL2", "L2ToL1", "Bridge", "TxLogs", "BoardSummary", "Chart", "Proof", "Webhook"]);
         spec.info = SwaggerSpec::simple_info("0.1", "Bleu Server");
 
         Self {
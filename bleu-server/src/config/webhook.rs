@@ -0,0 +1,24 @@
+use std::env;
+
+const DEFAULT_MAX_WEBHOOKS_PER_ADDRESS: i64 = 10;
+
+#[derive(Clone)]
+pub struct WebhookConfig {
+    max_webhooks_per_address: i64,
+}
+
+impl WebhookConfig {
+    pub fn load() -> Self {
+        let max_webhooks_per_address = match env::var("MAX_WEBHOOKS_PER_ADDRESS") {
+            Ok(max_webhooks_per_address) => max_webhooks_per_address.parse::<i64>().expect("MAX_WEBHOOKS_PER_ADDRESS must be a number!"),
+            Err(_) => DEFAULT_MAX_WEBHOOKS_PER_ADDRESS,
+        };
+        Self {
+            max_webhooks_per_address,
+        }
+    }
+
+    pub fn get_max_webhooks_per_address(&self) -> i64 {
+        self.max_webhooks_per_address
+    }
+}
@@ -1,7 +1,10 @@
 use std::fmt::{Debug, Display, Formatter};
 
 use actix_web::{HttpResponse, ResponseError};
+use actix_web::http::StatusCode;
 use actix_web::error::BlockingError;
+use bleu_merkle::MerkleError;
+use bleu_net::NetError;
 use paperclip::actix::api_v2_errors;
 use r2d2;
 use serde::Serialize;
@@ -9,18 +12,24 @@ use serde::Serialize;
 #[api_v2_errors(code = 400, code = 500)]
 pub enum ExpectedError {
     DieselError(String),
+    InvalidError(String),
+    InternalError(String),
 }
 
 impl ExpectedError {
     pub fn code(&self) -> String {
         match self {
             ExpectedError::DieselError(_) => String::from("DIESEL_ERROR"),
+            ExpectedError::InvalidError(_) => String::from("INVALID_ERROR"),
+            ExpectedError::InternalError(_) => String::from("INTERNAL_ERROR"),
         }
     }
 
     pub fn name(&self) -> String {
         match self {
             ExpectedError::DieselError(_) => String::from("DieselError"),
+            ExpectedError::InvalidError(_) => String::from("InvalidError"),
+            ExpectedError::InternalError(_) => String::from("InternalError"),
         }
     }
 }
@@ -43,10 +52,48 @@ impl From<BlockingError<diesel::result::Error>> for ExpectedError {
     }
 }
 
+impl From<BlockingError<ExpectedError>> for ExpectedError {
+    fn from(err: BlockingError<ExpectedError>) -> Self {
+        match err {
+            BlockingError::Error(err) => err,
+            BlockingError::Canceled => ExpectedError::InternalError(String::from("thread pool is gone!")),
+        }
+    }
+}
+
+/*
+ * Hex is only decoded from indexed data, so a malformed value is a fault of the server.
+ */
+impl From<hex::FromHexError> for ExpectedError {
+    fn from(err: hex::FromHexError) -> Self {
+        ExpectedError::InternalError(err.to_string())
+    }
+}
+
+/*
+ * Merkle trees are built from indexed data only.
+ */
+impl From<MerkleError> for ExpectedError {
+    fn from(err: MerkleError) -> Self {
+        ExpectedError::InternalError(err.to_string())
+    }
+}
+
+/*
+ * A callback url is given by the client.
+ */
+impl From<NetError> for ExpectedError {
+    fn from(err: NetError) -> Self {
+        ExpectedError::InvalidError(err.to_string())
+    }
+}
+
 impl Display for ExpectedError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             ExpectedError::DieselError(err) => write!(f, "{}", err),
+            ExpectedError::InvalidError(err) => write!(f, "{}", err),
+            ExpectedError::InternalError(err) => write!(f, "{}", err),
         }
     }
 }
@@ -55,6 +102,8 @@ impl Debug for ExpectedError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             ExpectedError::DieselError(err) => write!(f, "{:?}", err),
+            ExpectedError::InvalidError(err) => write!(f, "{:?}", err),
+            ExpectedError::InternalError(err) => write!(f, "{:?}", err),
         }
     }
 }
@@ -68,6 +117,18 @@ struct ErrorResponse {
 
 
 impl ResponseError for ExpectedError {
+    /*
+     * InvalidError is a mistake of the client, such as an invalid parameter or a mismatched secret.
+     * InternalError is invalid data read from the database, e.g. a hash which is not bytes32.
+     */
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ExpectedError::DieselError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ExpectedError::InvalidError(_) => StatusCode::BAD_REQUEST,
+            ExpectedError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
     fn error_response(&self) -> HttpResponse {
         let status_code = self.status_code();
         let error_response = ErrorResponse {
@@ -1 +1,4 @@
-pub mod convert;
\ No newline at end of file
+pub mod convert;
+pub mod merkle;
+pub mod time;
+pub mod metrics;
\ No newline at end of file
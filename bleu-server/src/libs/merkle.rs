@@ -0,0 +1 @@
+pub use bleu_merkle::{Bytes32, bytes32_to_hex, get_merkle_proof, hash_tx_chain_element, hex_to_bytes, hex_to_bytes32};
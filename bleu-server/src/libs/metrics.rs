@@ -0,0 +1,39 @@
+use cached::Cached;
+use lazy_static::lazy_static;
+use prometheus::{Encoder, GaugeVec, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, register_gauge_vec, register_histogram_vec, register_int_counter_vec, register_int_gauge, register_int_gauge_vec, TextEncoder};
+
+use crate::config::postgres::Pool;
+
+lazy_static! {
+    pub static ref HTTP_REQUESTS: IntCounterVec = register_int_counter_vec!("bleu_http_requests_total", "Number of requests by route template", &["method", "route", "status"]).unwrap();
+    pub static ref HTTP_REQUEST_SECONDS: HistogramVec = register_histogram_vec!("bleu_http_request_seconds", "Request latency by route template", &["method", "route"]).unwrap();
+    pub static ref HTTP_ERRORS: IntCounterVec = register_int_counter_vec!("bleu_http_errors_total", "Number of 4xx and 5xx responses by route template", &["method", "route", "status"]).unwrap();
+    pub static ref POOL_CONNECTIONS: IntGauge = register_int_gauge!("bleu_pool_connections", "Number of connections in the r2d2 pool").unwrap();
+    pub static ref POOL_IDLE_CONNECTIONS: IntGauge = register_int_gauge!("bleu_pool_idle_connections", "Number of idle connections in the r2d2 pool").unwrap();
+    pub static ref CACHE_HITS: IntGaugeVec = register_int_gauge_vec!("bleu_cache_hits", "Cache hits of the cached repository function", &["function"]).unwrap();
+    pub static ref CACHE_MISSES: IntGaugeVec = register_int_gauge_vec!("bleu_cache_misses", "Cache misses of the cached repository function", &["function"]).unwrap();
+    pub static ref CACHE_HIT_RATIO: GaugeVec = register_gauge_vec!("bleu_cache_hit_ratio", "Cache hit ratio of the cached repository function", &["function"]).unwrap();
+}
+
+pub fn observe_pool(pool: &Pool) {
+    let state = pool.state();
+    POOL_CONNECTIONS.set(state.connections as i64);
+    POOL_IDLE_CONNECTIONS.set(state.idle_connections as i64);
+}
+
+pub fn observe_cache<K, V>(function: &str, cache: &impl Cached<K, V>) {
+    let hits = cache.cache_hits().unwrap_or_default();
+    let misses = cache.cache_misses().unwrap_or_default();
+    let hit_ratio = if hits + misses == 0 { 0.0 } else { hits as f64 / (hits + misses) as f64 };
+    CACHE_HITS.with_label_values(&[function]).set(hits as i64);
+    CACHE_MISSES.with_label_values(&[function]).set(misses as i64);
+    CACHE_HIT_RATIO.with_label_values(&[function]).set(hit_ratio);
+}
+
+pub fn gather() -> String {
+    let mut buffer = Vec::new();
+    if let Err(err) = TextEncoder::new().encode(&prometheus::gather(), &mut buffer) {
+        log::error!("failed to encode metrics! error={}", err.to_string());
+    }
+    String::from_utf8(buffer).unwrap_or_default()
+}
@@ -0,0 +1,5 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn current_timestamp() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs() as i64).unwrap_or_default()
+}
@@ -6,10 +6,14 @@ use actix_files as fs;
 use actix_web::{App, HttpServer};
 use paperclip::actix::{OpenApiExt, web};
 
+use crate::config::finality::FinalityConfig;
+use crate::config::health::HealthConfig;
 use crate::config::postgres::PostgresConfig;
 use crate::config::server::ServerConfig;
 use crate::config::swagger::SwaggerConfig;
-use crate::service::{optimism, swagger};
+use crate::config::webhook::WebhookConfig;
+use crate::middleware::metrics::RequestMetrics;
+use crate::service::{health, metrics, optimism, swagger};
 
 mod service;
 mod repository;
@@ -18,6 +22,7 @@ mod model;
 mod error;
 mod libs;
 mod config;
+mod middleware;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -26,15 +31,25 @@ async fn main() -> std::io::Result<()> {
 
     let server_config = ServerConfig::load();
     let postgres_config = PostgresConfig::load();
+    let finality_config = FinalityConfig::load();
+    let health_config = HealthConfig::load();
+    let webhook_config = WebhookConfig::load();
     HttpServer::new(move || {
         let swagger_config = SwaggerConfig::load();
 
         App::new()
             .route("/swagger", actix_web::web::get().to(swagger::load_swagger))
+            .route("/metrics", actix_web::web::get().to(metrics::get_metrics))
+            .route("/health", actix_web::web::get().to(health::get_health))
+            .route("/ready", actix_web::web::get().to(health::get_ready))
             .service(fs::Files::new("/swagger-ui", "./swagger-ui").show_files_listing())
             .wrap(Cors::default().allow_any_origin().send_wildcard())
+            .wrap(RequestMetrics)
             .wrap_api_with_spec(swagger_config.get_spec())
             .data(postgres_config.get_pool())
+            .data(finality_config.clone())
+            .data(health_config.clone())
+            .data(webhook_config.clone())
             .service(
                 web::scope("/api/v1")
                     .service(web::resource("/optimism/tx-batch/latest").route(web::get().to(optimism::get_latest_tx_batch_summary)))
@@ -43,16 +58,28 @@ async fn main() -> std::io::Result<()> {
                     .service(web::resource("/optimism/tx/latest").route(web::get().to(optimism::get_latest_tx_summary)))
                     .service(web::resource("/optimism/tx/hash/{hash}").route(web::get().to(optimism::get_tx_by_hash)))
                     .service(web::resource("/optimism/tx/index/{index}").route(web::get().to(optimism::get_tx_by_index)))
+                    .service(web::resource("/optimism/tx/index/{index}/proof").route(web::get().to(optimism::get_tx_proof_by_index)))
                     .service(web::resource("/optimism/tx/tx-batch/index/{index}").route(web::get().to(optimism::get_paginated_tx_by_tx_batch_index)))
                     .service(web::resource("/optimism/tx/stateroot-batch/index/{index}").route(web::get().to(optimism::get_paginated_tx_by_state_batch_index)))
                     .service(web::resource("/optimism/tx/address/{address}").route(web::get().to(optimism::get_paginated_tx_by_address)))
                     .service(web::resource("/optimism/tx").route(web::get().to(optimism::get_paginated_tx)))
                     .service(web::resource("/optimism/tx/l1tol2/latest").route(web::get().to(optimism::get_latest_l1_to_l2_tx_summary)))
                     .service(web::resource("/optimism/tx/l1tol2").route(web::get().to(optimism::get_l1_to_l2_tx_by_page_count)))
+                    .service(web::resource("/optimism/tx/l2tol1").route(web::get().to(optimism::get_l2_to_l1_message_by_page_count)))
+                    .service(web::resource("/optimism/tx/bridge").route(web::get().to(optimism::get_paginated_bridge_transfer)))
+                    .service(web::resource("/optimism/tx/bridge/address/{address}").route(web::get().to(optimism::get_paginated_bridge_transfer_by_address)))
                     .service(web::resource("/optimism/tx/logs/hash/{hash}").route(web::get().to(optimism::get_tx_logs_by_hash)))
                     .service(web::resource("/optimism/stateroot-batch").route(web::get().to(optimism::get_paginated_state_batch)))
                     .service(web::resource("/optimism/stateroot-batch/index/{index}").route(web::get().to(optimism::get_state_batch_by_index)))
+                    .service(web::resource("/optimism/stateroot-batch/index/{index}/verification").route(web::get().to(optimism::get_state_batch_verification_by_index)))
+                    .service(web::resource("/optimism/stateroot/index/{index}/proof").route(web::get().to(optimism::get_state_root_proof_by_index)))
                     .service(web::resource("/optimism/board/summary").route(web::get().to(optimism::get_board_summary)))
+                    .service(web::resource("/optimism/chart/stats").route(web::get().to(optimism::get_chain_stats)))
+                    .service(web::resource("/optimism/chart/stats/{metric}").route(web::get().to(optimism::get_chain_stat_by_metric)))
+                    .service(web::resource("/optimism/webhook").route(web::post().to(optimism::create_address_webhook)))
+                    .service(web::resource("/optimism/webhook/address/{address}").route(web::get().to(optimism::get_address_webhooks_by_address)))
+                    .service(web::resource("/optimism/webhook/{id}").route(web::delete().to(optimism::delete_address_webhook)))
+                    .service(web::resource("/optimism/webhook/{id}/delivery").route(web::get().to(optimism::get_paginated_address_webhook_delivery)))
             )
             .with_json_spec_at("/api/spec")
             .build()
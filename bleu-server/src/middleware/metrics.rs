@@ -0,0 +1,94 @@
+use std::future::{Future, ready, Ready};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::Error;
+use actix_web::http::header::{HeaderName, HeaderValue};
+
+use crate::libs::metrics::{HTTP_ERRORS, HTTP_REQUEST_SECONDS, HTTP_REQUESTS};
+use crate::libs::time::current_timestamp;
+
+const REQUEST_ID_HEADER: &str = "x-request-id";
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+static REQUEST_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/*
+ * Records request count, latency and errors per route template, and tags every response with a request id.
+ * A request id given by the client or a proxy is kept, otherwise a new one is created.
+ */
+pub struct RequestMetrics;
+
+impl<S, B> Transform<S> for RequestMetrics
+    where
+        S: Service<Request=ServiceRequest, Response=ServiceResponse<B>, Error=Error>,
+        S::Future: 'static,
+        B: 'static {
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequestMetricsMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestMetricsMiddleware { service }))
+    }
+}
+
+pub struct RequestMetricsMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service for RequestMetricsMiddleware<S>
+    where
+        S: Service<Request=ServiceRequest, Response=ServiceResponse<B>, Error=Error>,
+        S::Future: 'static,
+        B: 'static {
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output=Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: ServiceRequest) -> Self::Future {
+        let request_id = req.headers().get(REQUEST_ID_HEADER)
+            .and_then(|request_id| request_id.to_str().ok())
+            .map(String::from)
+            .unwrap_or_else(next_request_id);
+        let method = req.method().to_string();
+        let path = req.path().to_string();
+        let route = req.request().match_pattern().unwrap_or_else(|| String::from(UNMATCHED_ROUTE));
+        let started_at = Instant::now();
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let mut res = fut.await?;
+            let elapsed = started_at.elapsed();
+            let status = res.status();
+            HTTP_REQUESTS.with_label_values(&[&method, &route, status.as_str()]).inc();
+            HTTP_REQUEST_SECONDS.with_label_values(&[&method, &route]).observe(elapsed.as_secs_f64());
+            if status.is_client_error() || status.is_server_error() {
+                HTTP_ERRORS.with_label_values(&[&method, &route, status.as_str()]).inc();
+            }
+            match res.response().error() {
+                Some(err) => log::warn!("request_id={} method={} path={} status={} elapsed={:?} error={}", request_id, method, path, status.as_u16(), elapsed, err),
+                None => log::info!("request_id={} method={} path={} status={} elapsed={:?}", request_id, method, path, status.as_u16(), elapsed),
+            }
+            if let Ok(header_value) = HeaderValue::from_str(&request_id) {
+                res.headers_mut().insert(HeaderName::from_static(REQUEST_ID_HEADER), header_value);
+            }
+            Ok(res)
+        })
+    }
+}
+
+fn next_request_id() -> String {
+    format!("{:x}-{:x}", current_timestamp(), REQUEST_SEQ.fetch_add(1, Ordering::Relaxed))
+}
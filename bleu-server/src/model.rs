@@ -1,3 +1,5 @@
 pub mod optimism;
 pub mod pagination;
-pub mod ethereum;
\ No newline at end of file
+pub mod ethereum;
+pub mod health;
+pub mod numeric;
\ No newline at end of file
@@ -7,13 +7,13 @@ pub struct EthereumTxLog {
     address: Option<String>,
     topics: Option<String>,
     data: Option<String>,
-    block_number: Option<String>,
+    block_number: Option<i64>,
     tx_hash: Option<String>,
-    tx_index: Option<String>,
+    tx_index: Option<i64>,
     block_hash: Option<String>,
-    log_index: Option<String>,
+    log_index: Option<i64>,
     removed: Option<bool>,
-    queue_index: Option<String>,
+    queue_index: Option<i64>,
 }
 
 impl EthereumTxLog {
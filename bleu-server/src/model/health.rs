@@ -0,0 +1,44 @@
+use serde::Serialize;
+
+use crate::error::error::ExpectedError;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthCheck {
+    name: String,
+    ok: bool,
+    message: String,
+}
+
+impl HealthCheck {
+    pub fn new(name: &str, result: Result<(), ExpectedError>) -> Self {
+        let (ok, message) = match result {
+            Ok(_) => (true, String::from("ok")),
+            Err(err) => (false, err.to_string()),
+        };
+        Self {
+            name: String::from(name),
+            ok,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    status: String,
+    checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    pub fn new(checks: Vec<HealthCheck>) -> Self {
+        let status = if checks.iter().all(|check| check.ok) { "ok" } else { "fail" };
+        Self {
+            status: String::from(status),
+            checks,
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.status == "ok"
+    }
+}
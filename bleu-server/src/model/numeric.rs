@@ -0,0 +1,62 @@
+use std::fmt;
+
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::data_types::PgNumeric;
+use diesel::pg::Pg;
+use diesel::sql_types::Numeric;
+use paperclip::v2::models::{DataType, DefaultSchemaRaw};
+use paperclip::v2::schema::Apiv2Schema;
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+/*
+ * A `numeric(78, 0)` column of uint256 values.
+ * It is serialized as a decimal string, since json numbers lose precision above 2^53.
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromSqlRow)]
+#[serde(transparent)]
+pub struct Uint256(String);
+
+impl Uint256 {
+    pub fn to_u256(&self) -> Option<U256> {
+        U256::from_dec_str(&self.0).ok()
+    }
+
+    /*
+     * Numeric digits are base 10000 and `weight` is the exponent of the first digit.
+     */
+    fn from_digits(weight: i16, digits: &[i16]) -> Self {
+        if digits.is_empty() || weight < 0 {
+            return Uint256(String::from("0"));
+        }
+        let mut decimal = digits[0].to_string();
+        for i in 1..=weight as usize {
+            decimal.push_str(&format!("{:04}", digits.get(i).cloned().unwrap_or(0)));
+        }
+        Uint256(decimal)
+    }
+}
+
+impl fmt::Display for Uint256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromSql<Numeric, Pg> for Uint256 {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        match PgNumeric::from_sql(bytes)? {
+            PgNumeric::Positive { weight, digits, .. } => Ok(Uint256::from_digits(weight, &digits)),
+            PgNumeric::Negative { .. } => Err("negative numeric is not uint256!".into()),
+            PgNumeric::NaN => Err("NaN is not uint256!".into()),
+        }
+    }
+}
+
+impl Apiv2Schema for Uint256 {
+    fn raw_schema() -> DefaultSchemaRaw {
+        let mut schema = DefaultSchemaRaw::default();
+        schema.data_type = Some(DataType::String);
+        schema
+    }
+}
@@ -1,39 +1,40 @@
 use paperclip::actix::Apiv2Schema;
+use primitive_types::U256;
 use serde::{Deserialize, Serialize};
 
+use crate::error::error::ExpectedError;
 use crate::libs;
+use crate::libs::merkle::{Bytes32, bytes32_to_hex, hash_tx_chain_element, hex_to_bytes, hex_to_bytes32};
+use crate::model::numeric::Uint256;
 use crate::model::pagination::PageInfo;
 use crate::repository::pagination::PaginatedRecord;
+use crate::schema::optimism::optimism_address_webhooks;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
 pub struct OptimismTxBatchSummary {
-    batch_index: Option<String>,
+    batch_index: Option<i64>,
     l1_tx_hash: Option<String>,
-    batch_size: Option<String>,
-    batch_timestamp: Option<String>,
+    batch_size: Option<i64>,
+    batch_timestamp: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
 pub struct OptimismTxBatch {
     optimism_tx_batches_id: i64,
-    batch_index: Option<String>,
-    batch_timestamp: Option<String>,
-    batch_size: Option<String>,
+    batch_index: Option<i64>,
+    batch_timestamp: Option<i64>,
+    batch_size: Option<i64>,
     l1_tx_hash: Option<String>,
-    l1_block_number: Option<String>,
+    l1_block_number: Option<i64>,
     batch_root: Option<String>,
-    previous_total_elements: Option<String>,
+    previous_total_elements: Option<i64>,
     extra_data: Option<String>,
     submitter: Option<String>,
 }
 
 impl OptimismTxBatch {
-    pub fn get_batch_index(&self) -> String {
-        if self.batch_index.is_none() {
-            String::from("0")
-        } else {
-            self.batch_index.clone().unwrap()
-        }
+    pub fn get_batch_index(&self) -> i64 {
+        self.batch_index.unwrap_or_default()
     }
 }
 
@@ -57,52 +58,58 @@ pub struct OptimismTxSummary {
     tx_hash: Option<String>,
     from_address: Option<String>,
     to_address: Option<String>,
-    value: Option<String>,
-    tx_timestamp: Option<String>,
+    value: Option<Uint256>,
+    tx_timestamp: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
 pub struct OptimismBlockTx {
     optimism_block_txs_id: i64,
     block_hash: Option<String>,
-    block_number: Option<String>,
+    block_number: Option<i64>,
     from_address: Option<String>,
-    gas: Option<String>,
-    gas_price: Option<String>,
+    gas: Option<i64>,
+    gas_price: Option<Uint256>,
     hash: Option<String>,
-    index: Option<String>,
+    index: Option<i64>,
     tx_input: Option<String>,
-    l1_block_number: Option<String>,
-    l1_timestamp: Option<String>,
+    l1_block_number: Option<i64>,
+    l1_timestamp: Option<i64>,
     l1_tx_origin: Option<String>,
-    nonce: Option<String>,
-    queue_index: Option<String>,
+    nonce: Option<i64>,
+    queue_index: Option<i64>,
     queue_origin: Option<String>,
     raw_tx: Option<String>,
     to_address: Option<String>,
-    tx_index: Option<String>,
+    tx_index: Option<i64>,
     tx_type: Option<String>,
-    value: Option<String>,
+    value: Option<Uint256>,
 }
 
 
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
 pub struct OptimismBlockTxPaging {
     tx: OptimismBlockTx,
-    gas_used: Option<String>,
+    gas_used: Option<i64>,
     contract_address: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
 pub struct OptimismBlockTxExtended {
     tx: OptimismBlockTx,
-    l1_tx_batch_index: Option<String>,
+    l1_tx_batch_index: Option<i64>,
     l1_submission_tx_hash: Option<String>,
-    l1_state_batch_index: Option<String>,
+    l1_state_batch_index: Option<i64>,
     l1_state_root_submission_tx_hash: Option<String>,
-    state: Option<String>,
-    gas_used: Option<String>,
+    state: Option<i64>,
+    gas_used: Option<i64>,
     contract_address: Option<String>,
+    l1_tx_batch_timestamp: Option<i64>,
+    l1_state_batch_timestamp: Option<i64>,
+    l1_fee: Option<Uint256>,
+    l1_gas_used: Option<Uint256>,
+    l1_gas_price: Option<Uint256>,
+    l1_fee_scalar: Option<String>,
 }
 
 impl OptimismBlockTxExtended {
@@ -110,8 +117,8 @@ impl OptimismBlockTxExtended {
         self.tx.queue_origin.is_some() && self.tx.queue_origin.clone().unwrap() == "l1".to_string()
     }
 
-    pub fn get_queue_index(&self) -> Option<String> {
-        self.tx.queue_index.clone()
+    pub fn get_queue_index(&self) -> Option<i64> {
+        self.tx.queue_index
     }
 
     pub fn is_queue_index_exist(&self) -> bool {
@@ -123,13 +130,119 @@ impl OptimismBlockTxExtended {
 pub struct OptimismBlockTxDetail {
     tx_ext: OptimismBlockTxExtended,
     l1_origin_tx_hash: Option<String>,
+    lifecycle: OptimismTxLifecycle,
+    fee: OptimismTxFee,
 }
 
 impl OptimismBlockTxDetail {
-    pub fn from(tx_ext: OptimismBlockTxExtended, l1_origin_tx_hash: Option<String>) -> Self {
+    pub fn from(tx_ext: OptimismBlockTxExtended, l1_origin_tx_hash: Option<String>, challenge_period: i64) -> Self {
+        let lifecycle = OptimismTxLifecycle::new(&tx_ext, challenge_period);
+        let fee = OptimismTxFee::new(&tx_ext);
         Self {
             tx_ext,
             l1_origin_tx_hash,
+            lifecycle,
+            fee,
+        }
+    }
+}
+
+/*
+ * The L2 execution fee is gasUsed * gasPrice, and the L1 data fee is the `l1Fee` charged by the sequencer for posting the tx to L1.
+ * Fees are None until the receipt is indexed.
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
+pub struct OptimismTxFee {
+    l2_execution_fee: Option<String>,
+    l1_data_fee: Option<String>,
+    total_fee: Option<String>,
+}
+
+impl OptimismTxFee {
+    pub fn new(tx_ext: &OptimismBlockTxExtended) -> Self {
+        let l2_execution_fee = tx_ext.gas_used.map(|gas_used| U256::from(gas_used as u64))
+            .zip(to_opt_u256(&tx_ext.tx.gas_price))
+            .and_then(|(gas_used, gas_price)| gas_used.checked_mul(gas_price));
+        let l1_data_fee = to_opt_u256(&tx_ext.l1_fee);
+        let total_fee = match (l2_execution_fee, l1_data_fee) {
+            (Some(l2_execution_fee), Some(l1_data_fee)) => l2_execution_fee.checked_add(l1_data_fee),
+            (l2_execution_fee, None) => l2_execution_fee,
+            _ => None,
+        };
+        Self {
+            l2_execution_fee: l2_execution_fee.map(|fee| fee.to_string()),
+            l1_data_fee: l1_data_fee.map(|fee| fee.to_string()),
+            total_fee: total_fee.map(|fee| fee.to_string()),
+        }
+    }
+}
+
+pub enum TxStatus {
+    Sequenced,
+    Batched,
+    StateRootPublished,
+    Finalized,
+}
+
+impl TxStatus {
+    pub fn value(&self) -> String {
+        match self {
+            TxStatus::Sequenced => String::from("sequenced"),
+            TxStatus::Batched => String::from("batched"),
+            TxStatus::StateRootPublished => String::from("state_root_published"),
+            TxStatus::Finalized => String::from("finalized"),
+        }
+    }
+
+    pub fn find(status: &str) -> Result<Self, ExpectedError> {
+        match status {
+            "sequenced" => Ok(TxStatus::Sequenced),
+            "batched" => Ok(TxStatus::Batched),
+            "state_root_published" => Ok(TxStatus::StateRootPublished),
+            "finalized" => Ok(TxStatus::Finalized),
+            _ => Err(ExpectedError::InvalidError(format!("unknown tx status! status={}", status))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestTxStatus {
+    pub status: Option<String>,
+}
+
+/*
+ * sequenced -> batched(tx batch on L1) -> state_root_published(state batch on L1) -> finalized(challenge period elapsed)
+ * finalized_timestamp is the expected one until the status becomes finalized.
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
+pub struct OptimismTxLifecycle {
+    status: String,
+    sequenced_timestamp: Option<i64>,
+    tx_batch_timestamp: Option<i64>,
+    state_batch_timestamp: Option<i64>,
+    finalized_timestamp: Option<i64>,
+}
+
+impl OptimismTxLifecycle {
+    pub fn new(tx_ext: &OptimismBlockTxExtended, challenge_period: i64) -> Self {
+        let finalized_timestamp = tx_ext.l1_state_batch_timestamp.map(|timestamp| timestamp + challenge_period);
+        let status = if let Some(finalized_timestamp) = finalized_timestamp {
+            if finalized_timestamp <= libs::time::current_timestamp() {
+                TxStatus::Finalized
+            } else {
+                TxStatus::StateRootPublished
+            }
+        } else if tx_ext.l1_tx_batch_timestamp.is_some() {
+            TxStatus::Batched
+        } else {
+            TxStatus::Sequenced
+        };
+        Self {
+            status: status.value(),
+            sequenced_timestamp: tx_ext.tx.l1_timestamp,
+            tx_batch_timestamp: tx_ext.l1_tx_batch_timestamp,
+            state_batch_timestamp: tx_ext.l1_state_batch_timestamp,
+            finalized_timestamp,
         }
     }
 }
@@ -152,24 +265,20 @@ impl PaginatedOptimismBlockTx {
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
 pub struct OptimismStateBatch {
     optimism_state_batches_id: i64,
-    batch_index: Option<String>,
-    batch_timestamp: Option<String>,
-    batch_size: Option<String>,
+    batch_index: Option<i64>,
+    batch_timestamp: Option<i64>,
+    batch_size: Option<i64>,
     l1_tx_hash: Option<String>,
-    l1_block_number: Option<String>,
+    l1_block_number: Option<i64>,
     batch_root: Option<String>,
-    previous_total_elements: Option<String>,
+    previous_total_elements: Option<i64>,
     extra_data: Option<String>,
     submitter: Option<String>,
 }
 
 impl OptimismStateBatch {
-    pub fn get_batch_index(&self) -> String {
-        if self.batch_index.is_none() {
-            String::from("0")
-        } else {
-            self.batch_index.clone().unwrap()
-        }
+    pub fn get_batch_index(&self) -> i64 {
+        self.batch_index.unwrap_or_default()
     }
 }
 
@@ -188,22 +297,198 @@ impl PaginatedOptimismStateBatch {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
+pub struct OptimismTx {
+    optimism_txs_id: i64,
+    index: Option<i64>,
+    batch_index: Option<i64>,
+    block_number: Option<i64>,
+    tx_timestamp: Option<i64>,
+    gas_limit: Option<i64>,
+    target: Option<String>,
+    origin: Option<String>,
+    data: Option<String>,
+    queue_origin: Option<String>,
+    value: Option<Uint256>,
+    queue_index: Option<i64>,
+    decoded: Option<String>,
+    confirmed: Option<bool>,
+    l1_tx_hash: Option<String>,
+}
+
+impl OptimismTx {
+    pub fn get_index(&self) -> u64 {
+        parse_index(&self.index)
+    }
+
+    pub fn get_batch_index(&self) -> Result<i64, ExpectedError> {
+        parse_batch_index(&self.batch_index)
+    }
+
+    pub fn leaf_hash(&self) -> Result<Bytes32, ExpectedError> {
+        let leaf_hash = if self.queue_origin.clone().unwrap_or_default() == "sequencer" {
+            let tx_data = hex_to_bytes(self.data.clone().unwrap_or_default().as_str())?;
+            hash_tx_chain_element(true, U256::zero(), to_u256(&self.tx_timestamp)?, to_u256(&self.block_number)?, &tx_data)
+        } else {
+            hash_tx_chain_element(false, to_u256(&self.queue_index)?, U256::zero(), U256::zero(), &[])
+        };
+        Ok(leaf_hash)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
+pub struct OptimismStateRoot {
+    optimism_state_roots_id: i64,
+    index: Option<i64>,
+    batch_index: Option<i64>,
+    value: Option<String>,
+    confirmed: Option<bool>,
+    l1_tx_hash: Option<String>,
+}
+
+impl OptimismStateRoot {
+    pub fn get_index(&self) -> u64 {
+        parse_index(&self.index)
+    }
+
+    pub fn get_batch_index(&self) -> Result<i64, ExpectedError> {
+        parse_batch_index(&self.batch_index)
+    }
+
+    pub fn leaf_hash(&self) -> Result<Bytes32, ExpectedError> {
+        Ok(hex_to_bytes32(self.value.clone().unwrap_or_default().as_str())?)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
+pub struct OptimismMerkleProof {
+    index: u64,
+    leaf_index: u64,
+    leaf: String,
+    siblings: Vec<String>,
+    batch_index: Option<i64>,
+    batch_root: Option<String>,
+    batch_size: Option<i64>,
+    previous_total_elements: Option<i64>,
+    extra_data: Option<String>,
+    l1_tx_hash: Option<String>,
+}
+
+impl OptimismMerkleProof {
+    pub fn from_tx_batch(tx_batch: OptimismTxBatch, index: u64, leaf_index: usize, leaf: &Bytes32, siblings: &Vec<Bytes32>) -> Self {
+        Self {
+            index,
+            leaf_index: leaf_index as u64,
+            leaf: bytes32_to_hex(leaf),
+            siblings: siblings.iter().map(|sibling| bytes32_to_hex(sibling)).collect(),
+            batch_index: tx_batch.batch_index,
+            batch_root: tx_batch.batch_root,
+            batch_size: tx_batch.batch_size,
+            previous_total_elements: tx_batch.previous_total_elements,
+            extra_data: tx_batch.extra_data,
+            l1_tx_hash: tx_batch.l1_tx_hash,
+        }
+    }
+
+    pub fn from_state_batch(state_batch: OptimismStateBatch, index: u64, leaf_index: usize, leaf: &Bytes32, siblings: &Vec<Bytes32>) -> Self {
+        Self {
+            index,
+            leaf_index: leaf_index as u64,
+            leaf: bytes32_to_hex(leaf),
+            siblings: siblings.iter().map(|sibling| bytes32_to_hex(sibling)).collect(),
+            batch_index: state_batch.batch_index,
+            batch_root: state_batch.batch_root,
+            batch_size: state_batch.batch_size,
+            previous_total_elements: state_batch.previous_total_elements,
+            extra_data: state_batch.extra_data,
+            l1_tx_hash: state_batch.l1_tx_hash,
+        }
+    }
+}
+
+fn parse_index(index: &Option<i64>) -> u64 {
+    index.unwrap_or_default() as u64
+}
+
+fn parse_batch_index(batch_index: &Option<i64>) -> Result<i64, ExpectedError> {
+    match batch_index {
+        Some(batch_index) => Ok(*batch_index),
+        None => Err(ExpectedError::InternalError(String::from("invalid batch index! batch_index=None"))),
+    }
+}
+
+fn to_u256(value: &Option<i64>) -> Result<U256, ExpectedError> {
+    match value {
+        Some(value) if *value >= 0 => Ok(U256::from(*value as u64)),
+        _ => Err(ExpectedError::InternalError(format!("invalid unsigned value! value={:?}", value))),
+    }
+}
+
+fn to_opt_u256(value: &Option<Uint256>) -> Option<U256> {
+    value.as_ref().and_then(Uint256::to_u256)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
+pub struct OptimismStateRootMismatch {
+    optimism_state_root_mismatches_id: i64,
+    index: Option<i64>,
+    batch_index: Option<i64>,
+    l1_tx_hash: Option<String>,
+    block_number: Option<i64>,
+    block_hash: Option<String>,
+    expected_state_root: Option<String>,
+    actual_state_root: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
+pub struct OptimismStateBatchVerification {
+    batch_index: i64,
+    batch_size: Option<i64>,
+    verified_count: i64,
+    mismatch_count: i64,
+    status: String,
+    mismatches: Vec<OptimismStateRootMismatch>,
+}
+
+impl OptimismStateBatchVerification {
+    pub fn new(state_batch: &OptimismStateBatch, verified_count: i64, mismatches: Vec<OptimismStateRootMismatch>) -> Self {
+        let mismatch_count = mismatches.len() as i64;
+        let batch_size = state_batch.batch_size;
+        let total_count = batch_size.unwrap_or(0);
+        let status = if mismatch_count > 0 {
+            "mismatched"
+        } else if total_count > 0 && verified_count >= total_count {
+            "verified"
+        } else {
+            "pending"
+        };
+        Self {
+            batch_index: state_batch.get_batch_index(),
+            batch_size,
+            verified_count,
+            mismatch_count,
+            status: String::from(status),
+            mismatches,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
 pub struct OptimismL1ToL2TxSummary {
-    l1_block_number: Option<String>,
+    l1_block_number: Option<i64>,
     l1_tx_hash: Option<String>,
     l2_tx_hash: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
 pub struct OptimismL1ToL2Tx {
-    l1_block_number: Option<String>,
-    queue_index: Option<String>,
+    l1_block_number: Option<i64>,
+    queue_index: Option<i64>,
     l2_tx_hash: Option<String>,
-    timestamp: Option<String>,
+    timestamp: Option<i64>,
     l1_tx_hash: Option<String>,
     l1_tx_origin: Option<String>,
-    gas_limit: Option<String>,
+    gas_limit: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
@@ -221,17 +506,205 @@ impl PaginatedOptimismL1ToL2Tx {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
+pub struct OptimismCrossDomainMessage {
+    optimism_cross_domain_messages_id: i64,
+    msg_hash: Option<String>,
+    event_name: Option<String>,
+    tx_hash: Option<String>,
+    block_number: Option<i64>,
+    block_timestamp: Option<i64>,
+    log_index: Option<i64>,
+    target: Option<String>,
+    sender: Option<String>,
+    message: Option<String>,
+    message_nonce: Option<Uint256>,
+    gas_limit: Option<i64>,
+}
+
+impl OptimismCrossDomainMessage {
+    pub fn get_msg_hash(&self) -> Option<String> {
+        self.msg_hash.clone()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
+pub struct OptimismSentMessage {
+    message: OptimismCrossDomainMessage,
+    l2_timestamp: Option<i64>,
+    l1_state_batch_index: Option<i64>,
+    l1_state_batch_timestamp: Option<i64>,
+}
+
+impl OptimismSentMessage {
+    pub fn get_msg_hash(&self) -> Option<String> {
+        self.message.get_msg_hash()
+    }
+}
+
+pub enum L2ToL1Status {
+    Initiated,
+    StateRootPublished,
+    ReadyToRelay,
+    Relayed,
+    Failed,
+}
+
+impl L2ToL1Status {
+    pub fn value(&self) -> String {
+        match self {
+            L2ToL1Status::Initiated => String::from("initiated"),
+            L2ToL1Status::StateRootPublished => String::from("state_root_published"),
+            L2ToL1Status::ReadyToRelay => String::from("ready_to_relay"),
+            L2ToL1Status::Relayed => String::from("relayed"),
+            L2ToL1Status::Failed => String::from("failed"),
+        }
+    }
+}
+
+/*
+ * A message can be relayed after the challenge period of the state batch including its L2 tx.
+ * A failed relay can be retried, so a RelayedMessage event takes precedence over FailedRelayedMessage events.
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
+pub struct OptimismL2ToL1Message {
+    sent_message: OptimismSentMessage,
+    status: String,
+    relayable_timestamp: Option<i64>,
+    l1_tx_hash: Option<String>,
+    l1_timestamp: Option<i64>,
+}
+
+impl OptimismL2ToL1Message {
+    pub fn new(sent_message: OptimismSentMessage, relay_messages: Vec<OptimismCrossDomainMessage>, challenge_period: i64) -> Self {
+        let relayed = relay_messages.iter().find(|relay| relay.event_name.as_deref() == Some("RelayedMessage"));
+        let failed = relay_messages.iter().filter(|relay| relay.event_name.as_deref() == Some("FailedRelayedMessage")).last();
+        let relayable_timestamp = sent_message.l1_state_batch_timestamp.map(|timestamp| timestamp + challenge_period);
+        let (status, relay) = if relayed.is_some() {
+            (L2ToL1Status::Relayed, relayed)
+        } else if failed.is_some() {
+            (L2ToL1Status::Failed, failed)
+        } else if let Some(relayable_timestamp) = relayable_timestamp {
+            if relayable_timestamp <= libs::time::current_timestamp() {
+                (L2ToL1Status::ReadyToRelay, None)
+            } else {
+                (L2ToL1Status::StateRootPublished, None)
+            }
+        } else {
+            (L2ToL1Status::Initiated, None)
+        };
+        Self {
+            sent_message,
+            status: status.value(),
+            relayable_timestamp,
+            l1_tx_hash: relay.and_then(|relay| relay.tx_hash.clone()),
+            l1_timestamp: relay.and_then(|relay| relay.block_timestamp),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
+pub struct PaginatedOptimismL2ToL1Message {
+    page_info: PageInfo,
+    records: Vec<OptimismL2ToL1Message>,
+}
+
+impl PaginatedOptimismL2ToL1Message {
+    pub fn new(paginated: PaginatedRecord<OptimismSentMessage>, relay_messages: Vec<OptimismCrossDomainMessage>, challenge_period: i64) -> Self {
+        let records = paginated.records.into_iter()
+            .map(|sent_message| {
+                let msg_hash = sent_message.get_msg_hash();
+                let relays = relay_messages.iter().filter(|relay| relay.msg_hash == msg_hash).cloned().collect();
+                OptimismL2ToL1Message::new(sent_message, relays, challenge_period)
+            })
+            .collect();
+        Self {
+            page_info: PageInfo::new(paginated.page, paginated.count, paginated.total_page, paginated.total_count),
+            records,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
+pub struct OptimismBridgeTransfer {
+    optimism_bridge_transfers_id: i64,
+    event_name: Option<String>,
+    transfer_type: Option<String>,
+    l1_token: Option<String>,
+    l2_token: Option<String>,
+    from_address: Option<String>,
+    to_address: Option<String>,
+    amount: Option<Uint256>,
+    extra_data: Option<String>,
+    tx_hash: Option<String>,
+    block_number: Option<i64>,
+    block_timestamp: Option<i64>,
+    log_index: Option<i64>,
+    queue_index: Option<i64>,
+    msg_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
+pub struct OptimismBridgeTransferPaging {
+    transfer: OptimismBridgeTransfer,
+    l2_block_tx_hash: Option<String>,
+    l1_enqueue_tx_hash: Option<String>,
+    l1_relay_tx_hash: Option<String>,
+}
+
+/*
+ * L1 deposits are linked to the L2 tx of the same queue index, finalized deposits to the L1 tx that enqueued them,
+ * and withdrawals to the L1 tx that relayed their cross domain message.
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
+pub struct OptimismBridgeTransferDetail {
+    transfer: OptimismBridgeTransfer,
+    l1_tx_hash: Option<String>,
+    l2_tx_hash: Option<String>,
+}
+
+impl From<OptimismBridgeTransferPaging> for OptimismBridgeTransferDetail {
+    fn from(paging: OptimismBridgeTransferPaging) -> Self {
+        let tx_hash = paging.transfer.tx_hash.clone();
+        let (l1_tx_hash, l2_tx_hash) = match paging.transfer.event_name.as_deref() {
+            Some("ETHDepositInitiated") | Some("ERC20DepositInitiated") => (tx_hash, paging.l2_block_tx_hash),
+            Some("DepositFinalized") => (paging.l1_enqueue_tx_hash, tx_hash),
+            _ => (paging.l1_relay_tx_hash, tx_hash),
+        };
+        Self {
+            transfer: paging.transfer,
+            l1_tx_hash,
+            l2_tx_hash,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
+pub struct PaginatedOptimismBridgeTransfer {
+    page_info: PageInfo,
+    records: Vec<OptimismBridgeTransferDetail>,
+}
+
+impl PaginatedOptimismBridgeTransfer {
+    pub fn new(paginated: PaginatedRecord<OptimismBridgeTransferPaging>) -> Self {
+        Self {
+            page_info: PageInfo::new(paginated.page, paginated.count, paginated.total_page, paginated.total_count),
+            records: paginated.records.into_iter().map(OptimismBridgeTransferDetail::from).collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
 pub struct OptimismTxReceiptLog {
     optimism_tx_receipt_logs_id: i64,
     address: Option<String>,
     topics: Option<String>,
     data: Option<String>,
-    block_number: Option<String>,
+    block_number: Option<i64>,
     tx_hash: Option<String>,
-    tx_index: Option<String>,
+    tx_index: Option<i64>,
     block_hash: Option<String>,
-    log_index: Option<String>,
+    log_index: Option<i64>,
     removed: Option<bool>,
 }
 
@@ -240,11 +713,11 @@ pub struct OptimismTxLog {
     address: Option<String>,
     topics: Option<Vec<String>>,
     data: Option<String>,
-    block_number: Option<String>,
+    block_number: Option<i64>,
     tx_hash: Option<String>,
-    tx_index: Option<String>,
+    tx_index: Option<i64>,
     block_hash: Option<String>,
-    log_index: Option<String>,
+    log_index: Option<i64>,
     removed: Option<bool>,
 }
 
@@ -268,17 +741,115 @@ impl OptimismTxLog {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
+pub struct OptimismChainStat {
+    optimism_chain_stats_id: i64,
+    granularity: Option<String>,
+    bucket_timestamp: Option<i64>,
+    tx_count: Option<i64>,
+    active_addresses: Option<i64>,
+    new_contracts: Option<i64>,
+    gas_used: Option<Uint256>,
+    avg_gas_price: Option<Uint256>,
+    deposit_count: Option<i64>,
+    tx_batch_count: Option<i64>,
+    state_batch_count: Option<i64>,
+}
+
+impl OptimismChainStat {
+    pub fn get_chart_point(&self, metric: &str) -> Result<OptimismChartPoint, ExpectedError> {
+        let value = match metric {
+            "tx_count" => self.tx_count.map(|count| count.to_string()),
+            "active_addresses" => self.active_addresses.map(|count| count.to_string()),
+            "new_contracts" => self.new_contracts.map(|count| count.to_string()),
+            "gas_used" => self.gas_used.as_ref().map(Uint256::to_string),
+            "avg_gas_price" => self.avg_gas_price.as_ref().map(Uint256::to_string),
+            "deposit_count" => self.deposit_count.map(|count| count.to_string()),
+            "tx_batch_count" => self.tx_batch_count.map(|count| count.to_string()),
+            "state_batch_count" => self.state_batch_count.map(|count| count.to_string()),
+            _ => return Err(ExpectedError::InvalidError(format!("unknown chart metric! metric={}", metric))),
+        };
+        Ok(OptimismChartPoint {
+            timestamp: self.bucket_timestamp,
+            value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
+pub struct OptimismChartPoint {
+    timestamp: Option<i64>,
+    value: Option<String>,
+}
+
+pub enum ChartGranularity {
+    Hour,
+    Day,
+}
+
+impl ChartGranularity {
+    pub fn value(&self) -> String {
+        match self {
+            ChartGranularity::Hour => String::from("hour"),
+            ChartGranularity::Day => String::from("day"),
+        }
+    }
+
+    pub fn seconds(&self) -> i64 {
+        match self {
+            ChartGranularity::Hour => 3600,
+            ChartGranularity::Day => 86400,
+        }
+    }
+
+    pub fn find(granularity: &str) -> Result<Self, ExpectedError> {
+        match granularity {
+            "hour" => Ok(ChartGranularity::Hour),
+            "day" => Ok(ChartGranularity::Day),
+            _ => Err(ExpectedError::InvalidError(format!("unknown chart granularity! granularity={}", granularity))),
+        }
+    }
+}
+
+/*
+ * `from` and `to` are unix timestamps, and the range defaults to the latest 30 buckets.
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestChainStat {
+    pub granularity: Option<String>,
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+}
+
+const DEFAULT_CHART_POINTS: i64 = 30;
+const MAX_CHART_POINTS: i64 = 1000;
+
+impl RequestChainStat {
+    pub fn get_range(&self) -> Result<(ChartGranularity, i64, i64), ExpectedError> {
+        let granularity = ChartGranularity::find(self.granularity.as_deref().unwrap_or("day"))?;
+        let to = self.to.unwrap_or_else(libs::time::current_timestamp);
+        let from = self.from.unwrap_or(to - granularity.seconds() * DEFAULT_CHART_POINTS);
+        if from >= to {
+            return Err(ExpectedError::InvalidError(format!("chart range is empty! from={}, to={}", from, to)));
+        }
+        if (to - from) / granularity.seconds() > MAX_CHART_POINTS {
+            return Err(ExpectedError::InvalidError(format!("chart range is too large! from={}, to={}, max_points={}", from, to, MAX_CHART_POINTS)));
+        }
+        Ok((granularity, from, to))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
 pub struct BoardSummary {
-    latest_tx_batch_index: String,
-    latest_state_batch_index: String,
+    latest_tx_batch_index: i64,
+    latest_state_batch_index: i64,
     tx_count: i64,
 }
 
 impl BoardSummary {
     pub fn new(
-        latest_tx_batch_index: String,
-        latest_state_batch_index: String,
+        latest_tx_batch_index: i64,
+        latest_state_batch_index: i64,
         tx_count: i64,
     ) -> Self {
         Self {
@@ -287,4 +858,118 @@ impl BoardSummary {
             tx_count,
         }
     }
+}
+
+/*
+ * `direction` is one of `from`, `to` and `any` (default), and is ignored when `topic` is given.
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestAddressWebhook {
+    pub address: String,
+    pub direction: Option<String>,
+    pub topic: Option<String>,
+    pub callback_url: String,
+}
+
+impl RequestAddressWebhook {
+    pub fn to_new_webhook(&self, secret: String) -> Result<NewOptimismAddressWebhook, ExpectedError> {
+        if !is_hex(&self.address, 40) {
+            return Err(ExpectedError::InvalidError(format!("invalid address! address={}", self.address)));
+        }
+        let direction = self.direction.clone().unwrap_or(String::from("any"));
+        if !vec!["from", "to", "any"].contains(&direction.as_str()) {
+            return Err(ExpectedError::InvalidError(format!("unknown direction! direction={}", direction)));
+        }
+        if let Some(topic) = &self.topic {
+            if !is_hex(topic, 64) {
+                return Err(ExpectedError::InvalidError(format!("invalid topic! topic={}", topic)));
+            }
+        }
+        if !self.callback_url.starts_with("http://") && !self.callback_url.starts_with("https://") {
+            return Err(ExpectedError::InvalidError(format!("callback url must be http or https! callback_url={}", self.callback_url)));
+        }
+        Ok(NewOptimismAddressWebhook {
+            address: self.address.to_lowercase(),
+            direction,
+            topic: self.topic.as_ref().map(|topic| topic.to_lowercase()),
+            callback_url: self.callback_url.clone(),
+            secret,
+            active: true,
+            created_at: libs::time::current_timestamp(),
+        })
+    }
+}
+
+fn is_hex(value: &str, length: usize) -> bool {
+    value.len() == length + 2 && value.starts_with("0x") && value[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[table_name = "optimism_address_webhooks"]
+pub struct NewOptimismAddressWebhook {
+    address: String,
+    direction: String,
+    topic: Option<String>,
+    callback_url: String,
+    secret: String,
+    active: bool,
+    created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
+pub struct OptimismAddressWebhook {
+    optimism_address_webhooks_id: i64,
+    address: String,
+    direction: String,
+    topic: Option<String>,
+    callback_url: String,
+    active: bool,
+    created_at: i64,
+}
+
+/*
+ * The secret is only returned when the webhook is created, and is required to delete it.
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
+pub struct CreatedOptimismAddressWebhook {
+    webhook: OptimismAddressWebhook,
+    secret: String,
+}
+
+impl CreatedOptimismAddressWebhook {
+    pub fn new(webhook: OptimismAddressWebhook, secret: String) -> Self {
+        Self {
+            webhook,
+            secret,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
+pub struct OptimismAddressWebhookDelivery {
+    optimism_address_webhook_deliveries_id: i64,
+    webhook_id: i64,
+    event: String,
+    tx_hash: String,
+    log_index: Option<String>,
+    status: String,
+    attempt: i64,
+    response_status: Option<i64>,
+    error: Option<String>,
+    delivered_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Apiv2Schema)]
+pub struct PaginatedOptimismAddressWebhookDelivery {
+    page_info: PageInfo,
+    records: Vec<OptimismAddressWebhookDelivery>,
+}
+
+impl PaginatedOptimismAddressWebhookDelivery {
+    pub fn new(paginated: PaginatedRecord<OptimismAddressWebhookDelivery>) -> Self {
+        Self {
+            page_info: PageInfo::new(paginated.page, paginated.count, paginated.total_page, paginated.total_count),
+            records: paginated.records,
+        }
+    }
 }
\ No newline at end of file
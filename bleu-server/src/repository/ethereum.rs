@@ -7,7 +7,7 @@ pub mod ethereum_log {
     use crate::model::ethereum::EthereumTxLog;
     use crate::schema::ethereum::ethereum_tx_logs;
 
-    pub async fn find_by_queue_index(conn: PgConn, queue_index: String) -> Result<EthereumTxLog, ExpectedError> {
+    pub async fn find_by_queue_index(conn: PgConn, queue_index: i64) -> Result<EthereumTxLog, ExpectedError> {
         let tx_log = web::block(move || {
             ethereum_tx_logs::table.filter(ethereum_tx_logs::columns::queue_index.eq(queue_index))
                 .first::<EthereumTxLog>(&conn)
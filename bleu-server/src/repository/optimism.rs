@@ -16,7 +16,7 @@ pub mod tx_batch {
         let conn = pool.get()?;
         let batch_summary = web::block(move || {
             optimism_tx_batches::table.select((batch_index, l1_tx_hash, batch_size, batch_timestamp))
-                .order(optimism_tx_batches_id.desc())
+                .order(batch_index.desc())
                 .limit(10)
                 .load::<OptimismTxBatchSummary>(&conn)
         }).await?;
@@ -26,7 +26,7 @@ pub mod tx_batch {
     pub async fn find_batch_by_index(pool: web::Data<Pool>, index: i64) -> Result<OptimismTxBatch, ExpectedError> {
         let conn = pool.get()?;
         let batch_summary = web::block(move || {
-            optimism_tx_batches::table.filter(batch_index.eq(index.to_string()))
+            optimism_tx_batches::table.filter(batch_index.eq(index))
                 .first::<OptimismTxBatch>(&conn)
         }).await?;
         Ok(batch_summary)
@@ -36,7 +36,7 @@ pub mod tx_batch {
         let conn = pool.get()?;
         let paginated_batch = web::block(move || {
             optimism_tx_batches::table.into_boxed()
-                .order(optimism_tx_batches_id.desc())
+                .order(batch_index.desc())
                 .load_with_pagination(&conn, page, count)
         }).await?;
         Ok(paginated_batch)
@@ -45,7 +45,7 @@ pub mod tx_batch {
     pub async fn find_latest_tx_batch(conn: PgConn) -> Result<OptimismTxBatch, ExpectedError> {
         let latest_tx_batch = web::block(move || {
             optimism_tx_batches::table.into_boxed()
-                .order(optimism_tx_batches_id.desc())
+                .order(batch_index.desc())
                 .first::<OptimismTxBatch>(&conn)
         }).await?;
         Ok(latest_tx_batch)
@@ -55,14 +55,17 @@ pub mod tx_batch {
 pub mod tx {
     use actix_web::web;
     use diesel::prelude::*;
+    use diesel::dsl::sql;
     use diesel::RunQueryDsl;
+    use diesel::sql_types::Bool;
 
     use crate::config::postgres::{PgConn, Pool};
     use crate::error::error::ExpectedError;
-    use crate::model::optimism::{OptimismBlockTxDetail, OptimismBlockTxExtended, OptimismBlockTxPaging, OptimismTxSummary};
+    use crate::libs;
+    use crate::model::optimism::{OptimismBlockTxDetail, OptimismBlockTxExtended, OptimismBlockTxPaging, OptimismTxSummary, TxStatus};
     use crate::repository::ethereum::ethereum_log::find_by_queue_index;
     use crate::repository::pagination::{LoadPaginated, PaginatedRecord};
-    use crate::schema::optimism::{optimism_block_txs, optimism_state_roots, optimism_tx_receipts};
+    use crate::schema::optimism::{optimism_block_txs, optimism_state_batches, optimism_state_roots, optimism_tx_batches, optimism_tx_receipts};
     use crate::schema::optimism::optimism_block_txs::columns::*;
     use crate::schema::optimism::optimism_txs;
 
@@ -70,28 +73,30 @@ pub mod tx {
         let conn = pool.get()?;
         let tx_summary = web::block(move || {
             optimism_block_txs::table.select((hash, from_address, to_address, value, l1_timestamp))
-                .order(optimism_block_txs_id.desc())
+                .order(optimism_block_txs::index.desc())
                 .limit(10)
                 .load::<OptimismTxSummary>(&conn)
         }).await?;
         Ok(tx_summary)
     }
 
-    pub async fn find_tx_by_hash(pool: web::Data<Pool>, tx_hash: String) -> Result<OptimismBlockTxDetail, ExpectedError> {
-        Ok(find_tx_detail_by_condition(pool, Some(tx_hash), None).await?)
+    pub async fn find_tx_by_hash(pool: web::Data<Pool>, tx_hash: String, challenge_period: i64) -> Result<OptimismBlockTxDetail, ExpectedError> {
+        Ok(find_tx_detail_by_condition(pool, Some(tx_hash), None, challenge_period).await?)
     }
 
-    pub async fn find_tx_by_index(pool: web::Data<Pool>, batch_tx_index: i64) -> Result<OptimismBlockTxDetail, ExpectedError> {
-        Ok(find_tx_detail_by_condition(pool, None, Some(batch_tx_index)).await?)
+    pub async fn find_tx_by_index(pool: web::Data<Pool>, batch_tx_index: i64, challenge_period: i64) -> Result<OptimismBlockTxDetail, ExpectedError> {
+        Ok(find_tx_detail_by_condition(pool, None, Some(batch_tx_index), challenge_period).await?)
     }
 
-    pub async fn find_tx_detail_by_condition(pool: web::Data<Pool>, tx_hash: Option<String>, batch_tx_index: Option<i64>) -> Result<OptimismBlockTxDetail, ExpectedError> {
+    pub async fn find_tx_detail_by_condition(pool: web::Data<Pool>, tx_hash: Option<String>, batch_tx_index: Option<i64>, challenge_period: i64) -> Result<OptimismBlockTxDetail, ExpectedError> {
         let conn = pool.get()?;
         let tx_ext: OptimismBlockTxExtended = web::block(move || {
             let query = optimism_block_txs::table
                 .left_outer_join(optimism_txs::table.on(optimism_block_txs::columns::index.eq(optimism_txs::columns::index)))
                 .left_outer_join(optimism_state_roots::table.on(optimism_block_txs::columns::index.eq(optimism_state_roots::columns::index)))
                 .left_outer_join(optimism_tx_receipts::table.on(optimism_block_txs::columns::hash.eq(optimism_tx_receipts::columns::tx_hash)))
+                .left_outer_join(optimism_tx_batches::table.on(optimism_txs::columns::batch_index.eq(optimism_tx_batches::columns::batch_index)))
+                .left_outer_join(optimism_state_batches::table.on(optimism_state_roots::columns::batch_index.eq(optimism_state_batches::columns::batch_index)))
                 .select((
                     optimism_block_txs::all_columns,
                     optimism_txs::columns::batch_index.nullable(),
@@ -100,12 +105,18 @@ pub mod tx {
                     optimism_state_roots::columns::l1_tx_hash.nullable(),
                     optimism_tx_receipts::columns::status.nullable(),
                     optimism_tx_receipts::columns::gas_used.nullable(),
-                    optimism_tx_receipts::columns::contract_address.nullable()
+                    optimism_tx_receipts::columns::contract_address.nullable(),
+                    optimism_tx_batches::columns::batch_timestamp.nullable(),
+                    optimism_state_batches::columns::batch_timestamp.nullable(),
+                    optimism_tx_receipts::columns::l1_fee.nullable(),
+                    optimism_tx_receipts::columns::l1_gas_used.nullable(),
+                    optimism_tx_receipts::columns::l1_gas_price.nullable(),
+                    optimism_tx_receipts::columns::l1_fee_scalar.nullable()
                 ));
             if tx_hash.is_some() {
                 query.filter(hash.eq(tx_hash.unwrap().clone())).first::<OptimismBlockTxExtended>(&conn)
             } else {
-                query.filter(optimism_block_txs::index.eq(batch_tx_index.unwrap().to_string())).first::<OptimismBlockTxExtended>(&conn)
+                query.filter(optimism_block_txs::index.eq(batch_tx_index.unwrap())).first::<OptimismBlockTxExtended>(&conn)
             }
         }).await?;
         let conn = pool.get()?;
@@ -118,14 +129,18 @@ pub mod tx {
             }
             false => None
         };
-        Ok(OptimismBlockTxDetail::from(tx_ext, l1_tx_hash))
+        Ok(OptimismBlockTxDetail::from(tx_ext, l1_tx_hash, challenge_period))
     }
 
-    pub async fn find_tx_by_tx_batch_index_page_count(pool: web::Data<Pool>, tx_batch_index: i64, page: i64, count: i64) -> Result<PaginatedRecord<OptimismBlockTxPaging>, ExpectedError> {
+    pub async fn find_tx_by_tx_batch_index_page_count(pool: web::Data<Pool>, tx_batch_index: i64, page: i64, count: i64, status: Option<String>, challenge_period: i64) -> Result<PaginatedRecord<OptimismBlockTxPaging>, ExpectedError> {
+        let condition = match status {
+            Some(status) => Some(status_condition(status.as_str(), challenge_period)?),
+            None => None,
+        };
         let conn = pool.get()?;
         let paginated_tx = web::block(move || {
-            optimism_block_txs::table.inner_join(optimism_txs::table.on(
-                optimism_block_txs::index.eq(optimism_txs::index).and(optimism_txs::batch_index.eq(tx_batch_index.to_string()))
+            let mut query = optimism_block_txs::table.inner_join(optimism_txs::table.on(
+                optimism_block_txs::index.eq(optimism_txs::index).and(optimism_txs::batch_index.eq(tx_batch_index))
             ))
                 .left_outer_join(optimism_tx_receipts::table.on(optimism_block_txs::columns::hash.eq(optimism_tx_receipts::columns::tx_hash)))
                 .select((
@@ -133,25 +148,37 @@ pub mod tx {
                     optimism_tx_receipts::columns::gas_used.nullable(),
                     optimism_tx_receipts::columns::contract_address.nullable()
                 ))
-                .order(optimism_block_txs_id.desc())
-                .load_with_pagination(&conn, page, count)
+                .order(optimism_block_txs::index.desc())
+                .into_boxed();
+            if let Some(condition) = condition {
+                query = query.filter(sql::<Bool>(condition.as_str()));
+            }
+            query.load_with_pagination(&conn, page, count)
         }).await?;
         Ok(paginated_tx)
     }
 
-    pub async fn find_tx_by_state_batch_index_page_count(pool: web::Data<Pool>, state_batch_index: i64, page: i64, count: i64) -> Result<PaginatedRecord<OptimismBlockTxPaging>, ExpectedError> {
+    pub async fn find_tx_by_state_batch_index_page_count(pool: web::Data<Pool>, state_batch_index: i64, page: i64, count: i64, status: Option<String>, challenge_period: i64) -> Result<PaginatedRecord<OptimismBlockTxPaging>, ExpectedError> {
+        let condition = match status {
+            Some(status) => Some(status_condition(status.as_str(), challenge_period)?),
+            None => None,
+        };
         let conn = pool.get()?;
         let paginated_tx = web::block(move || {
-            optimism_block_txs::table.inner_join(optimism_state_roots::table.on(
-                optimism_block_txs::index.eq(optimism_state_roots::index).and(optimism_state_roots::batch_index.eq(state_batch_index.to_string()))
+            let mut query = optimism_block_txs::table.inner_join(optimism_state_roots::table.on(
+                optimism_block_txs::index.eq(optimism_state_roots::index).and(optimism_state_roots::batch_index.eq(state_batch_index))
             )).left_outer_join(optimism_tx_receipts::table.on(optimism_block_txs::columns::hash.eq(optimism_tx_receipts::columns::tx_hash)))
                 .select((
                     optimism_block_txs::all_columns,
                     optimism_tx_receipts::columns::gas_used.nullable(),
                     optimism_tx_receipts::columns::contract_address.nullable()
                 ))
-                .order(optimism_block_txs_id.desc())
-                .load_with_pagination(&conn, page, count)
+                .order(optimism_block_txs::index.desc())
+                .into_boxed();
+            if let Some(condition) = condition {
+                query = query.filter(sql::<Bool>(condition.as_str()));
+            }
+            query.load_with_pagination(&conn, page, count)
         }).await?;
         Ok(paginated_tx)
     }
@@ -163,37 +190,75 @@ pub mod tx {
         Ok(tx_count)
     }
 
-    pub async fn find_tx_by_address_page_count(pool: web::Data<Pool>, address: String, page: i64, count: i64) -> Result<PaginatedRecord<OptimismBlockTxPaging>, ExpectedError> {
+    pub async fn find_tx_by_address_page_count(pool: web::Data<Pool>, address: String, page: i64, count: i64, status: Option<String>, challenge_period: i64) -> Result<PaginatedRecord<OptimismBlockTxPaging>, ExpectedError> {
+        let condition = match status {
+            Some(status) => Some(status_condition(status.as_str(), challenge_period)?),
+            None => None,
+        };
         let conn = pool.get()?;
         let paginated_tx = web::block(move || {
-            optimism_block_txs::table.filter(from_address.eq(address.clone()).or(to_address.eq(address)))
+            let mut query = optimism_block_txs::table.filter(from_address.eq(address.clone()).or(to_address.eq(address)))
                 .left_outer_join(optimism_tx_receipts::table.on(optimism_block_txs::columns::hash.eq(optimism_tx_receipts::columns::tx_hash)))
                 .select((
                     optimism_block_txs::all_columns,
                     optimism_tx_receipts::columns::gas_used.nullable(),
                     optimism_tx_receipts::columns::contract_address.nullable()
                 ))
-                .order(optimism_block_txs_id.desc())
-                .load_with_pagination(&conn, page, count)
+                .order(optimism_block_txs::index.desc())
+                .into_boxed();
+            if let Some(condition) = condition {
+                query = query.filter(sql::<Bool>(condition.as_str()));
+            }
+            query.load_with_pagination(&conn, page, count)
         }).await?;
         Ok(paginated_tx)
     }
 
-    pub async fn find_tx_by_page_count(pool: web::Data<Pool>, page: i64, count: i64) -> Result<PaginatedRecord<OptimismBlockTxPaging>, ExpectedError> {
+    pub async fn find_tx_by_page_count(pool: web::Data<Pool>, page: i64, count: i64, status: Option<String>, challenge_period: i64) -> Result<PaginatedRecord<OptimismBlockTxPaging>, ExpectedError> {
+        let condition = match status {
+            Some(status) => Some(status_condition(status.as_str(), challenge_period)?),
+            None => None,
+        };
         let conn = pool.get()?;
         let paginated_tx = web::block(move || {
-            optimism_block_txs::table
+            let mut query = optimism_block_txs::table
                 .left_outer_join(optimism_tx_receipts::table.on(optimism_block_txs::columns::hash.eq(optimism_tx_receipts::columns::tx_hash)))
                 .select((
                     optimism_block_txs::all_columns,
                     optimism_tx_receipts::columns::gas_used.nullable(),
                     optimism_tx_receipts::columns::contract_address.nullable()
                 ))
-                .order(optimism_block_txs_id.desc())
-                .load_with_pagination(&conn, page, count)
+                .order(optimism_block_txs::index.desc())
+                .into_boxed();
+            if let Some(condition) = condition {
+                query = query.filter(sql::<Bool>(condition.as_str()));
+            }
+            query.load_with_pagination(&conn, page, count)
         }).await?;
         Ok(paginated_tx)
     }
+
+    /*
+     * Each condition matches the latest stage a tx has reached, so a tx belongs to exactly one status.
+     */
+    fn status_condition(status: &str, challenge_period: i64) -> Result<String, ExpectedError> {
+        let batched = "EXISTS (SELECT 1 FROM optimism_txs status_tx \
+            INNER JOIN optimism_tx_batches status_tx_batch ON status_tx_batch.batch_index = status_tx.batch_index \
+            WHERE status_tx.index = optimism_block_txs.index)";
+        let published = "EXISTS (SELECT 1 FROM optimism_state_roots status_root \
+            INNER JOIN optimism_state_batches status_state_batch ON status_state_batch.batch_index = status_root.batch_index \
+            WHERE status_root.index = optimism_block_txs.index)";
+        let finalized = format!("EXISTS (SELECT 1 FROM optimism_state_roots status_root \
+            INNER JOIN optimism_state_batches status_state_batch ON status_state_batch.batch_index = status_root.batch_index \
+            WHERE status_root.index = optimism_block_txs.index AND status_state_batch.batch_timestamp + {} <= {})", challenge_period, libs::time::current_timestamp());
+        let condition = match TxStatus::find(status)? {
+            TxStatus::Sequenced => format!("NOT {}", batched),
+            TxStatus::Batched => format!("{} AND NOT {}", batched, published),
+            TxStatus::StateRootPublished => format!("{} AND NOT {}", published, finalized),
+            TxStatus::Finalized => finalized,
+        };
+        Ok(format!("({})", condition))
+    }
 }
 
 pub mod state_batch {
@@ -212,7 +277,7 @@ pub mod state_batch {
         let conn = pool.get()?;
         let paginated_batch = web::block(move || {
             optimism_state_batches::table.into_boxed()
-                .order(optimism_state_batches_id.desc())
+                .order(batch_index.desc())
                 .load_with_pagination(&conn, page, count)
         }).await?;
         Ok(paginated_batch)
@@ -221,7 +286,7 @@ pub mod state_batch {
     pub async fn find_batch_by_index(pool: web::Data<Pool>, index: i64) -> Result<OptimismStateBatch, ExpectedError> {
         let conn = pool.get()?;
         let state_batch = web::block(move || {
-            optimism_state_batches::table.filter(batch_index.eq(index.to_string()))
+            optimism_state_batches::table.filter(batch_index.eq(index))
                 .first::<OptimismStateBatch>(&conn)
         }).await?;
         Ok(state_batch)
@@ -230,13 +295,109 @@ pub mod state_batch {
     pub async fn find_latest_state_batch(conn: PgConn) -> Result<OptimismStateBatch, ExpectedError> {
         let latest_state_batch = web::block(move || {
             optimism_state_batches::table.into_boxed()
-                .order(optimism_state_batches_id.desc())
+                .order(batch_index.desc())
                 .first::<OptimismStateBatch>(&conn)
         }).await?;
         Ok(latest_state_batch)
     }
 }
 
+pub mod state_root_verification {
+    use actix_web::web;
+    use diesel::prelude::*;
+    use diesel::RunQueryDsl;
+
+    use crate::config::postgres::Pool;
+    use crate::error::error::ExpectedError;
+    use crate::model::optimism::{OptimismStateBatchVerification, OptimismStateRootMismatch};
+    use crate::repository::optimism::state_batch::find_batch_by_index;
+    use crate::schema::optimism::{optimism_block_txs, optimism_blocks, optimism_state_root_mismatches, optimism_state_roots};
+
+    pub async fn find_verification_by_batch_index(pool: web::Data<Pool>, index: i64) -> Result<OptimismStateBatchVerification, ExpectedError> {
+        let state_batch = find_batch_by_index(pool.clone(), index).await?;
+        let conn = pool.get()?;
+        let verified_count: i64 = web::block(move || {
+            optimism_state_roots::table
+                .inner_join(optimism_block_txs::table.on(optimism_state_roots::index.eq(optimism_block_txs::index)))
+                .inner_join(optimism_blocks::table.on(optimism_block_txs::block_hash.eq(optimism_blocks::hash)))
+                .filter(optimism_state_roots::batch_index.eq(index))
+                .filter(optimism_state_roots::value.eq(optimism_blocks::state_root))
+                .count()
+                .get_result(&conn)
+        }).await?;
+        let conn = pool.get()?;
+        let mismatches = web::block(move || {
+            optimism_state_root_mismatches::table
+                .filter(optimism_state_root_mismatches::batch_index.eq(index))
+                .order(optimism_state_root_mismatches::index.asc())
+                .load::<OptimismStateRootMismatch>(&conn)
+        }).await?;
+        Ok(OptimismStateBatchVerification::new(&state_batch, verified_count, mismatches))
+    }
+}
+
+pub mod proof {
+    use actix_web::web;
+    use diesel::prelude::*;
+    use diesel::RunQueryDsl;
+
+    use crate::config::postgres::Pool;
+    use crate::error::error::ExpectedError;
+    use crate::libs::merkle::{Bytes32, get_merkle_proof};
+    use crate::model::optimism::{OptimismMerkleProof, OptimismStateRoot, OptimismTx};
+    use crate::repository::optimism::{state_batch, tx_batch};
+    use crate::schema::optimism::{optimism_state_roots, optimism_txs};
+
+    pub async fn find_tx_proof_by_index(pool: web::Data<Pool>, index: i64) -> Result<OptimismMerkleProof, ExpectedError> {
+        let conn = pool.get()?;
+        let tx = web::block(move || {
+            optimism_txs::table.filter(optimism_txs::index.eq(index))
+                .first::<OptimismTx>(&conn)
+        }).await?;
+        let batch_index = tx.get_batch_index()?;
+        let tx_batch = tx_batch::find_batch_by_index(pool.clone(), batch_index).await?;
+        let conn = pool.get()?;
+        let mut batch_txs = web::block(move || {
+            optimism_txs::table.filter(optimism_txs::batch_index.eq(batch_index))
+                .load::<OptimismTx>(&conn)
+        }).await?;
+        batch_txs.sort_by_key(|batch_tx| batch_tx.get_index());
+
+        let leaves = batch_txs.iter().map(|batch_tx| batch_tx.leaf_hash()).collect::<Result<Vec<Bytes32>, ExpectedError>>()?;
+        let leaf_index = find_leaf_index(batch_txs.iter().map(|batch_tx| batch_tx.get_index()).collect(), tx.get_index())?;
+        let siblings = get_merkle_proof(&leaves, leaf_index)?;
+        Ok(OptimismMerkleProof::from_tx_batch(tx_batch, tx.get_index(), leaf_index, &leaves[leaf_index], &siblings))
+    }
+
+    pub async fn find_state_root_proof_by_index(pool: web::Data<Pool>, index: i64) -> Result<OptimismMerkleProof, ExpectedError> {
+        let conn = pool.get()?;
+        let state_root = web::block(move || {
+            optimism_state_roots::table.filter(optimism_state_roots::index.eq(index))
+                .first::<OptimismStateRoot>(&conn)
+        }).await?;
+        let batch_index = state_root.get_batch_index()?;
+        let state_batch = state_batch::find_batch_by_index(pool.clone(), batch_index).await?;
+        let conn = pool.get()?;
+        let mut batch_state_roots = web::block(move || {
+            optimism_state_roots::table.filter(optimism_state_roots::batch_index.eq(batch_index))
+                .load::<OptimismStateRoot>(&conn)
+        }).await?;
+        batch_state_roots.sort_by_key(|batch_state_root| batch_state_root.get_index());
+
+        let leaves = batch_state_roots.iter().map(|batch_state_root| batch_state_root.leaf_hash()).collect::<Result<Vec<Bytes32>, ExpectedError>>()?;
+        let leaf_index = find_leaf_index(batch_state_roots.iter().map(|batch_state_root| batch_state_root.get_index()).collect(), state_root.get_index())?;
+        let siblings = get_merkle_proof(&leaves, leaf_index)?;
+        Ok(OptimismMerkleProof::from_state_batch(state_batch, state_root.get_index(), leaf_index, &leaves[leaf_index], &siblings))
+    }
+
+    fn find_leaf_index(indexes: Vec<u64>, index: u64) -> Result<usize, ExpectedError> {
+        match indexes.iter().position(|batch_element_index| *batch_element_index == index) {
+            Some(leaf_index) => Ok(leaf_index),
+            None => Err(ExpectedError::InternalError(format!("element does not exist in the batch! index={}", index))),
+        }
+    }
+}
+
 pub mod l1_to_l2 {
     use actix_web::web;
     use cached::proc_macro::cached;
@@ -258,7 +419,7 @@ pub mod l1_to_l2 {
                 ethereum_tx_logs::table.on(optimism_block_txs::queue_index.eq(ethereum_tx_logs::queue_index))
             )
                 .filter(optimism_block_txs::queue_origin.eq("l1"))
-                .order(optimism_block_txs::optimism_block_txs_id.desc())
+                .order(optimism_block_txs::queue_index.desc())
                 .select((optimism_block_txs::l1_block_number, ethereum_tx_logs::tx_hash, optimism_block_txs::hash))
                 .limit(10)
                 .load::<OptimismL1ToL2TxSummary>(&conn)
@@ -273,7 +434,7 @@ pub mod l1_to_l2 {
                 ethereum_tx_logs::table.on(optimism_block_txs::queue_index.eq(ethereum_tx_logs::queue_index))
             )
                 .filter(optimism_block_txs::queue_origin.eq("l1"))
-                .order(optimism_block_txs::optimism_block_txs_id.desc())
+                .order(optimism_block_txs::queue_index.desc())
                 .select((
                     optimism_block_txs::l1_block_number,
                     optimism_block_txs::queue_index,
@@ -337,4 +498,255 @@ pub mod summary {
         );
         Ok(board_summary)
     }
+}
+
+pub mod l2_to_l1 {
+    use actix_web::web;
+    use diesel::prelude::*;
+    use diesel::RunQueryDsl;
+
+    use crate::config::postgres::Pool;
+    use crate::error::error::ExpectedError;
+    use crate::model::optimism::{OptimismCrossDomainMessage, OptimismSentMessage, PaginatedOptimismL2ToL1Message};
+    use crate::repository::pagination::{LoadPaginated, PaginatedRecord};
+    use crate::schema::optimism::{optimism_block_txs, optimism_cross_domain_messages, optimism_state_batches, optimism_state_roots};
+
+    pub async fn find_l2_to_l1_message_by_page_count(pool: web::Data<Pool>, page: i64, count: i64, challenge_period: i64) -> Result<PaginatedOptimismL2ToL1Message, ExpectedError> {
+        let conn = pool.get()?;
+        let paginated_message: PaginatedRecord<OptimismSentMessage> = web::block(move || {
+            optimism_cross_domain_messages::table
+                .filter(optimism_cross_domain_messages::event_name.eq("SentMessage"))
+                .left_outer_join(optimism_block_txs::table.on(optimism_cross_domain_messages::tx_hash.eq(optimism_block_txs::hash)))
+                .left_outer_join(optimism_state_roots::table.on(optimism_block_txs::index.eq(optimism_state_roots::index)))
+                .left_outer_join(optimism_state_batches::table.on(optimism_state_roots::batch_index.eq(optimism_state_batches::batch_index)))
+                .select((
+                    optimism_cross_domain_messages::all_columns,
+                    optimism_block_txs::l1_timestamp.nullable(),
+                    optimism_state_roots::batch_index.nullable(),
+                    optimism_state_batches::batch_timestamp.nullable()
+                ))
+                .order((optimism_cross_domain_messages::block_number.desc(), optimism_cross_domain_messages::log_index.desc()))
+                .load_with_pagination(&conn, page, count)
+        }).await?;
+        let msg_hashes = paginated_message.records.iter()
+            .filter_map(|sent_message| sent_message.get_msg_hash())
+            .collect::<Vec<String>>();
+        let conn = pool.get()?;
+        let relay_messages = web::block(move || {
+            optimism_cross_domain_messages::table
+                .filter(optimism_cross_domain_messages::msg_hash.eq_any(msg_hashes))
+                .filter(optimism_cross_domain_messages::event_name.ne("SentMessage"))
+                .order((optimism_cross_domain_messages::block_number.asc(), optimism_cross_domain_messages::log_index.asc()))
+                .load::<OptimismCrossDomainMessage>(&conn)
+        }).await?;
+        Ok(PaginatedOptimismL2ToL1Message::new(paginated_message, relay_messages, challenge_period))
+    }
+}
+
+pub mod bridge {
+    use actix_web::web;
+    use diesel::prelude::*;
+    use diesel::RunQueryDsl;
+
+    use crate::config::postgres::Pool;
+    use crate::error::error::ExpectedError;
+    use crate::model::optimism::OptimismBridgeTransferPaging;
+    use crate::repository::pagination::{LoadPaginated, PaginatedRecord};
+    use crate::schema::ethereum::ethereum_tx_logs;
+    use crate::schema::optimism::{optimism_block_txs, optimism_bridge_transfers, optimism_cross_domain_messages};
+
+    pub async fn find_bridge_transfer_by_page_count(pool: web::Data<Pool>, page: i64, count: i64) -> Result<PaginatedRecord<OptimismBridgeTransferPaging>, ExpectedError> {
+        let conn = pool.get()?;
+        let paginated_transfer = web::block(move || {
+            optimism_bridge_transfers::table
+                .left_outer_join(optimism_block_txs::table.on(
+                    optimism_block_txs::queue_index.eq(optimism_bridge_transfers::queue_index).and(optimism_block_txs::queue_origin.eq("l1"))
+                        .or(optimism_block_txs::hash.eq(optimism_bridge_transfers::tx_hash))
+                ))
+                .left_outer_join(ethereum_tx_logs::table.on(optimism_block_txs::queue_index.eq(ethereum_tx_logs::queue_index)))
+                .left_outer_join(optimism_cross_domain_messages::table.on(
+                    optimism_cross_domain_messages::msg_hash.eq(optimism_bridge_transfers::msg_hash).and(optimism_cross_domain_messages::event_name.eq("RelayedMessage"))
+                ))
+                .select((
+                    optimism_bridge_transfers::all_columns,
+                    optimism_block_txs::hash.nullable(),
+                    ethereum_tx_logs::tx_hash.nullable(),
+                    optimism_cross_domain_messages::tx_hash.nullable()
+                ))
+                .order((optimism_bridge_transfers::block_number.desc(), optimism_bridge_transfers::log_index.desc()))
+                .load_with_pagination(&conn, page, count)
+        }).await?;
+        Ok(paginated_transfer)
+    }
+
+    pub async fn find_bridge_transfer_by_address_page_count(pool: web::Data<Pool>, address: String, page: i64, count: i64) -> Result<PaginatedRecord<OptimismBridgeTransferPaging>, ExpectedError> {
+        let conn = pool.get()?;
+        let address = address.to_lowercase();
+        let paginated_transfer = web::block(move || {
+            optimism_bridge_transfers::table
+                .left_outer_join(optimism_block_txs::table.on(
+                    optimism_block_txs::queue_index.eq(optimism_bridge_transfers::queue_index).and(optimism_block_txs::queue_origin.eq("l1"))
+                        .or(optimism_block_txs::hash.eq(optimism_bridge_transfers::tx_hash))
+                ))
+                .left_outer_join(ethereum_tx_logs::table.on(optimism_block_txs::queue_index.eq(ethereum_tx_logs::queue_index)))
+                .left_outer_join(optimism_cross_domain_messages::table.on(
+                    optimism_cross_domain_messages::msg_hash.eq(optimism_bridge_transfers::msg_hash).and(optimism_cross_domain_messages::event_name.eq("RelayedMessage"))
+                ))
+                .filter(optimism_bridge_transfers::from_address.eq(address.clone()).or(optimism_bridge_transfers::to_address.eq(address)))
+                .select((
+                    optimism_bridge_transfers::all_columns,
+                    optimism_block_txs::hash.nullable(),
+                    ethereum_tx_logs::tx_hash.nullable(),
+                    optimism_cross_domain_messages::tx_hash.nullable()
+                ))
+                .order((optimism_bridge_transfers::block_number.desc(), optimism_bridge_transfers::log_index.desc()))
+                .load_with_pagination(&conn, page, count)
+        }).await?;
+        Ok(paginated_transfer)
+    }
+}
+
+pub mod chart {
+    use actix_web::web;
+    use diesel::prelude::*;
+    use diesel::RunQueryDsl;
+
+    use crate::config::postgres::Pool;
+    use crate::error::error::ExpectedError;
+    use crate::model::optimism::OptimismChainStat;
+    use crate::schema::optimism::optimism_chain_stats;
+    use crate::schema::optimism::optimism_chain_stats::columns::*;
+
+    pub async fn find_chain_stats(pool: web::Data<Pool>, stat_granularity: String, from: i64, to: i64) -> Result<Vec<OptimismChainStat>, ExpectedError> {
+        let conn = pool.get()?;
+        let chain_stats = web::block(move || {
+            optimism_chain_stats::table.filter(granularity.eq(stat_granularity))
+                .filter(bucket_timestamp.ge(from))
+                .filter(bucket_timestamp.lt(to))
+                .order(bucket_timestamp.asc())
+                .load::<OptimismChainStat>(&conn)
+        }).await?;
+        Ok(chain_stats)
+    }
+}
+
+pub mod health {
+    use std::time::Duration;
+
+    use actix_web::web;
+    use diesel::prelude::*;
+    use diesel::RunQueryDsl;
+
+    use crate::config::postgres::Pool;
+    use crate::error::error::ExpectedError;
+    use crate::schema::optimism::optimism_blocks;
+    use crate::schema::optimism::optimism_blocks::columns::*;
+
+    /*
+     * The connection is taken inside the blocking pool with `pool_timeout`, so a probe never stalls a worker for the pool's default 30 seconds.
+     */
+    pub async fn find_latest_block_timestamp(pool: web::Data<Pool>, pool_timeout: Duration) -> Result<Option<i64>, ExpectedError> {
+        let latest_block_timestamp = web::block(move || {
+            let conn = pool.get_timeout(pool_timeout)?;
+            optimism_blocks::table.select(block_timestamp)
+                .order(block_number.desc())
+                .first::<Option<i64>>(&conn)
+                .optional()
+                .map_err(ExpectedError::from)
+        }).await?;
+        Ok(latest_block_timestamp.flatten())
+    }
+}
+
+pub mod address_webhook {
+    use actix_web::web;
+    use diesel::prelude::*;
+    use diesel::RunQueryDsl;
+
+    use crate::config::postgres::Pool;
+    use crate::error::error::ExpectedError;
+    use crate::model::optimism::{NewOptimismAddressWebhook, OptimismAddressWebhook, OptimismAddressWebhookDelivery};
+    use crate::repository::pagination::{LoadPaginated, PaginatedRecord};
+    use crate::schema::optimism::{optimism_address_webhook_deliveries, optimism_address_webhooks};
+
+    pub async fn insert_webhook(pool: web::Data<Pool>, new_webhook: NewOptimismAddressWebhook) -> Result<OptimismAddressWebhook, ExpectedError> {
+        let conn = pool.get()?;
+        let webhook = web::block(move || {
+            diesel::insert_into(optimism_address_webhooks::table)
+                .values(&new_webhook)
+                .returning((
+                    optimism_address_webhooks::optimism_address_webhooks_id,
+                    optimism_address_webhooks::address,
+                    optimism_address_webhooks::direction,
+                    optimism_address_webhooks::topic,
+                    optimism_address_webhooks::callback_url,
+                    optimism_address_webhooks::active,
+                    optimism_address_webhooks::created_at,
+                ))
+                .get_result::<OptimismAddressWebhook>(&conn)
+        }).await?;
+        Ok(webhook)
+    }
+
+    pub async fn count_active_webhooks(pool: web::Data<Pool>, address: String) -> Result<i64, ExpectedError> {
+        let conn = pool.get()?;
+        let address = address.to_lowercase();
+        let count = web::block(move || {
+            optimism_address_webhooks::table
+                .filter(optimism_address_webhooks::address.eq(address))
+                .filter(optimism_address_webhooks::active.eq(true))
+                .count()
+                .get_result::<i64>(&conn)
+        }).await?;
+        Ok(count)
+    }
+
+    pub async fn find_webhooks_by_address(pool: web::Data<Pool>, address: String) -> Result<Vec<OptimismAddressWebhook>, ExpectedError> {
+        let conn = pool.get()?;
+        let address = address.to_lowercase();
+        let webhooks = web::block(move || {
+            optimism_address_webhooks::table
+                .select((
+                    optimism_address_webhooks::optimism_address_webhooks_id,
+                    optimism_address_webhooks::address,
+                    optimism_address_webhooks::direction,
+                    optimism_address_webhooks::topic,
+                    optimism_address_webhooks::callback_url,
+                    optimism_address_webhooks::active,
+                    optimism_address_webhooks::created_at,
+                ))
+                .filter(optimism_address_webhooks::address.eq(address))
+                .order(optimism_address_webhooks::optimism_address_webhooks_id.desc())
+                .load::<OptimismAddressWebhook>(&conn)
+        }).await?;
+        Ok(webhooks)
+    }
+
+    /*
+     * Deliveries are kept as the history of the deleted webhook.
+     */
+    pub async fn delete_webhook(pool: web::Data<Pool>, webhook_id: i64, webhook_secret: String) -> Result<(), ExpectedError> {
+        let conn = pool.get()?;
+        let deleted = web::block(move || {
+            diesel::delete(optimism_address_webhooks::table
+                .filter(optimism_address_webhooks::optimism_address_webhooks_id.eq(webhook_id))
+                .filter(optimism_address_webhooks::secret.eq(webhook_secret)))
+                .execute(&conn)
+        }).await?;
+        if deleted == 0 {
+            return Err(ExpectedError::InvalidError(format!("webhook not found or secret mismatched! webhook_id={}", webhook_id)));
+        }
+        Ok(())
+    }
+
+    pub async fn find_deliveries_by_webhook_page_count(pool: web::Data<Pool>, webhook_id: i64, page: i64, count: i64) -> Result<PaginatedRecord<OptimismAddressWebhookDelivery>, ExpectedError> {
+        let conn = pool.get()?;
+        let paginated_delivery = web::block(move || {
+            optimism_address_webhook_deliveries::table.into_boxed()
+                .filter(optimism_address_webhook_deliveries::webhook_id.eq(webhook_id))
+                .order(optimism_address_webhook_deliveries::optimism_address_webhook_deliveries_id.desc())
+                .load_with_pagination(&conn, page, count)
+        }).await?;
+        Ok(paginated_delivery)
+    }
 }
\ No newline at end of file
@@ -4,12 +4,12 @@ table! {
         address -> Nullable<Text>,
         topics -> Nullable<Text>,
         data -> Nullable<Text>,
-        block_number -> Nullable<Text>,
+        block_number -> Nullable<BigInt>,
         tx_hash -> Nullable<Text>,
-        tx_index -> Nullable<Text>,
+        tx_index -> Nullable<BigInt>,
         block_hash -> Nullable<Text>,
-        log_index -> Nullable<Text>,
+        log_index -> Nullable<BigInt>,
         removed -> Nullable<Bool>,
-        queue_index -> Nullable<Text>,
+        queue_index -> Nullable<BigInt>,
     }
 }
\ No newline at end of file
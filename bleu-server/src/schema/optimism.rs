@@ -3,13 +3,13 @@ use crate::schema::ethereum::ethereum_tx_logs;
 table! {
     optimism_tx_batches (optimism_tx_batches_id) {
         optimism_tx_batches_id -> BigInt,
-        batch_index -> Nullable<Text>,
-        batch_timestamp -> Nullable<Text>,
-        batch_size -> Nullable<Text>,
+        batch_index -> Nullable<BigInt>,
+        batch_timestamp -> Nullable<BigInt>,
+        batch_size -> Nullable<BigInt>,
         l1_tx_hash -> Nullable<Text>,
-        l1_block_number -> Nullable<Text>,
+        l1_block_number -> Nullable<BigInt>,
         batch_root -> Nullable<Text>,
-        previous_total_elements -> Nullable<Text>,
+        previous_total_elements -> Nullable<BigInt>,
         extra_data -> Nullable<Text>,
         submitter -> Nullable<Text>,
     }
@@ -18,17 +18,17 @@ table! {
 table! {
     optimism_txs (optimism_txs_id) {
         optimism_txs_id -> BigInt,
-        index -> Nullable<Text>,
-        batch_index -> Nullable<Text>,
-        batch_number -> Nullable<Text>,
-        tx_timestamp -> Nullable<Text>,
-        gas_limit -> Nullable<Text>,
+        index -> Nullable<BigInt>,
+        batch_index -> Nullable<BigInt>,
+        block_number -> Nullable<BigInt>,
+        tx_timestamp -> Nullable<BigInt>,
+        gas_limit -> Nullable<BigInt>,
         target -> Nullable<Text>,
         origin -> Nullable<Text>,
         data -> Nullable<Text>,
         queue_origin -> Nullable<Text>,
-        value -> Nullable<Text>,
-        queue_index -> Nullable<Text>,
+        value -> Nullable<Numeric>,
+        queue_index -> Nullable<BigInt>,
         decoded -> Nullable<Text>,
         confirmed -> Nullable<Bool>,
         l1_tx_hash -> Nullable<Text>,
@@ -38,13 +38,13 @@ table! {
 table! {
     optimism_state_batches (optimism_state_batches_id) {
         optimism_state_batches_id -> BigInt,
-        batch_index -> Nullable<Text>,
-        batch_timestamp -> Nullable<Text>,
-        batch_size -> Nullable<Text>,
+        batch_index -> Nullable<BigInt>,
+        batch_timestamp -> Nullable<BigInt>,
+        batch_size -> Nullable<BigInt>,
         l1_tx_hash -> Nullable<Text>,
-        l1_block_number -> Nullable<Text>,
+        l1_block_number -> Nullable<BigInt>,
         batch_root -> Nullable<Text>,
-        previous_total_elements -> Nullable<Text>,
+        previous_total_elements -> Nullable<BigInt>,
         extra_data -> Nullable<Text>,
         submitter -> Nullable<Text>,
     }
@@ -53,8 +53,8 @@ table! {
 table! {
     optimism_state_roots (optimism_state_roots_id) {
         optimism_state_roots_id -> BigInt,
-        index -> Nullable<Text>,
-        batch_index -> Nullable<Text>,
+        index -> Nullable<BigInt>,
+        batch_index -> Nullable<BigInt>,
         value -> Nullable<Text>,
         confirmed -> Nullable<Bool>,
         l1_tx_hash -> Nullable<Text>,
@@ -64,23 +64,23 @@ table! {
 table! {
     optimism_blocks (optimism_blocks_id) {
         optimism_blocks_id -> BigInt,
-        difficulty -> Nullable<Text>,
+        difficulty -> Nullable<Numeric>,
         extra_data -> Nullable<Text>,
-        gas_limit -> Nullable<Text>,
-        gas_used -> Nullable<Text>,
+        gas_limit -> Nullable<BigInt>,
+        gas_used -> Nullable<BigInt>,
         hash -> Nullable<Text>,
         logs_bloom -> Nullable<Text>,
         miner -> Nullable<Text>,
         mix_hash -> Nullable<Text>,
         nonce -> Nullable<Text>,
-        block_number -> Nullable<Text>,
+        block_number -> Nullable<BigInt>,
         parent_hash -> Nullable<Text>,
         receipts_root -> Nullable<Text>,
         sha3_uncles -> Nullable<Text>,
-        block_size -> Nullable<Text>,
+        block_size -> Nullable<BigInt>,
         state_root -> Nullable<Text>,
-        block_timestamp -> Nullable<Text>,
-        total_difficulty -> Nullable<Text>,
+        block_timestamp -> Nullable<BigInt>,
+        total_difficulty -> Nullable<Numeric>,
     }
 }
 
@@ -88,24 +88,24 @@ table! {
     optimism_block_txs (optimism_block_txs_id) {
         optimism_block_txs_id -> BigInt,
         block_hash -> Nullable<Text>,
-        block_number -> Nullable<Text>,
+        block_number -> Nullable<BigInt>,
         from_address -> Nullable<Text>,
-        gas -> Nullable<Text>,
-        gas_price -> Nullable<Text>,
+        gas -> Nullable<BigInt>,
+        gas_price -> Nullable<Numeric>,
         hash -> Nullable<Text>,
-        index -> Nullable<Text>,
+        index -> Nullable<BigInt>,
         tx_input -> Nullable<Text>,
-        l1_block_number -> Nullable<Text>,
-        l1_timestamp -> Nullable<Text>,
+        l1_block_number -> Nullable<BigInt>,
+        l1_timestamp -> Nullable<BigInt>,
         l1_tx_origin -> Nullable<Text>,
-        nonce -> Nullable<Text>,
-        queue_index -> Nullable<Text>,
+        nonce -> Nullable<BigInt>,
+        queue_index -> Nullable<BigInt>,
         queue_origin -> Nullable<Text>,
         raw_tx -> Nullable<Text>,
         to_address -> Nullable<Text>,
-        tx_index -> Nullable<Text>,
+        tx_index -> Nullable<BigInt>,
         tx_type -> Nullable<Text>,
-        value -> Nullable<Text>,
+        value -> Nullable<Numeric>,
     }
 }
 
@@ -113,16 +113,20 @@ table! {
     optimism_tx_receipts (optimism_tx_receipts_id) {
         optimism_tx_receipts_id -> BigInt,
         block_hash -> Nullable<Text>,
-        block_number -> Nullable<Text>,
+        block_number -> Nullable<BigInt>,
         contract_address -> Nullable<Text>,
-        cumulative_gas_used -> Nullable<Text>,
+        cumulative_gas_used -> Nullable<BigInt>,
         from_address -> Nullable<Text>,
-        gas_used -> Nullable<Text>,
+        gas_used -> Nullable<BigInt>,
         logs_bloom -> Nullable<Text>,
-        status -> Nullable<Text>,
+        status -> Nullable<BigInt>,
         to_address -> Nullable<Text>,
         tx_hash -> Nullable<Text>,
-        tx_index -> Nullable<Text>,
+        tx_index -> Nullable<BigInt>,
+        l1_fee -> Nullable<Numeric>,
+        l1_gas_used -> Nullable<Numeric>,
+        l1_gas_price -> Nullable<Numeric>,
+        l1_fee_scalar -> Nullable<Text>,
     }
 }
 
@@ -132,15 +136,109 @@ table! {
         address -> Nullable<Text>,
         topics -> Nullable<Text>,
         data -> Nullable<Text>,
-        block_number -> Nullable<Text>,
+        block_number -> Nullable<BigInt>,
         tx_hash -> Nullable<Text>,
-        tx_index -> Nullable<Text>,
+        tx_index -> Nullable<BigInt>,
         block_hash -> Nullable<Text>,
-        log_index -> Nullable<Text>,
+        log_index -> Nullable<BigInt>,
         removed -> Nullable<Bool>,
     }
 }
 
+table! {
+    optimism_state_root_mismatches (optimism_state_root_mismatches_id) {
+        optimism_state_root_mismatches_id -> BigInt,
+        index -> Nullable<BigInt>,
+        batch_index -> Nullable<BigInt>,
+        l1_tx_hash -> Nullable<Text>,
+        block_number -> Nullable<BigInt>,
+        block_hash -> Nullable<Text>,
+        expected_state_root -> Nullable<Text>,
+        actual_state_root -> Nullable<Text>,
+    }
+}
+
+table! {
+    optimism_cross_domain_messages (optimism_cross_domain_messages_id) {
+        optimism_cross_domain_messages_id -> BigInt,
+        msg_hash -> Nullable<Text>,
+        event_name -> Nullable<Text>,
+        tx_hash -> Nullable<Text>,
+        block_number -> Nullable<BigInt>,
+        block_timestamp -> Nullable<BigInt>,
+        log_index -> Nullable<BigInt>,
+        target -> Nullable<Text>,
+        sender -> Nullable<Text>,
+        message -> Nullable<Text>,
+        message_nonce -> Nullable<Numeric>,
+        gas_limit -> Nullable<BigInt>,
+    }
+}
+
+table! {
+    optimism_bridge_transfers (optimism_bridge_transfers_id) {
+        optimism_bridge_transfers_id -> BigInt,
+        event_name -> Nullable<Text>,
+        transfer_type -> Nullable<Text>,
+        l1_token -> Nullable<Text>,
+        l2_token -> Nullable<Text>,
+        from_address -> Nullable<Text>,
+        to_address -> Nullable<Text>,
+        amount -> Nullable<Numeric>,
+        extra_data -> Nullable<Text>,
+        tx_hash -> Nullable<Text>,
+        block_number -> Nullable<BigInt>,
+        block_timestamp -> Nullable<BigInt>,
+        log_index -> Nullable<BigInt>,
+        queue_index -> Nullable<BigInt>,
+        msg_hash -> Nullable<Text>,
+    }
+}
+
+table! {
+    optimism_chain_stats (optimism_chain_stats_id) {
+        optimism_chain_stats_id -> BigInt,
+        granularity -> Nullable<Text>,
+        bucket_timestamp -> Nullable<BigInt>,
+        tx_count -> Nullable<BigInt>,
+        active_addresses -> Nullable<BigInt>,
+        new_contracts -> Nullable<BigInt>,
+        gas_used -> Nullable<Numeric>,
+        avg_gas_price -> Nullable<Numeric>,
+        deposit_count -> Nullable<BigInt>,
+        tx_batch_count -> Nullable<BigInt>,
+        state_batch_count -> Nullable<BigInt>,
+    }
+}
+
+table! {
+    optimism_address_webhooks (optimism_address_webhooks_id) {
+        optimism_address_webhooks_id -> BigInt,
+        address -> Text,
+        direction -> Text,
+        topic -> Nullable<Text>,
+        callback_url -> Text,
+        secret -> Text,
+        active -> Bool,
+        created_at -> BigInt,
+    }
+}
+
+table! {
+    optimism_address_webhook_deliveries (optimism_address_webhook_deliveries_id) {
+        optimism_address_webhook_deliveries_id -> BigInt,
+        webhook_id -> BigInt,
+        event -> Text,
+        tx_hash -> Text,
+        log_index -> Nullable<Text>,
+        status -> Text,
+        attempt -> BigInt,
+        response_status -> Nullable<BigInt>,
+        error -> Nullable<Text>,
+        delivered_at -> BigInt,
+    }
+}
+
 joinable_inner!(
     left_table_ty = optimism_txs::table,
     right_table_ty = optimism_block_txs::table,
@@ -186,4 +284,4 @@ joinable_inner!(
     primary_key_expr = optimism_block_txs::dsl::hash,
 );
 
-allow_tables_to_appear_in_same_query!(optimism_block_txs, optimism_txs, optimism_state_roots, ethereum_tx_logs, optimism_tx_receipts);
+allow_tables_to_appear_in_same_query!(optimism_block_txs, optimism_txs, optimism_state_roots, ethereum_tx_logs, optimism_tx_receipts, optimism_blocks, optimism_tx_batches, optimism_state_batches, optimism_cross_domain_messages, optimism_bridge_transfers);
@@ -1,2 +1,4 @@
 pub mod optimism;
-pub mod swagger;
\ No newline at end of file
+pub mod swagger;
+pub mod metrics;
+pub mod health;
\ No newline at end of file
@@ -0,0 +1,44 @@
+use actix_web::{HttpResponse, web};
+
+use crate::config::health::HealthConfig;
+use crate::config::postgres::Pool;
+use crate::error::error::ExpectedError;
+use crate::libs::time::current_timestamp;
+use crate::model::health::{HealthCheck, HealthReport};
+use crate::repository::optimism;
+
+pub async fn get_health() -> HttpResponse {
+    HttpResponse::Ok().json(HealthReport::new(vec![HealthCheck::new("process", Ok(()))]))
+}
+
+/*
+ * Ready when a connection can be taken from the pool within `READY_POOL_TIMEOUT` and the latest indexed block is fresher than the threshold.
+ * Both checks share one connection and one blocking call.
+ */
+pub async fn get_ready(pool: web::Data<Pool>, health_config: web::Data<HealthConfig>) -> HttpResponse {
+    let (postgres_check, freshness_check) = match optimism::health::find_latest_block_timestamp(pool, health_config.get_pool_timeout()).await {
+        Ok(block_timestamp) => (Ok(()), check_freshness(block_timestamp, health_config.get_freshness_threshold())),
+        Err(err) => (Err(err), Err(ExpectedError::InternalError(String::from("postgres is not reachable!")))),
+    };
+    let report = HealthReport::new(vec![
+        HealthCheck::new("postgres", postgres_check),
+        HealthCheck::new("freshness", freshness_check),
+    ]);
+    if report.is_ok() {
+        HttpResponse::Ok().json(report)
+    } else {
+        HttpResponse::ServiceUnavailable().json(report)
+    }
+}
+
+fn check_freshness(block_timestamp: Option<i64>, freshness_threshold: i64) -> Result<(), ExpectedError> {
+    let block_timestamp = match block_timestamp {
+        Some(block_timestamp) => block_timestamp,
+        None => return Err(ExpectedError::InternalError(String::from("no block indexed yet!"))),
+    };
+    let elapsed = current_timestamp() - block_timestamp;
+    if elapsed > freshness_threshold {
+        return Err(ExpectedError::InternalError(format!("indexed data is stale! elapsed={}, threshold={}", elapsed, freshness_threshold)));
+    }
+    Ok(())
+}
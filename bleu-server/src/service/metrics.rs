@@ -0,0 +1,15 @@
+use actix_web::{HttpResponse, web};
+
+use crate::config::postgres::Pool;
+use crate::libs::metrics;
+use crate::repository::optimism::{l1_to_l2, summary, tx_batch};
+
+pub async fn get_metrics(pool: web::Data<Pool>) -> HttpResponse {
+    metrics::observe_pool(&pool);
+    metrics::observe_cache("find_latest_batch_summary", &*tx_batch::FIND_LATEST_BATCH_SUMMARY.lock().await);
+    metrics::observe_cache("find_latest_l1_to_l2_tx_summary", &*l1_to_l2::FIND_LATEST_L1_TO_L2_TX_SUMMARY.lock().await);
+    metrics::observe_cache("find_board_summary", &*summary::FIND_BOARD_SUMMARY.lock().await);
+    HttpResponse::Ok()
+        .content_type(prometheus::TEXT_FORMAT)
+        .body(metrics::gather())
+}
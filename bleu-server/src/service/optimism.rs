@@ -1,7 +1,10 @@
+use actix_web::{HttpRequest, HttpResponse};
 use paperclip::actix::{api_v2_operation, web};
 use paperclip::actix::web::Json;
 
+use crate::config::finality::FinalityConfig;
 use crate::config::postgres::Pool;
+use crate::config::webhook::WebhookConfig;
 use crate::error::error::ExpectedError;
 use crate::model::optimism::*;
 use crate::model::pagination::RequestPage;
@@ -29,38 +32,38 @@ pub async fn get_latest_tx_summary(pool: web::Data<Pool>) -> Result<Json<Vec<Opt
 }
 
 #[api_v2_operation(tags(Tx))]
-pub async fn get_tx_by_hash(pool: web::Data<Pool>, path_params: web::Path<String>) -> Result<Json<OptimismBlockTxDetail>, ExpectedError> {
+pub async fn get_tx_by_hash(pool: web::Data<Pool>, finality_config: web::Data<FinalityConfig>, path_params: web::Path<String>) -> Result<Json<OptimismBlockTxDetail>, ExpectedError> {
     let hash = path_params.into_inner();
-    Ok(Json(optimism::tx::find_tx_by_hash(pool, hash).await?))
+    Ok(Json(optimism::tx::find_tx_by_hash(pool, hash, finality_config.get_challenge_period()).await?))
 }
 
 #[api_v2_operation(tags(Tx))]
-pub async fn get_tx_by_index(pool: web::Data<Pool>, path_params: web::Path<i64>) -> Result<Json<OptimismBlockTxDetail>, ExpectedError> {
+pub async fn get_tx_by_index(pool: web::Data<Pool>, finality_config: web::Data<FinalityConfig>, path_params: web::Path<i64>) -> Result<Json<OptimismBlockTxDetail>, ExpectedError> {
     let batch_tx_index = path_params.into_inner();
-    Ok(Json(optimism::tx::find_tx_by_index(pool, batch_tx_index).await?))
+    Ok(Json(optimism::tx::find_tx_by_index(pool, batch_tx_index, finality_config.get_challenge_period()).await?))
 }
 
 #[api_v2_operation(tags(Tx))]
-pub async fn get_paginated_tx_by_tx_batch_index(pool: web::Data<Pool>, path_params: web::Path<i64>, req_page: web::Query<RequestPage>) -> Result<Json<PaginatedOptimismBlockTx>, ExpectedError> {
+pub async fn get_paginated_tx_by_tx_batch_index(pool: web::Data<Pool>, finality_config: web::Data<FinalityConfig>, path_params: web::Path<i64>, req_page: web::Query<RequestPage>, req_status: web::Query<RequestTxStatus>) -> Result<Json<PaginatedOptimismBlockTx>, ExpectedError> {
     let tx_batch_index = path_params.into_inner();
-    Ok(Json(PaginatedOptimismBlockTx::new(optimism::tx::find_tx_by_tx_batch_index_page_count(pool, tx_batch_index, req_page.page, req_page.count).await?)))
+    Ok(Json(PaginatedOptimismBlockTx::new(optimism::tx::find_tx_by_tx_batch_index_page_count(pool, tx_batch_index, req_page.page, req_page.count, req_status.into_inner().status, finality_config.get_challenge_period()).await?)))
 }
 
 #[api_v2_operation(tags(Tx))]
-pub async fn get_paginated_tx_by_state_batch_index(pool: web::Data<Pool>, path_params: web::Path<i64>, req_page: web::Query<RequestPage>) -> Result<Json<PaginatedOptimismBlockTx>, ExpectedError> {
+pub async fn get_paginated_tx_by_state_batch_index(pool: web::Data<Pool>, finality_config: web::Data<FinalityConfig>, path_params: web::Path<i64>, req_page: web::Query<RequestPage>, req_status: web::Query<RequestTxStatus>) -> Result<Json<PaginatedOptimismBlockTx>, ExpectedError> {
     let state_batch_index = path_params.into_inner();
-    Ok(Json(PaginatedOptimismBlockTx::new(optimism::tx::find_tx_by_state_batch_index_page_count(pool, state_batch_index, req_page.page, req_page.count).await?)))
+    Ok(Json(PaginatedOptimismBlockTx::new(optimism::tx::find_tx_by_state_batch_index_page_count(pool, state_batch_index, req_page.page, req_page.count, req_status.into_inner().status, finality_config.get_challenge_period()).await?)))
 }
 
 #[api_v2_operation(tags(Tx))]
-pub async fn get_paginated_tx_by_address(pool: web::Data<Pool>, path_params: web::Path<String>, req_page: web::Query<RequestPage>) -> Result<Json<PaginatedOptimismBlockTx>, ExpectedError> {
+pub async fn get_paginated_tx_by_address(pool: web::Data<Pool>, finality_config: web::Data<FinalityConfig>, path_params: web::Path<String>, req_page: web::Query<RequestPage>, req_status: web::Query<RequestTxStatus>) -> Result<Json<PaginatedOptimismBlockTx>, ExpectedError> {
     let address = path_params.into_inner();
-    Ok(Json(PaginatedOptimismBlockTx::new(optimism::tx::find_tx_by_address_page_count(pool, address, req_page.page, req_page.count).await?)))
+    Ok(Json(PaginatedOptimismBlockTx::new(optimism::tx::find_tx_by_address_page_count(pool, address, req_page.page, req_page.count, req_status.into_inner().status, finality_config.get_challenge_period()).await?)))
 }
 
 #[api_v2_operation(tags(Tx))]
-pub async fn get_paginated_tx(pool: web::Data<Pool>, req_page: web::Query<RequestPage>) -> Result<Json<PaginatedOptimismBlockTx>, ExpectedError> {
-    Ok(Json(PaginatedOptimismBlockTx::new(optimism::tx::find_tx_by_page_count(pool, req_page.page, req_page.count).await?)))
+pub async fn get_paginated_tx(pool: web::Data<Pool>, finality_config: web::Data<FinalityConfig>, req_page: web::Query<RequestPage>, req_status: web::Query<RequestTxStatus>) -> Result<Json<PaginatedOptimismBlockTx>, ExpectedError> {
+    Ok(Json(PaginatedOptimismBlockTx::new(optimism::tx::find_tx_by_page_count(pool, req_page.page, req_page.count, req_status.into_inner().status, finality_config.get_challenge_period()).await?)))
 }
 
 #[api_v2_operation(tags(StateRootBatch))]
@@ -74,6 +77,24 @@ pub async fn get_state_batch_by_index(pool: web::Data<Pool>, path_params: web::P
     Ok(Json(optimism::state_batch::find_batch_by_index(pool, index).await?))
 }
 
+#[api_v2_operation(tags(StateRootBatch))]
+pub async fn get_state_batch_verification_by_index(pool: web::Data<Pool>, path_params: web::Path<i64>) -> Result<Json<OptimismStateBatchVerification>, ExpectedError> {
+    let index = path_params.into_inner();
+    Ok(Json(optimism::state_root_verification::find_verification_by_batch_index(pool, index).await?))
+}
+
+#[api_v2_operation(tags(Proof))]
+pub async fn get_tx_proof_by_index(pool: web::Data<Pool>, path_params: web::Path<i64>) -> Result<Json<OptimismMerkleProof>, ExpectedError> {
+    let index = path_params.into_inner();
+    Ok(Json(optimism::proof::find_tx_proof_by_index(pool, index).await?))
+}
+
+#[api_v2_operation(tags(Proof))]
+pub async fn get_state_root_proof_by_index(pool: web::Data<Pool>, path_params: web::Path<i64>) -> Result<Json<OptimismMerkleProof>, ExpectedError> {
+    let index = path_params.into_inner();
+    Ok(Json(optimism::proof::find_state_root_proof_by_index(pool, index).await?))
+}
+
 #[api_v2_operation(tags(L1ToL2))]
 pub async fn get_latest_l1_to_l2_tx_summary(pool: web::Data<Pool>) -> Result<Json<Vec<OptimismL1ToL2TxSummary>>, ExpectedError> {
     Ok(Json(optimism::l1_to_l2::find_latest_l1_to_l2_tx_summary(pool).await?))
@@ -84,6 +105,22 @@ pub async fn get_l1_to_l2_tx_by_page_count(pool: web::Data<Pool>, req_page: web:
     Ok(Json(PaginatedOptimismL1ToL2Tx::new(optimism::l1_to_l2::find_l1_to_l2_tx_by_page_count(pool, req_page.page, req_page.count).await?)))
 }
 
+#[api_v2_operation(tags(L2ToL1))]
+pub async fn get_l2_to_l1_message_by_page_count(pool: web::Data<Pool>, finality_config: web::Data<FinalityConfig>, req_page: web::Query<RequestPage>) -> Result<Json<PaginatedOptimismL2ToL1Message>, ExpectedError> {
+    Ok(Json(optimism::l2_to_l1::find_l2_to_l1_message_by_page_count(pool, req_page.page, req_page.count, finality_config.get_challenge_period()).await?))
+}
+
+#[api_v2_operation(tags(Bridge))]
+pub async fn get_paginated_bridge_transfer(pool: web::Data<Pool>, req_page: web::Query<RequestPage>) -> Result<Json<PaginatedOptimismBridgeTransfer>, ExpectedError> {
+    Ok(Json(PaginatedOptimismBridgeTransfer::new(optimism::bridge::find_bridge_transfer_by_page_count(pool, req_page.page, req_page.count).await?)))
+}
+
+#[api_v2_operation(tags(Bridge))]
+pub async fn get_paginated_bridge_transfer_by_address(pool: web::Data<Pool>, path_params: web::Path<String>, req_page: web::Query<RequestPage>) -> Result<Json<PaginatedOptimismBridgeTransfer>, ExpectedError> {
+    let address = path_params.into_inner();
+    Ok(Json(PaginatedOptimismBridgeTransfer::new(optimism::bridge::find_bridge_transfer_by_address_page_count(pool, address, req_page.page, req_page.count).await?)))
+}
+
 #[api_v2_operation(tags(TxLogs))]
 pub async fn get_tx_logs_by_hash(pool: web::Data<Pool>, path_params: web::Path<String>) -> Result<Json<Vec<OptimismTxLog>>, ExpectedError> {
     let hash = path_params.into_inner();
@@ -95,4 +132,63 @@ pub async fn get_tx_logs_by_hash(pool: web::Data<Pool>, path_params: web::Path<S
 pub async fn get_board_summary(pool: web::Data<Pool>) -> Result<Json<BoardSummary>, ExpectedError> {
     let board_summary = optimism::summary::find_board_summary(pool).await?;
     Ok(Json(board_summary))
+}
+
+#[api_v2_operation(tags(Chart))]
+pub async fn get_chain_stats(pool: web::Data<Pool>, req_stat: web::Query<RequestChainStat>) -> Result<Json<Vec<OptimismChainStat>>, ExpectedError> {
+    let (granularity, from, to) = req_stat.get_range()?;
+    Ok(Json(optimism::chart::find_chain_stats(pool, granularity.value(), from, to).await?))
+}
+
+#[api_v2_operation(tags(Chart))]
+pub async fn get_chain_stat_by_metric(pool: web::Data<Pool>, path_params: web::Path<String>, req_stat: web::Query<RequestChainStat>) -> Result<Json<Vec<OptimismChartPoint>>, ExpectedError> {
+    let metric = path_params.into_inner();
+    let (granularity, from, to) = req_stat.get_range()?;
+    let chain_stats = optimism::chart::find_chain_stats(pool, granularity.value(), from, to).await?;
+    let chart_points = chain_stats.iter()
+        .map(|chain_stat| chain_stat.get_chart_point(metric.as_str()))
+        .collect::<Result<Vec<OptimismChartPoint>, ExpectedError>>()?;
+    Ok(Json(chart_points))
+}
+
+/*
+ * The callback host must resolve to public addresses only, and an address has at most `MAX_WEBHOOKS_PER_ADDRESS` active subscriptions.
+ */
+#[api_v2_operation(tags(Webhook))]
+pub async fn create_address_webhook(pool: web::Data<Pool>, webhook_config: web::Data<WebhookConfig>, req_webhook: Json<RequestAddressWebhook>) -> Result<Json<CreatedOptimismAddressWebhook>, ExpectedError> {
+    let secret = hex::encode(rand::random::<[u8; 32]>());
+    let new_webhook = req_webhook.to_new_webhook(secret.clone())?;
+    let callback_url = req_webhook.callback_url.clone();
+    actix_web::web::block(move || bleu_net::resolve_public(&callback_url).map(|_| ()).map_err(ExpectedError::from)).await?;
+    let max_webhooks = webhook_config.get_max_webhooks_per_address();
+    if optimism::address_webhook::count_active_webhooks(pool.clone(), req_webhook.address.clone()).await? >= max_webhooks {
+        return Err(ExpectedError::InvalidError(format!("too many webhooks for the address! address={}, max={}", req_webhook.address, max_webhooks)));
+    }
+    let webhook = optimism::address_webhook::insert_webhook(pool, new_webhook).await?;
+    Ok(Json(CreatedOptimismAddressWebhook::new(webhook, secret)))
+}
+
+#[api_v2_operation(tags(Webhook))]
+pub async fn get_address_webhooks_by_address(pool: web::Data<Pool>, path_params: web::Path<String>) -> Result<Json<Vec<OptimismAddressWebhook>>, ExpectedError> {
+    let address = path_params.into_inner();
+    Ok(Json(optimism::address_webhook::find_webhooks_by_address(pool, address).await?))
+}
+
+/*
+ * The secret returned on creation is required in the `x-webhook-secret` header.
+ */
+#[api_v2_operation(tags(Webhook))]
+pub async fn delete_address_webhook(pool: web::Data<Pool>, req: HttpRequest, path_params: web::Path<i64>) -> Result<HttpResponse, ExpectedError> {
+    let webhook_id = path_params.into_inner();
+    let secret = req.headers().get("x-webhook-secret")
+        .and_then(|secret| secret.to_str().ok())
+        .ok_or(ExpectedError::InvalidError(String::from("x-webhook-secret header is required!")))?;
+    optimism::address_webhook::delete_webhook(pool, webhook_id, String::from(secret)).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[api_v2_operation(tags(Webhook))]
+pub async fn get_paginated_address_webhook_delivery(pool: web::Data<Pool>, path_params: web::Path<i64>, req_page: web::Query<RequestPage>) -> Result<Json<PaginatedOptimismAddressWebhookDelivery>, ExpectedError> {
+    let webhook_id = path_params.into_inner();
+    Ok(Json(PaginatedOptimismAddressWebhookDelivery::new(optimism::address_webhook::find_deliveries_by_webhook_page_count(pool, webhook_id, req_page.page, req_page.count).await?)))
 }
\ No newline at end of file